@@ -0,0 +1,7 @@
+ALTER TABLE images ADD COLUMN content_type TEXT NOT NULL DEFAULT '';
+ALTER TABLE images ADD COLUMN size INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE images ADD COLUMN deleted INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE image_revisions ADD COLUMN content_type TEXT NOT NULL DEFAULT '';
+ALTER TABLE image_revisions ADD COLUMN size INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE image_revisions ADD COLUMN deleted INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,10 @@
+CREATE TABLE gallery_items (
+  project_data_id INTEGER NOT NULL,
+  position INTEGER NOT NULL,
+  project_id INTEGER NOT NULL,
+  filename TEXT NOT NULL,
+  caption TEXT NOT NULL,
+  FOREIGN KEY(project_data_id) REFERENCES project_data(project_data_id),
+  FOREIGN KEY(project_id, filename) REFERENCES images(project_id, filename),
+  UNIQUE(project_data_id, position)
+);
@@ -0,0 +1,73 @@
+CREATE TABLE admins(
+  user_id INTEGER NOT NULL,
+  FOREIGN KEY(user_id) REFERENCES users(user_id),
+  UNIQUE(user_id)
+);
+
+ALTER TABLE projects ADD COLUMN deleted_at INTEGER;
+ALTER TABLE projects ADD COLUMN deleted_by INTEGER REFERENCES users(user_id);
+
+/* Deleted projects are kept out of the full-text index */
+
+DROP TRIGGER projects_ad;
+DROP TRIGGER projects_au;
+
+CREATE TRIGGER projects_ad AFTER DELETE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme
+  WHERE old.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_au AFTER UPDATE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme
+  WHERE old.deleted_at IS NULL;
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme
+  WHERE new.deleted_at IS NULL;
+END;
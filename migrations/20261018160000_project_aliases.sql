@@ -0,0 +1,7 @@
+CREATE TABLE project_aliases (
+  name TEXT NOT NULL,
+  project_id INTEGER NOT NULL,
+  created_at INTEGER NOT NULL,
+  FOREIGN KEY(project_id) REFERENCES projects(project_id),
+  UNIQUE(name)
+);
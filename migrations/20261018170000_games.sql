@@ -0,0 +1,40 @@
+CREATE TABLE games (
+  game_id INTEGER PRIMARY KEY NOT NULL,
+  title TEXT NOT NULL,
+  title_sort TEXT NOT NULL,
+  publisher TEXT NOT NULL,
+  year TEXT NOT NULL,
+  UNIQUE(title, publisher, year)
+);
+
+/* Projects and project data keep their copies of the game fields for
+   full-text search and for revisions */
+ALTER TABLE projects ADD COLUMN game_id INTEGER REFERENCES games(game_id);
+ALTER TABLE project_data ADD COLUMN game_id INTEGER REFERENCES games(game_id);
+
+/* Existing projects are the same game if they have the same title,
+   publisher, and year */
+INSERT INTO games (title, title_sort, publisher, year)
+SELECT game_title, MIN(game_title_sort), game_publisher, game_year
+FROM project_data
+GROUP BY game_title, game_publisher, game_year;
+
+UPDATE projects
+SET game_id = (
+  SELECT game_id
+  FROM games
+  WHERE games.title = projects.game_title
+    AND games.publisher = projects.game_publisher
+    AND games.year = projects.game_year
+);
+
+UPDATE project_data
+SET game_id = (
+  SELECT game_id
+  FROM games
+  WHERE games.title = project_data.game_title
+    AND games.publisher = project_data.game_publisher
+    AND games.year = project_data.game_year
+);
+
+CREATE INDEX projects_game_id ON projects(game_id);
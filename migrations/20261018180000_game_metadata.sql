@@ -0,0 +1,163 @@
+/* Designers are stored as a JSON array of names */
+
+ALTER TABLE projects ADD COLUMN game_designers TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE projects ADD COLUMN game_min_players INTEGER;
+ALTER TABLE projects ADD COLUMN game_max_players INTEGER;
+ALTER TABLE projects ADD COLUMN game_playing_time INTEGER;
+ALTER TABLE projects ADD COLUMN game_era TEXT;
+ALTER TABLE projects ADD COLUMN game_external_id TEXT;
+
+ALTER TABLE project_data ADD COLUMN game_designers TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE project_data ADD COLUMN game_min_players INTEGER;
+ALTER TABLE project_data ADD COLUMN game_max_players INTEGER;
+ALTER TABLE project_data ADD COLUMN game_playing_time INTEGER;
+ALTER TABLE project_data ADD COLUMN game_era TEXT;
+ALTER TABLE project_data ADD COLUMN game_external_id TEXT;
+
+CREATE INDEX projects_game_external_id ON projects(game_external_id);
+
+/* Rebuild the full-text index with the textual game fields */
+
+DROP TRIGGER projects_ai;
+DROP TRIGGER projects_ad;
+DROP TRIGGER projects_au;
+DROP TABLE projects_fts;
+
+CREATE VIRTUAL TABLE projects_fts USING fts5(
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  content="projects",
+  content_rowid="project_id"
+);
+
+/* Set weight for game title to 100 */
+INSERT INTO projects_fts(
+  projects_fts,
+  rank
+) VALUES(
+  'rank',
+  'bm25(100.0)'
+);
+
+INSERT INTO projects_fts (
+  rowid,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era
+)
+SELECT
+  project_id,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era
+FROM projects
+WHERE deleted_at IS NULL;
+
+CREATE TRIGGER projects_ai AFTER INSERT ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era
+  WHERE new.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_ad AFTER DELETE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era
+  WHERE old.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_au AFTER UPDATE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era
+  WHERE old.deleted_at IS NULL;
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era
+  WHERE new.deleted_at IS NULL;
+END;
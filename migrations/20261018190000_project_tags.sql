@@ -0,0 +1,4 @@
+/* Tags are stored as a JSON array */
+
+ALTER TABLE projects ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE project_data ADD COLUMN tags TEXT NOT NULL DEFAULT '[]';
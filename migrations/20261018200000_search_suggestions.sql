@@ -0,0 +1,89 @@
+/* Prefix index of project names, game titles, and publishers for
+   search-as-you-type suggestions */
+
+CREATE VIRTUAL TABLE search_suggestions_fts USING fts5(
+  name,
+  game_title,
+  game_publisher,
+  content="projects",
+  content_rowid="project_id",
+  prefix="1 2 3"
+);
+
+INSERT INTO search_suggestions_fts (
+  rowid,
+  name,
+  game_title,
+  game_publisher
+)
+SELECT
+  project_id,
+  name,
+  game_title,
+  game_publisher
+FROM projects
+WHERE deleted_at IS NULL;
+
+CREATE TRIGGER search_suggestions_ai AFTER INSERT ON projects
+BEGIN
+  INSERT INTO search_suggestions_fts (
+    rowid,
+    name,
+    game_title,
+    game_publisher
+  )
+  SELECT
+    new.project_id,
+    new.name,
+    new.game_title,
+    new.game_publisher
+  WHERE new.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER search_suggestions_ad AFTER DELETE ON projects
+BEGIN
+  INSERT INTO search_suggestions_fts (
+    search_suggestions_fts,
+    rowid,
+    name,
+    game_title,
+    game_publisher
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.name,
+    old.game_title,
+    old.game_publisher
+  WHERE old.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER search_suggestions_au AFTER UPDATE ON projects
+BEGIN
+  INSERT INTO search_suggestions_fts (
+    search_suggestions_fts,
+    rowid,
+    name,
+    game_title,
+    game_publisher
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.name,
+    old.game_title,
+    old.game_publisher
+  WHERE old.deleted_at IS NULL;
+  INSERT INTO search_suggestions_fts (
+    rowid,
+    name,
+    game_title,
+    game_publisher
+  )
+  SELECT
+    new.project_id,
+    new.name,
+    new.game_title,
+    new.game_publisher
+  WHERE new.deleted_at IS NULL;
+END;
@@ -0,0 +1,403 @@
+/* Package names, release filenames, and owner usernames are copied into
+   projects so that the full-text index can cover them; the triggers on
+   packages, releases, owners, and users keep the copies current */
+
+ALTER TABLE projects ADD COLUMN package_names TEXT NOT NULL DEFAULT '';
+ALTER TABLE projects ADD COLUMN release_filenames TEXT NOT NULL DEFAULT '';
+ALTER TABLE projects ADD COLUMN owner_usernames TEXT NOT NULL DEFAULT '';
+
+UPDATE projects
+SET
+  package_names = (
+    SELECT COALESCE(group_concat(name, ' '), '')
+    FROM (
+      SELECT packages.name
+      FROM packages
+      WHERE packages.project_id = projects.project_id
+      ORDER BY packages.name
+    )
+  ),
+  release_filenames = (
+    SELECT COALESCE(group_concat(filename, ' '), '')
+    FROM (
+      SELECT releases.filename
+      FROM releases
+      JOIN packages
+      ON releases.package_id = packages.package_id
+      WHERE packages.project_id = projects.project_id
+      ORDER BY releases.filename
+    )
+  ),
+  owner_usernames = (
+    SELECT COALESCE(group_concat(username, ' '), '')
+    FROM (
+      SELECT users.username
+      FROM owners
+      JOIN users
+      ON owners.user_id = users.user_id
+      WHERE owners.project_id = projects.project_id
+      ORDER BY users.username
+    )
+  );
+
+CREATE TRIGGER packages_ai AFTER INSERT ON packages
+BEGIN
+  UPDATE projects
+  SET
+    package_names = (
+      SELECT COALESCE(group_concat(name, ' '), '')
+      FROM (
+        SELECT packages.name
+        FROM packages
+        WHERE packages.project_id = projects.project_id
+        ORDER BY packages.name
+      )
+    )
+  WHERE project_id = new.project_id;
+END;
+
+CREATE TRIGGER packages_ad AFTER DELETE ON packages
+BEGIN
+  UPDATE projects
+  SET
+    package_names = (
+      SELECT COALESCE(group_concat(name, ' '), '')
+      FROM (
+        SELECT packages.name
+        FROM packages
+        WHERE packages.project_id = projects.project_id
+        ORDER BY packages.name
+      )
+    )
+  WHERE project_id = old.project_id;
+END;
+
+CREATE TRIGGER packages_au AFTER UPDATE ON packages
+BEGIN
+  UPDATE projects
+  SET
+    package_names = (
+      SELECT COALESCE(group_concat(name, ' '), '')
+      FROM (
+        SELECT packages.name
+        FROM packages
+        WHERE packages.project_id = projects.project_id
+        ORDER BY packages.name
+      )
+    ),
+    release_filenames = (
+      SELECT COALESCE(group_concat(filename, ' '), '')
+      FROM (
+        SELECT releases.filename
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = projects.project_id
+        ORDER BY releases.filename
+      )
+    )
+  WHERE project_id IN (old.project_id, new.project_id);
+END;
+
+CREATE TRIGGER releases_ai AFTER INSERT ON releases
+BEGIN
+  UPDATE projects
+  SET
+    release_filenames = (
+      SELECT COALESCE(group_concat(filename, ' '), '')
+      FROM (
+        SELECT releases.filename
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = projects.project_id
+        ORDER BY releases.filename
+      )
+    )
+  WHERE project_id IN (
+    SELECT project_id
+    FROM packages
+    WHERE package_id = new.package_id
+  );
+END;
+
+CREATE TRIGGER releases_ad AFTER DELETE ON releases
+BEGIN
+  UPDATE projects
+  SET
+    release_filenames = (
+      SELECT COALESCE(group_concat(filename, ' '), '')
+      FROM (
+        SELECT releases.filename
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = projects.project_id
+        ORDER BY releases.filename
+      )
+    )
+  WHERE project_id IN (
+    SELECT project_id
+    FROM packages
+    WHERE package_id = old.package_id
+  );
+END;
+
+CREATE TRIGGER releases_au AFTER UPDATE ON releases
+BEGIN
+  UPDATE projects
+  SET
+    release_filenames = (
+      SELECT COALESCE(group_concat(filename, ' '), '')
+      FROM (
+        SELECT releases.filename
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = projects.project_id
+        ORDER BY releases.filename
+      )
+    )
+  WHERE project_id IN (
+    SELECT project_id
+    FROM packages
+    WHERE package_id IN (old.package_id, new.package_id)
+  );
+END;
+
+CREATE TRIGGER owners_ai AFTER INSERT ON owners
+BEGIN
+  UPDATE projects
+  SET
+    owner_usernames = (
+      SELECT COALESCE(group_concat(username, ' '), '')
+      FROM (
+        SELECT users.username
+        FROM owners
+        JOIN users
+        ON owners.user_id = users.user_id
+        WHERE owners.project_id = projects.project_id
+        ORDER BY users.username
+      )
+    )
+  WHERE project_id = new.project_id;
+END;
+
+CREATE TRIGGER owners_ad AFTER DELETE ON owners
+BEGIN
+  UPDATE projects
+  SET
+    owner_usernames = (
+      SELECT COALESCE(group_concat(username, ' '), '')
+      FROM (
+        SELECT users.username
+        FROM owners
+        JOIN users
+        ON owners.user_id = users.user_id
+        WHERE owners.project_id = projects.project_id
+        ORDER BY users.username
+      )
+    )
+  WHERE project_id = old.project_id;
+END;
+
+CREATE TRIGGER users_au AFTER UPDATE OF username ON users
+BEGIN
+  UPDATE projects
+  SET
+    owner_usernames = (
+      SELECT COALESCE(group_concat(username, ' '), '')
+      FROM (
+        SELECT users.username
+        FROM owners
+        JOIN users
+        ON owners.user_id = users.user_id
+        WHERE owners.project_id = projects.project_id
+        ORDER BY users.username
+      )
+    )
+  WHERE project_id IN (
+    SELECT project_id
+    FROM owners
+    WHERE user_id = new.user_id
+  );
+END;
+
+/* Rebuild the full-text index with the new columns */
+
+DROP TRIGGER projects_ai;
+DROP TRIGGER projects_ad;
+DROP TRIGGER projects_au;
+DROP TABLE projects_fts;
+
+CREATE VIRTUAL TABLE projects_fts USING fts5(
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames,
+  content="projects",
+  content_rowid="project_id"
+);
+
+/* Set weight for game title to 100 */
+INSERT INTO projects_fts(
+  projects_fts,
+  rank
+) VALUES(
+  'rank',
+  'bm25(100.0)'
+);
+
+INSERT INTO projects_fts (
+  rowid,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames
+)
+SELECT
+  project_id,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames
+FROM projects
+WHERE deleted_at IS NULL;
+
+CREATE TRIGGER projects_ai AFTER INSERT ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era,
+    new.package_names,
+    new.release_filenames,
+    new.owner_usernames
+  WHERE new.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_ad AFTER DELETE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era,
+    old.package_names,
+    old.release_filenames,
+    old.owner_usernames
+  WHERE old.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_au AFTER UPDATE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era,
+    old.package_names,
+    old.release_filenames,
+    old.owner_usernames
+  WHERE old.deleted_at IS NULL;
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era,
+    new.package_names,
+    new.release_filenames,
+    new.owner_usernames
+  WHERE new.deleted_at IS NULL;
+END;
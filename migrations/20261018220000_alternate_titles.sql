@@ -0,0 +1,194 @@
+/* Alternate titles are stored as a JSON array */
+
+ALTER TABLE projects ADD COLUMN game_alternate_titles TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE project_data ADD COLUMN game_alternate_titles TEXT NOT NULL DEFAULT '[]';
+
+/* Rebuild the full-text index with the alternate titles */
+
+DROP TRIGGER projects_ai;
+DROP TRIGGER projects_ad;
+DROP TRIGGER projects_au;
+DROP TABLE projects_fts;
+
+CREATE VIRTUAL TABLE projects_fts USING fts5(
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames,
+  game_alternate_titles,
+  content="projects",
+  content_rowid="project_id"
+);
+
+/* Set weight for game title to 100 */
+INSERT INTO projects_fts(
+  projects_fts,
+  rank
+) VALUES(
+  'rank',
+  'bm25(100.0)'
+);
+
+INSERT INTO projects_fts (
+  rowid,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames,
+  game_alternate_titles
+)
+SELECT
+  project_id,
+  game_title,
+  game_publisher,
+  game_year,
+  description,
+  readme,
+  game_designers,
+  game_era,
+  package_names,
+  release_filenames,
+  owner_usernames,
+  game_alternate_titles
+FROM projects
+WHERE deleted_at IS NULL;
+
+CREATE TRIGGER projects_ai AFTER INSERT ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames,
+    game_alternate_titles
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era,
+    new.package_names,
+    new.release_filenames,
+    new.owner_usernames,
+    new.game_alternate_titles
+  WHERE new.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_ad AFTER DELETE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames,
+    game_alternate_titles
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era,
+    old.package_names,
+    old.release_filenames,
+    old.owner_usernames,
+    old.game_alternate_titles
+  WHERE old.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER projects_au AFTER UPDATE ON projects
+BEGIN
+  INSERT INTO projects_fts (
+    projects_fts,
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames,
+    game_alternate_titles
+  )
+  SELECT
+    'delete',
+    old.project_id,
+    old.game_title,
+    old.game_publisher,
+    old.game_year,
+    old.description,
+    old.readme,
+    old.game_designers,
+    old.game_era,
+    old.package_names,
+    old.release_filenames,
+    old.owner_usernames,
+    old.game_alternate_titles
+  WHERE old.deleted_at IS NULL;
+  INSERT INTO projects_fts (
+    rowid,
+    game_title,
+    game_publisher,
+    game_year,
+    description,
+    readme,
+    game_designers,
+    game_era,
+    package_names,
+    release_filenames,
+    owner_usernames,
+    game_alternate_titles
+  )
+  SELECT
+    new.project_id,
+    new.game_title,
+    new.game_publisher,
+    new.game_year,
+    new.description,
+    new.readme,
+    new.game_designers,
+    new.game_era,
+    new.package_names,
+    new.release_filenames,
+    new.owner_usernames,
+    new.game_alternate_titles
+  WHERE new.deleted_at IS NULL;
+END;
@@ -1,5 +1,14 @@
 use serde::Deserialize;
 
+use crate::{
+    search::SearchWeights,
+    validation::Limits
+};
+
+fn default_deletion_grace_days() -> u32 {
+    30
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub db_path: String,
@@ -8,5 +17,11 @@ pub struct Config {
     pub listen_ip: String,
     pub listen_port: u16,
     pub max_release_size: u32,
-    pub max_image_size: u32
+    pub max_image_size: u32,
+    #[serde(default = "default_deletion_grace_days")]
+    pub deletion_grace_days: u32,
+    #[serde(default)]
+    pub limits: Limits,
+    #[serde(default)]
+    pub search_weights: SearchWeights
 }
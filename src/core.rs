@@ -12,10 +12,11 @@ use std::{
 use thiserror::Error;
 
 use crate::{
-    model::{Owner, PackageDataPost, Package, Projects, ProjectData, ProjectDataPatch, ProjectDataPost, Project, User, Users},
-    params::ProjectsParams,
+    model::{Game, GamePatch, GameProjects, Images, Owner, PackageDataPost, Package, Projects, ProjectData, ProjectDataPatch, ProjectDataPost, Project, ProjectDiff, Revisions, SearchIndexReport, SearchSuggestions, User, Users},
+    params::{ProjectsParams, RevisionsParams, SuggestParams},
     pagination,
     time,
+    validation::FieldError,
     version::Version
 };
 
@@ -27,6 +28,20 @@ pub enum CoreError {
     TooLarge,
     #[error("Cannot remove last owner")]
     CannotRemoveLastOwner,
+    #[error("Cannot remove current project image")]
+    ImageInUse,
+    #[error("Conflict")]
+    Conflict,
+    #[error("Game exists")]
+    GameExists,
+    #[error("Package exists")]
+    PackageExists,
+    #[error("Version exists")]
+    VersionExists,
+    #[error("Invalid fields")]
+    InvalidFields(Vec<FieldError>),
+    #[error("Invalid reference")]
+    InvalidReference,
     #[error("Invalid project name")]
     InvalidProjectName,
     #[error("Project name in use")]
@@ -35,6 +50,8 @@ pub enum CoreError {
     MalformedQuery,
     #[error("Not a found")]
     NotFound,
+    #[error("Not a game")]
+    NotAGame,
     #[error("Not a package")]
     NotAPackage,
     #[error("Not a project")]
@@ -45,10 +62,12 @@ pub enum CoreError {
     NotAUser,
     #[error("Not a version")]
     NotAVersion,
+    #[error("Precondition failed")]
+    PreconditionFailed,
     #[error("Internal error")]
     InternalError,
     #[error("{0}")]
-    DatabaseError(#[from] sqlx::Error),
+    DatabaseError(sqlx::Error),
     #[error("{0}")]
     TimeError(#[from] time::Error),
     #[error("{0}")]
@@ -63,6 +82,13 @@ impl PartialEq for CoreError {
     }
 }
 
+#[derive(Debug)]
+pub struct ImageUpload<'a> {
+    pub name: &'a str,
+    pub content_type: &'a Mime,
+    pub content_length: Option<u64>
+}
+
 #[async_trait]
 pub trait Core {
     async fn get_project_id(
@@ -73,6 +99,22 @@ pub trait Core {
         unimplemented!();
     }
 
+    async fn get_deleted_project_id(
+        &self,
+        _proj: &str
+    ) -> Result<Project, CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn get_project_alias(
+        &self,
+        _proj: &str
+    ) -> Result<String, CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn get_package_id(
          &self,
         _proj: Project,
@@ -125,6 +167,14 @@ pub trait Core {
         unimplemented!();
     }
 
+    async fn user_is_admin(
+        &self,
+        _user: User
+    ) -> Result<bool, CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn get_projects(
         &self,
         _params: ProjectsParams
@@ -141,6 +191,14 @@ pub trait Core {
         unimplemented!();
     }
 
+    async fn get_current_revision(
+        &self,
+        _proj: Project
+    ) -> Result<i64, CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn create_project(
         &self,
         _user: User,
@@ -155,12 +213,58 @@ pub trait Core {
         &self,
         _owner: Owner,
         _proj: Project,
+        _expected: Option<i64>,
         _proj_data: &ProjectDataPatch
     ) -> Result<(), CoreError>
     {
         unimplemented!();
     }
 
+    async fn rename_project(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _name: &str
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn revert_project(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _revision: i64
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn delete_project(
+        &self,
+        _owner: Owner,
+        _proj: Project
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn restore_project(
+        &self,
+        _owner: Owner,
+        _proj: Project
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn purge_projects(
+        &self
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn get_project_revision(
         &self,
         _proj: Project,
@@ -170,10 +274,30 @@ pub trait Core {
         unimplemented!();
     }
 
+    async fn get_project_revisions(
+        &self,
+        _proj: Project,
+        _params: RevisionsParams
+    ) -> Result<Revisions, CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn get_project_diff(
+        &self,
+        _proj: Project,
+        _from: i64,
+        _to: i64
+    ) -> Result<ProjectDiff, CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn create_package(
         &self,
         _owner: Owner,
         _proj: Project,
+        _expected: Option<i64>,
         _pkg: &str,
         _pkg_data: &PackageDataPost
     ) -> Result<(), CoreError>
@@ -258,18 +382,68 @@ pub trait Core {
         unimplemented!();
     }
 
+    async fn get_images(
+        &self,
+        _proj: Project
+    ) -> Result<Images, CoreError>
+    {
+        unimplemented!();
+    }
+
     async fn add_image(
         &self,
         _owner: Owner,
         _proj: Project,
-        _img_name: &str,
-        _content_type: &Mime,
-        _content_length: Option<u64>,
+        _expected: Option<i64>,
+        _img: &ImageUpload<'_>,
         _stream: Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>
     ) -> Result<(), CoreError>
     {
         unimplemented!();
     }
+
+    async fn remove_image(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _img_name: &str
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn get_game(
+        &self,
+        _game: Game
+    ) -> Result<GameProjects, CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn update_game(
+        &self,
+        _owner: Owner,
+        _game: Game,
+        _game_data: &GamePatch
+    ) -> Result<(), CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn get_search_suggestions(
+        &self,
+        _params: SuggestParams
+    ) -> Result<SearchSuggestions, CoreError>
+    {
+        unimplemented!();
+    }
+
+    async fn reindex_search(
+        &self
+    ) -> Result<SearchIndexReport, CoreError>
+    {
+        unimplemented!();
+    }
 }
 
 pub type CoreArc = Arc<dyn Core + Send + Sync>;
@@ -1,11 +1,12 @@
 use axum::async_trait;
 use serde::Deserialize;
-use sqlx::FromRow;
+use sqlx::{FromRow, types::Json};
 
 use crate::{
     core::CoreError,
-    model::{Owner, Package, PackageDataPost, Project, ProjectDataPatch, ProjectDataPost, User, Users},
-    pagination::{Direction, SortBy},
+    model::{GalleryItem, Game, GamePatch, Owner, Package, PackageDataPost, Project, ProjectDataPatch, ProjectDataPost, User, Users},
+    pagination::{Direction, Facet, Filters, SortBy},
+    search::{SearchWeights, SuggestionField},
     version::Version
 };
 
@@ -22,6 +23,15 @@ pub struct ProjectSummaryRow {
     pub game_title_sort: String,
     pub game_publisher: String,
     pub game_year: String,
+    pub game_id: Option<i64>,
+    pub game_alternate_titles: Json<Vec<String>>,
+    pub game_designers: Json<Vec<String>>,
+    pub game_min_players: Option<i64>,
+    pub game_max_players: Option<i64>,
+    pub game_playing_time: Option<i64>,
+    pub game_era: Option<String>,
+    pub game_external_id: Option<String>,
+    pub tags: Json<Vec<String>>,
     pub image: Option<String>
 }
 
@@ -38,10 +48,38 @@ pub struct ProjectRow {
     pub game_title_sort: String,
     pub game_publisher: String,
     pub game_year: String,
+    pub game_id: Option<i64>,
+    pub game_alternate_titles: Json<Vec<String>>,
+    pub game_designers: Json<Vec<String>>,
+    pub game_min_players: Option<i64>,
+    pub game_max_players: Option<i64>,
+    pub game_playing_time: Option<i64>,
+    pub game_era: Option<String>,
+    pub game_external_id: Option<String>,
+    pub tags: Json<Vec<String>>,
     pub image: Option<String>,
     pub readme: String
 }
 
+// Matches in highlights are delimited by private use characters, which
+// cannot be confused with anything in the text itself
+pub const HIGHLIGHT_START: &str = "\u{E000}";
+pub const HIGHLIGHT_END: &str = "\u{E001}";
+
+#[derive(Debug, Deserialize, Eq, FromRow, PartialEq)]
+pub struct HighlightRow {
+    pub name: String,
+    pub title: String,
+    pub description: String,
+    pub readme: String
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+pub struct SearchIndexMismatchRow {
+    pub project_id: i64,
+    pub name: Option<String>
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq)]
 pub struct PackageRow {
     pub package_id: i64,
@@ -68,6 +106,46 @@ pub struct FileRow {
 //    requires: String
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct ImageRow {
+    pub filename: String,
+    pub url: String,
+    pub content_type: String,
+    pub size: i64,
+    pub published_at: i64,
+    pub published_by: String
+}
+
+#[derive(Debug)]
+pub struct ImageFileRow<'a> {
+    pub filename: &'a str,
+    pub url: &'a str,
+    pub content_type: &'a str,
+    pub size: i64
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct GameRow {
+    pub game_id: i64,
+    pub title: String,
+    pub title_sort: String,
+    pub publisher: String,
+    pub year: String
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+pub struct RevisionSummaryRow {
+    pub revision: i64,
+    pub modified_at: i64,
+    pub modified_by: String,
+    pub created: bool,
+    pub metadata: bool,
+    pub renamed: bool,
+    pub image: bool,
+    pub package: bool,
+    pub release: bool
+}
+
 #[async_trait]
 pub trait DatabaseClient {
     async fn get_project_id(
@@ -75,15 +153,57 @@ pub trait DatabaseClient {
         _projname: &str
     ) -> Result<Project, CoreError>;
 
+    async fn get_deleted_project_id(
+        &self,
+        _projname: &str
+    ) -> Result<Project, CoreError>;
+
+    async fn get_project_alias(
+        &self,
+        _projname: &str
+    ) -> Result<String, CoreError>;
+
     async fn get_projects_count(
         &self,
+        _filters: &Filters
     ) -> Result<i64, CoreError>;
 
     async fn get_projects_query_count(
         &self,
-        _query: &str
+        _query: &str,
+        _filters: &Filters
     ) -> Result<i64, CoreError>;
 
+    async fn get_projects_highlights(
+        &self,
+        _query: &str,
+        _names: &[String]
+    ) -> Result<Vec<HighlightRow>, CoreError>;
+
+    async fn get_search_suggestions(
+        &self,
+        _field: SuggestionField,
+        _query: &str,
+        _limit: u32
+    ) -> Result<Vec<String>, CoreError>;
+
+    async fn set_search_weights(
+        &self,
+        _weights: &SearchWeights
+    ) -> Result<(), CoreError>;
+
+    async fn reindex_search(
+        &self
+    ) -> Result<(Vec<SearchIndexMismatchRow>, Vec<SearchIndexMismatchRow>), CoreError>;
+
+    async fn get_projects_facet(
+        &self,
+        _facet: Facet,
+        _query: Option<&str>,
+        _filters: &Filters,
+        _limit: u32
+    ) -> Result<Vec<(String, i64)>, CoreError>;
+
     async fn get_user_id(
         &self,
         _username: &str
@@ -100,6 +220,11 @@ pub trait DatabaseClient {
         _proj: Project
     ) -> Result<bool, CoreError>;
 
+    async fn user_is_admin(
+        &self,
+        _user: User
+    ) -> Result<bool, CoreError>;
+
     async fn add_owner(
         &self,
         _user: User,
@@ -133,6 +258,7 @@ pub trait DatabaseClient {
         &self,
         _sort_by: SortBy,
         _dir: Direction,
+        _filters: &Filters,
         _limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>;
 
@@ -141,6 +267,7 @@ pub trait DatabaseClient {
         _query: &str,
         _sort_by: SortBy,
         _dir: Direction,
+        _filters: &Filters,
         _limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>;
 
@@ -150,6 +277,7 @@ pub trait DatabaseClient {
         _dir: Direction,
         _field: &str,
         _id: u32,
+        _filters: &Filters,
         _limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>;
 
@@ -160,6 +288,7 @@ pub trait DatabaseClient {
         _dir: Direction,
         _field: &str,
         _id: u32,
+        _filters: &Filters,
         _limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>;
 
@@ -175,10 +304,45 @@ pub trait DatabaseClient {
         &self,
         _owner: Owner,
         _proj: Project,
+        _expected: Option<i64>,
         _proj_data: &ProjectDataPatch,
         _now: i64
     ) -> Result<(), CoreError>;
 
+    async fn rename_project(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _name: &str,
+        _now: i64
+    ) -> Result<(), CoreError>;
+
+    async fn revert_project(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _revision: i64,
+        _now: i64
+    ) -> Result<(), CoreError>;
+
+    async fn delete_project(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _now: i64
+    ) -> Result<(), CoreError>;
+
+    async fn restore_project(
+        &self,
+        _proj: Project,
+        _after: i64
+    ) -> Result<(), CoreError>;
+
+    async fn purge_projects(
+        &self,
+        _before: i64
+    ) -> Result<Vec<String>, CoreError>;
+
     async fn get_project_row(
         &self,
         proj: Project
@@ -190,6 +354,12 @@ pub trait DatabaseClient {
         _revision: i64
     ) -> Result<ProjectRow, CoreError>;
 
+    async fn get_gallery(
+        &self,
+        _proj: Project,
+        _revision: i64
+    ) -> Result<Vec<GalleryItem>, CoreError>;
+
     async fn get_packages(
         &self,
         _proj: Project
@@ -205,6 +375,7 @@ pub trait DatabaseClient {
         &self,
         _owner: Owner,
         _proj: Project,
+        _expected: Option<i64>,
         _pkg: &str,
         _pkg_data: &PackageDataPost,
         _now: i64
@@ -291,12 +462,56 @@ pub trait DatabaseClient {
         _date: i64
     ) -> Result<String, CoreError>;
 
+    async fn get_images(
+        &self,
+        _proj: Project
+    ) -> Result<Vec<ImageRow>, CoreError>;
+
     async fn add_image_url(
+        &self,
+        _owner: Owner,
+        _proj: Project,
+        _expected: Option<i64>,
+        _img: &ImageFileRow<'_>,
+        _now: i64
+    ) -> Result<(), CoreError>;
+
+    async fn remove_image(
         &self,
         _owner: Owner,
         _proj: Project,
         _img_name: &str,
-        _url: &str,
+        _now: i64
+    ) -> Result<(), CoreError>;
+
+    async fn get_revisions_count(
+        &self,
+        _proj: Project
+    ) -> Result<i64, CoreError>;
+
+    async fn get_revisions_window(
+        &self,
+        _proj: Project,
+        _dir: Direction,
+        _revision: i64,
+        _limit: u32
+    ) -> Result<Vec<RevisionSummaryRow>, CoreError>;
+
+    async fn get_game_row(
+        &self,
+        _game: Game
+    ) -> Result<GameRow, CoreError>;
+
+    async fn get_game_projects(
+        &self,
+        _game: Game
+    ) -> Result<Vec<String>, CoreError>;
+
+    async fn update_game(
+        &self,
+        _owner: Owner,
+        _game: Game,
+        _game_data: &GamePatch,
         _now: i64
     ) -> Result<(), CoreError>;
 }
@@ -0,0 +1,297 @@
+use similar::TextDiff;
+use std::collections::BTreeSet;
+
+use crate::model::{FieldDiff, GameDataDiff, ProjectData, ProjectDiff, ReleaseRef};
+
+fn diff_field<T>(from: &T, to: &T) -> Option<FieldDiff<T>>
+where
+    T: Clone + PartialEq
+{
+    match from == to {
+        true => None,
+        false => Some(FieldDiff { from: from.clone(), to: to.clone() })
+    }
+}
+
+fn diff_text(from: &str, to: &str, from_rev: i64, to_rev: i64) -> Option<String> {
+    match from == to {
+        true => None,
+        false => Some(
+            TextDiff::from_lines(from, to)
+                .unified_diff()
+                .header(&format!("r{from_rev}"), &format!("r{to_rev}"))
+                .to_string()
+        )
+    }
+}
+
+fn package_names(pd: &ProjectData) -> BTreeSet<String> {
+    pd.packages.iter()
+        .map(|pkg| pkg.name.clone())
+        .collect()
+}
+
+fn release_refs(pd: &ProjectData) -> BTreeSet<ReleaseRef> {
+    pd.packages.iter()
+        .flat_map(|pkg| pkg.releases.iter().map(|r| ReleaseRef {
+            package: pkg.name.clone(),
+            version: r.version.clone()
+        }))
+        .collect()
+}
+
+pub fn diff_project_data(from: &ProjectData, to: &ProjectData) -> ProjectDiff {
+    let (from_pkgs, to_pkgs) = (package_names(from), package_names(to));
+    let (from_rels, to_rels) = (release_refs(from), release_refs(to));
+
+    ProjectDiff {
+        from: from.revision,
+        to: to.revision,
+        name: diff_field(&from.name, &to.name),
+        description: diff_field(&from.description, &to.description),
+        tags: diff_field(&from.tags, &to.tags),
+        game: GameDataDiff {
+            title: diff_field(&from.game.title, &to.game.title),
+            title_sort_key: diff_field(
+                &from.game.title_sort_key,
+                &to.game.title_sort_key
+            ),
+            publisher: diff_field(&from.game.publisher, &to.game.publisher),
+            year: diff_field(&from.game.year, &to.game.year),
+            alternate_titles: diff_field(
+                &from.game.alternate_titles,
+                &to.game.alternate_titles
+            ),
+            designers: diff_field(&from.game.designers, &to.game.designers),
+            min_players: diff_field(
+                &from.game.min_players,
+                &to.game.min_players
+            ),
+            max_players: diff_field(
+                &from.game.max_players,
+                &to.game.max_players
+            ),
+            playing_time: diff_field(
+                &from.game.playing_time,
+                &to.game.playing_time
+            ),
+            era: diff_field(&from.game.era, &to.game.era),
+            external_id: diff_field(
+                &from.game.external_id,
+                &to.game.external_id
+            )
+        },
+        readme: diff_text(&from.readme, &to.readme, from.revision, to.revision),
+        image: diff_field(&from.image, &to.image),
+        gallery: diff_field(&from.gallery, &to.gallery),
+        packages_added: to_pkgs.difference(&from_pkgs).cloned().collect(),
+        packages_removed: from_pkgs.difference(&to_pkgs).cloned().collect(),
+        releases_added: to_rels.difference(&from_rels).cloned().collect(),
+        releases_removed: from_rels.difference(&to_rels).cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::model::{FileData, GalleryItem, GameData, PackageData};
+
+    fn release(version: &str) -> FileData {
+        FileData {
+            version: version.into(),
+            filename: "x.vmod".into(),
+            url: "https://example.com/x.vmod".into(),
+            size: 1,
+            checksum: "".into(),
+            published_at: "".into(),
+            published_by: "bob".into(),
+            requires: "".into(),
+            authors: vec![]
+        }
+    }
+
+    fn project_data(revision: i64) -> ProjectData {
+        ProjectData {
+            name: "test_game".into(),
+            description: "A test game".into(),
+            revision,
+            created_at: "".into(),
+            modified_at: "".into(),
+            tags: vec![],
+            game: GameData {
+                id: None,
+                title: "A Game of Tests".into(),
+                title_sort_key: "Game of Tests, A".into(),
+                publisher: "Test Game Company".into(),
+                year: "1978".into(),
+                ..Default::default()
+            },
+            readme: "one\ntwo\nthree\n".into(),
+            readme_html: None,
+            image: None,
+            gallery: vec![],
+            owners: vec![],
+            packages: vec![
+                PackageData {
+                    name: "a_package".into(),
+                    description: "".into(),
+                    releases: vec![release("1.0.0")],
+                    files: vec![]
+                }
+            ]
+        }
+    }
+
+    #[test]
+    fn diff_project_data_same() {
+        assert_eq!(
+            diff_project_data(&project_data(1), &project_data(1)),
+            ProjectDiff {
+                from: 1,
+                to: 1,
+                name: None,
+                description: None,
+                tags: None,
+                game: GameDataDiff::default(),
+                readme: None,
+                image: None,
+                gallery: None,
+                packages_added: vec![],
+                packages_removed: vec![],
+                releases_added: vec![],
+                releases_removed: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn diff_project_data_same_serializes_null() {
+        let diff = serde_json::to_value(
+            diff_project_data(&project_data(1), &project_data(1))
+        ).unwrap();
+
+        assert_eq!(
+            diff["game"],
+            serde_json::json!({
+                "title": null,
+                "title_sort_key": null,
+                "publisher": null,
+                "year": null,
+                "alternate_titles": null,
+                "designers": null,
+                "min_players": null,
+                "max_players": null,
+                "playing_time": null,
+                "era": null,
+                "external_id": null
+            })
+        );
+        assert_eq!(diff.get("tags"), Some(&serde_json::Value::Null));
+        assert_eq!(diff.get("gallery"), Some(&serde_json::Value::Null));
+    }
+
+    #[test]
+    fn diff_project_data_changed() {
+        let from = project_data(1);
+
+        let mut to = project_data(3);
+        to.description = "A better test game".into();
+        to.tags = vec!["napoleonic".into()];
+        to.game.year = "1979".into();
+        to.readme = "one\n2\nthree\n".into();
+        to.image = Some("img.png".into());
+        to.gallery = vec![
+            GalleryItem {
+                filename: "map.png".into(),
+                caption: "The map".into()
+            }
+        ];
+        to.packages[0].releases.push(release("1.1.0"));
+        to.packages.push(
+            PackageData {
+                name: "b_package".into(),
+                description: "".into(),
+                releases: vec![],
+                files: vec![]
+            }
+        );
+
+        assert_eq!(
+            diff_project_data(&from, &to),
+            ProjectDiff {
+                from: 1,
+                to: 3,
+                name: None,
+                description: Some(
+                    FieldDiff {
+                        from: "A test game".into(),
+                        to: "A better test game".into()
+                    }
+                ),
+                tags: Some(
+                    FieldDiff {
+                        from: vec![],
+                        to: vec!["napoleonic".into()]
+                    }
+                ),
+                game: GameDataDiff {
+                    year: Some(
+                        FieldDiff {
+                            from: "1978".into(),
+                            to: "1979".into()
+                        }
+                    ),
+                    ..Default::default()
+                },
+                readme: Some(
+                    "--- r1\n+++ r3\n@@ -1,3 +1,3 @@\n one\n-two\n+2\n three\n".into()
+                ),
+                image: Some(
+                    FieldDiff {
+                        from: None,
+                        to: Some("img.png".into())
+                    }
+                ),
+                gallery: Some(
+                    FieldDiff {
+                        from: vec![],
+                        to: vec![
+                            GalleryItem {
+                                filename: "map.png".into(),
+                                caption: "The map".into()
+                            }
+                        ]
+                    }
+                ),
+                packages_added: vec!["b_package".into()],
+                packages_removed: vec![],
+                releases_added: vec![
+                    ReleaseRef {
+                        package: "a_package".into(),
+                        version: "1.1.0".into()
+                    }
+                ],
+                releases_removed: vec![]
+            }
+        );
+    }
+
+    #[test]
+    fn diff_project_data_removed() {
+        let mut to = project_data(2);
+        to.packages.clear();
+
+        let diff = diff_project_data(&project_data(1), &to);
+        assert_eq!(diff.packages_removed, vec!["a_package".to_string()]);
+        assert_eq!(
+            diff.releases_removed,
+            vec![
+                ReleaseRef {
+                    package: "a_package".into(),
+                    version: "1.0.0".into()
+                }
+            ]
+        );
+    }
+}
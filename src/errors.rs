@@ -1,53 +1,138 @@
 use thiserror::Error;
 
-use crate::core::CoreError;
+use crate::{
+    core::CoreError,
+    params,
+    validation::FieldError
+};
 
-// TODO: better error messsages
 #[derive(Debug, Error, PartialEq)]
 pub enum AppError {
     #[error("Unsupported media type")]
     BadMimeType,
     #[error("Payload too large")]
     TooLarge,
-//    #[error("Cannot remove last project owner")]
-    #[error("Bad request")]
+    #[error("Cannot remove last project owner")]
     CannotRemoveLastOwner,
-    #[error("{0}")]
+    #[error("Cannot remove current project image")]
+    ImageInUse,
+    #[error("Conflict")]
+    Conflict,
+    #[error("Game exists")]
+    GameExists,
+    #[error("Package exists")]
+    PackageExists,
+    #[error("Version exists")]
+    VersionExists,
+    #[error("Database error")]
     DatabaseError(String),
 // TODO: Internal error should have a string? cause?
     #[error("Internal error")]
     InternalError,
+    #[error("Invalid fields")]
+    InvalidFields(Vec<FieldError>),
+    #[error("Invalid project name")]
+    InvalidProjectName,
+    #[error("Invalid reference")]
+    InvalidReference,
+    #[error("Invalid search query")]
+    InvalidSearchQuery(String),
     #[error("Unprocessable entity")]
     JsonError,
-    #[error("Bad request")]
+    #[error("Limit out of range")]
     LimitOutOfRange,
-    #[error("Bad request")]
+    #[error("Malformed query")]
     MalformedQuery,
-    #[error("Bad request")]
+    #[error("Malformed version")]
     MalformedVersion,
-    #[error("Not found")]
+    #[error("Moved permanently")]
+    MovedPermanently(String),
+    #[error("Not a game")]
+    NotAGame,
+    #[error("Not a package")]
+    NotAPackage,
+    #[error("Not a project")]
+    NotAProject,
+    #[error("Not a revision")]
+    NotARevision,
+    #[error("Not a user")]
     NotAUser,
+    #[error("Not a version")]
+    NotAVersion,
     #[error("Not found")]
     NotFound,
+    #[error("Permanent redirect")]
+    PermanentRedirect(String),
+    #[error("Precondition failed")]
+    PreconditionFailed,
+    #[error("Project name in use")]
+    ProjectNameInUse,
     #[error("Unauthorized")]
     Unauthorized
 }
 
+impl AppError {
+    // Codes are part of the API; do not change existing ones
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::BadMimeType => "bad_mime_type",
+            AppError::TooLarge => "too_large",
+            AppError::CannotRemoveLastOwner => "cannot_remove_last_owner",
+            AppError::ImageInUse => "image_in_use",
+            AppError::Conflict => "conflict",
+            AppError::GameExists => "game_exists",
+            AppError::PackageExists => "package_exists",
+            AppError::VersionExists => "version_exists",
+            AppError::DatabaseError(_) => "database_error",
+            AppError::InternalError => "internal_error",
+            AppError::InvalidFields(_) => "invalid_fields",
+            AppError::InvalidProjectName => "invalid_project_name",
+            AppError::InvalidReference => "invalid_reference",
+            AppError::InvalidSearchQuery(_) => "invalid_search_query",
+            AppError::JsonError => "malformed_json",
+            AppError::LimitOutOfRange => "limit_out_of_range",
+            AppError::MalformedQuery => "malformed_query",
+            AppError::MalformedVersion => "malformed_version",
+            AppError::MovedPermanently(_) => "moved_permanently",
+            AppError::NotAGame => "not_a_game",
+            AppError::NotAPackage => "not_a_package",
+            AppError::NotAProject => "not_a_project",
+            AppError::NotARevision => "not_a_revision",
+            AppError::NotAUser => "not_a_user",
+            AppError::NotAVersion => "not_a_version",
+            AppError::NotFound => "not_found",
+            AppError::PermanentRedirect(_) => "permanent_redirect",
+            AppError::PreconditionFailed => "precondition_failed",
+            AppError::ProjectNameInUse => "project_name_in_use",
+            AppError::Unauthorized => "unauthorized"
+        }
+    }
+}
+
 impl From<CoreError> for AppError {
     fn from(err: CoreError) -> Self {
         match err {
             CoreError::BadMimeType => AppError::BadMimeType,
             CoreError::TooLarge => AppError::TooLarge,
-            CoreError::CannotRemoveLastOwner => AppError::CannotRemoveLastOwner  ,
-            CoreError::InvalidProjectName => AppError::MalformedQuery, // FIXME
-            CoreError::ProjectNameInUse => AppError::MalformedQuery, // FIXME
+            CoreError::CannotRemoveLastOwner => AppError::CannotRemoveLastOwner,
+            CoreError::ImageInUse => AppError::ImageInUse,
+            CoreError::Conflict => AppError::Conflict,
+            CoreError::GameExists => AppError::GameExists,
+            CoreError::PackageExists => AppError::PackageExists,
+            CoreError::VersionExists => AppError::VersionExists,
+            CoreError::InvalidFields(e) => AppError::InvalidFields(e),
+            CoreError::InvalidReference => AppError::InvalidReference,
+            CoreError::InvalidProjectName => AppError::InvalidProjectName,
+            CoreError::ProjectNameInUse => AppError::ProjectNameInUse,
             CoreError::MalformedQuery => AppError::MalformedQuery,
             CoreError::NotFound => AppError::NotFound,
-            CoreError::NotAPackage => AppError::NotFound,
-            CoreError::NotAProject => AppError::NotFound,
-            CoreError::NotARevision => AppError::NotFound,
+            CoreError::NotAGame => AppError::NotAGame,
+            CoreError::NotAPackage => AppError::NotAPackage,
+            CoreError::NotAProject => AppError::NotAProject,
+            CoreError::NotARevision => AppError::NotARevision,
             CoreError::NotAUser => AppError::NotAUser,
-            CoreError::NotAVersion => AppError::NotFound,
+            CoreError::NotAVersion => AppError::NotAVersion,
+            CoreError::PreconditionFailed => AppError::PreconditionFailed,
             CoreError::InternalError => AppError::InternalError,
             CoreError::DatabaseError(e) => AppError::DatabaseError(e.to_string()),
             CoreError::TimeError(_) => AppError::InternalError,
@@ -55,3 +140,14 @@ impl From<CoreError> for AppError {
         }
     }
 }
+
+impl From<params::Error> for AppError {
+    fn from(err: params::Error) -> Self {
+        match err {
+            params::Error::LimitError(_) => AppError::LimitOutOfRange,
+            params::Error::QueryError(e) =>
+                AppError::InvalidSearchQuery(e.to_string()),
+            _ => AppError::MalformedQuery
+        }
+    }
+}
@@ -1,10 +1,11 @@
 use axum::{
     async_trait, RequestPartsExt,
     extract::{
-        FromRequest, FromRequestParts, FromRef, Path, Request, State,
+        FromRequest, FromRequestParts, FromRef, MatchedPath, Path, Request,
+        State,
         rejection::{JsonRejection, QueryRejection}
     },
-    http::request::Parts
+    http::{Method, request::Parts}
 };
 use axum_extra::{
     TypedHeader,
@@ -18,10 +19,10 @@ use itertools::Itertools;
 use unwrap_infallible::UnwrapInfallible;
 
 use crate::{
-    core::CoreArc,
+    core::{CoreArc, CoreError},
     errors::AppError,
     jwt::{self, Claims, DecodingKey},
-    model::{Owned, Owner, Package, Project, User},
+    model::{Admin, Game, Owned, OwnedOrAdmin, Owner, Package, Project, User},
     version::Version
 };
 
@@ -107,10 +108,90 @@ where
     )
 }
 
+fn redirect_to_project(parts: &Parts, old: &str, new: &str) -> String {
+    // replace the old project name in the path with the new one
+    let mut segs = parts.uri.path().split('/').collect::<Vec<_>>();
+    if let Some(i) = segs.windows(2)
+        .position(|w| w[0] == "projects" && w[1] == old)
+    {
+        segs[i + 1] = new;
+    }
+
+    let path = segs.join("/");
+
+    match parts.uri.query() {
+        Some(query) => format!("{path}?{query}"),
+        None => path
+    }
+}
+
+fn deleted_project_allowed(parts: &Parts) -> bool {
+    // deleted projects may be read or restored, but not otherwise changed
+    parts.method == Method::GET ||
+        parts.method == Method::HEAD ||
+        parts.extensions.get::<MatchedPath>()
+            .is_some_and(|p| p.as_str().ends_with("/:proj/restore"))
+}
+
+async fn get_project_id<S>(
+    parts: &mut Parts,
+    state: &S,
+    proj: &str
+) -> Result<Project, AppError>
+where
+    S: Send + Sync,
+    DecodingKey: FromRef<S>,
+    CoreArc: FromRef<S>
+{
+    let core = get_state(parts, state).await;
+
+    match core.get_project_id(proj).await {
+        Err(CoreError::NotAProject) => {
+            // old names of renamed projects redirect to the current name
+            match core.get_project_alias(proj).await {
+                Ok(name) => {
+                    let location = redirect_to_project(parts, proj, &name);
+                    // 301 lets clients change the method to GET, so
+                    // redirect anything else with 308
+                    return Err(
+                        match parts.method {
+                            Method::GET | Method::HEAD =>
+                                AppError::MovedPermanently(location),
+                            _ => AppError::PermanentRedirect(location)
+                        }
+                    );
+                },
+                Err(CoreError::NotAProject) => {},
+                Err(e) => return Err(e.into())
+            }
+
+            if !deleted_project_allowed(parts) {
+                return Err(AppError::NotAProject);
+            }
+
+            // deleted projects are visible only to their owners and admins
+            let proj = core.get_deleted_project_id(proj).await?;
+
+            let user = User::from_request_parts(parts, state)
+                .await
+                .or(Err(AppError::NotAProject))?;
+
+            match core.user_is_owner(user, proj).await? ||
+                core.user_is_admin(user).await?
+            {
+                true => Ok(proj),
+                false => Err(AppError::NotAProject)
+            }
+        },
+        r => Ok(r?)
+    }
+}
+
 #[async_trait]
 impl<S> FromRequestParts<S> for Project
 where
     S: Send + Sync,
+    DecodingKey: FromRef<S>,
     CoreArc: FromRef<S>
 {
     type Rejection = AppError;
@@ -125,10 +206,31 @@ where
             .next_tuple()
             .ok_or(AppError::InternalError)?;
 
-        let core = get_state(parts, state).await;
-
         // look up the project id
-        Ok(core.get_project_id(&proj).await?)
+        get_project_id(parts, state, &proj).await
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Game
+where
+    S: Send + Sync,
+    CoreArc: FromRef<S>
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection>
+    {
+        let (game, ) = get_path_iter(parts, state)
+            .await?
+            .next_tuple()
+            .ok_or(AppError::InternalError)?;
+
+        // game ids are numeric; anything else cannot be a game
+        Ok(Game(game.parse::<i64>().or(Err(AppError::NotAGame))?))
     }
 }
 
@@ -136,6 +238,7 @@ where
 impl<S> FromRequestParts<S> for Package
 where
     S: Send + Sync,
+    DecodingKey: FromRef<S>,
     CoreArc: FromRef<S>
 {
     type Rejection = AppError;
@@ -155,6 +258,7 @@ pub struct ProjectPackage(pub Project, pub Package);
 impl<S> FromRequestParts<S> for ProjectPackage
 where
     S: Send + Sync,
+    DecodingKey: FromRef<S>,
     CoreArc: FromRef<S>
 {
     type Rejection = AppError;
@@ -169,11 +273,11 @@ where
             .next_tuple()
             .ok_or(AppError::InternalError)?;
 
-        let core = get_state(parts, state).await;
-
 // TODO: could combine project-package lookup?
         // look up the project id
-        let proj = core.get_project_id(&proj).await?;
+        let proj = get_project_id(parts, state, &proj).await?;
+
+        let core = get_state(parts, state).await;
 
         // look up the package id
         let pkg = core.get_package_id(proj, &pkg).await?;
@@ -188,6 +292,7 @@ pub struct ProjectPackageVersion(pub Project, pub Package, pub Version);
 impl<S> FromRequestParts<S> for ProjectPackageVersion
 where
     S: Send + Sync,
+    DecodingKey: FromRef<S>,
     CoreArc: FromRef<S>
 {
     type Rejection = AppError;
@@ -202,18 +307,18 @@ where
             .next_tuple()
             .ok_or(AppError::InternalError)?;
 
-        let core = get_state(parts, state).await;
-
 // TODO: could combine project-package lookup?
         // look up the project id
-        let proj = core.get_project_id(&proj).await?;
+        let proj = get_project_id(parts, state, &proj).await?;
+
+        let core = get_state(parts, state).await;
 
         // look up the package id
         let pkg = core.get_package_id(proj, &pkg).await?;
 
         // parse the version
         let ver = ver.parse::<Version>()
-            .or(Err(AppError::NotFound))?;
+            .or(Err(AppError::NotAVersion))?;
 
         Ok(ProjectPackageVersion(proj, pkg, ver))
     }
@@ -249,6 +354,65 @@ where
     }
 }
 
+#[async_trait]
+impl<S> FromRequestParts<S> for OwnedOrAdmin
+where
+    S: Send + Sync,
+    DecodingKey: FromRef<S>,
+    CoreArc: FromRef<S>
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection>
+    {
+        // check that the requester is authorized
+        let user = User::from_request_parts(parts, state).await?;
+
+        // check that that project exists
+        let proj = Project::from_request_parts(parts, state).await?;
+
+        let core = get_state(parts, state).await;
+
+        // check that that requester owns the project or is an admin
+        match core.user_is_owner(user, proj).await? ||
+            core.user_is_admin(user).await?
+        {
+            true => Ok(OwnedOrAdmin(Owner(user.0), proj)),
+            false =>  Err(AppError::Unauthorized)
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Admin
+where
+    S: Send + Sync,
+    DecodingKey: FromRef<S>,
+    CoreArc: FromRef<S>
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &S
+    ) -> Result<Self, Self::Rejection>
+    {
+        // check that the requester is authorized
+        let user = User::from_request_parts(parts, state).await?;
+
+        let core = get_state(parts, state).await;
+
+        // check that the requester is an admin
+        match core.user_is_admin(user).await? {
+            true => Ok(Admin(user)),
+            false => Err(AppError::Unauthorized)
+        }
+    }
+}
+
 impl From<JsonRejection> for AppError {
     fn from(err: JsonRejection) -> Self {
         match err {
@@ -260,7 +424,7 @@ impl From<JsonRejection> for AppError {
 
 impl From<QueryRejection> for AppError {
     fn from(_: QueryRejection) -> Self {
-       AppError::MalformedQuery
+        AppError::MalformedQuery
     }
 }
 
@@ -311,7 +475,7 @@ mod test {
         body::Body,
         http::{
             Method, StatusCode,
-            header::AUTHORIZATION
+            header::{AUTHORIZATION, LOCATION}
         },
         routing::get
     };
@@ -565,6 +729,28 @@ mod test {
                 _ => Err(CoreError::NotAProject)
             }
         }
+
+        async fn get_deleted_project_id(
+            &self,
+            proj: &str
+        ) -> Result<Project, CoreError>
+        {
+            match proj {
+                "deleted_project" => Ok(Project(43)),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
+
+        async fn get_project_alias(
+            &self,
+            proj: &str
+        ) -> Result<String, CoreError>
+        {
+            match proj {
+                "old_project" => Ok("a_project".into()),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
     }
 
     async fn project_ok(
@@ -642,13 +828,43 @@ mod test {
             }
         }
 
+        async fn get_deleted_project_id(
+            &self,
+            proj: &str
+        ) -> Result<Project, CoreError>
+        {
+            match proj {
+                "deleted_project" => Ok(Project(43)),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
+
+        async fn get_project_alias(
+            &self,
+            proj: &str
+        ) -> Result<String, CoreError>
+        {
+            match proj {
+                "old_project" => Ok("a_project".into()),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
+
         async fn user_is_owner(
             &self,
             user: User,
             proj: Project
         ) -> Result<bool, CoreError>
         {
-            Ok(user == User(1) && proj == Project(42))
+            Ok(user == User(1) && (proj == Project(42) || proj == Project(43)))
+        }
+
+        async fn user_is_admin(
+            &self,
+            user: User
+        ) -> Result<bool, CoreError>
+        {
+            Ok(user == User(3))
         }
 
         async fn get_owners(
@@ -812,4 +1028,332 @@ mod test {
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    async fn deleted_project_ok(
+        proj: Project,
+        State(_): State<AppState>
+    )
+    {
+        assert_eq!(proj, Project(43));
+    }
+
+    #[tokio::test]
+    async fn project_id_from_request_parts_deleted_owner() {
+        let exp = bob_ok();
+
+        let app = Router::new()
+            .route("/:proj", get(deleted_project_ok))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/deleted_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn project_id_from_request_parts_deleted_admin() {
+        let exp = Claims {
+            sub: 3,
+            exp: 899999999999,
+            iat: 0
+        };
+
+        let app = Router::new()
+            .route("/:proj", get(deleted_project_ok))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/deleted_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn project_id_from_request_parts_deleted_not_owner() {
+        let exp = Claims {
+            sub: 2,
+            exp: 899999999999,
+            iat: 0
+        };
+
+        let app = Router::new()
+            .route("/:proj", get(project_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/deleted_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn project_id_from_request_parts_deleted_no_auth_header() {
+        let app = Router::new()
+            .route("/:proj", get(project_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/deleted_project")
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn project_id_from_request_parts_old_name() {
+        let app = Router::new()
+            .route("/projects/:proj/x", get(project_fail))
+            .with_state(make_state(ProjectTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/projects/old_project/x?y=1")
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "/projects/a_project/x?y=1"
+        );
+    }
+
+    async fn owned_or_admin_ok(
+        owned: OwnedOrAdmin,
+        State(_): State<AppState>
+    )
+    {
+        assert_eq!(owned.1, Project(42));
+    }
+
+    async fn owned_or_admin_fail(
+        _: OwnedOrAdmin,
+        State(_): State<AppState>
+    )
+    {
+        unreachable!();
+    }
+
+    #[tokio::test]
+    async fn owned_or_admin_from_request_parts_owner() {
+        let exp = bob_ok();
+
+        let app = Router::new()
+            .route("/:proj", get(owned_or_admin_ok))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/a_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn owned_or_admin_from_request_parts_admin() {
+        let exp = Claims {
+            sub: 3,
+            exp: 899999999999,
+            iat: 0
+        };
+
+        let app = Router::new()
+            .route("/:proj", get(owned_or_admin_ok))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/a_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn owned_or_admin_from_request_parts_not_owner() {
+        let exp = Claims {
+            sub: 2,
+            exp: 899999999999,
+            iat: 0
+        };
+
+        let app = Router::new()
+            .route("/:proj", get(owned_or_admin_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/a_project")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn owned_or_admin_from_request_parts_no_auth_header() {
+        let app = Router::new()
+            .route("/:proj", get(owned_or_admin_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/a_project")
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    async fn admin_ok(
+        admin: Admin,
+        State(_): State<AppState>
+    )
+    {
+        assert_eq!(admin.0, User(3));
+    }
+
+    async fn admin_fail(
+        _: Admin,
+        State(_): State<AppState>
+    )
+    {
+        unreachable!();
+    }
+
+    #[tokio::test]
+    async fn admin_from_request_parts_admin() {
+        let exp = Claims {
+            sub: 3,
+            exp: 899999999999,
+            iat: 0
+        };
+
+        let app = Router::new()
+            .route("/", get(admin_ok))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn admin_from_request_parts_not_admin() {
+        let exp = bob_ok();
+
+        let app = Router::new()
+            .route("/", get(admin_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/")
+                    .header(AUTHORIZATION, token(KEY, &exp))
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn admin_from_request_parts_no_auth_header() {
+        let app = Router::new()
+            .route("/", get(admin_fail))
+            .with_state(make_state(OwnersTestCore {}));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/")
+                    .body(Body::empty())
+                    .unwrap()
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
 }
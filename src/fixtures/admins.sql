@@ -0,0 +1,3 @@
+INSERT INTO admins (user_id)
+VALUES
+  (3);
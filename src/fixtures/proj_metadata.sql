@@ -0,0 +1,24 @@
+UPDATE projects SET
+  game_designers = '["Jim Dunnigan"]',
+  game_min_players = 2,
+  game_max_players = 4,
+  game_playing_time = 120,
+  game_era = "WWII",
+  game_external_id = "bgg:1"
+WHERE project_id = 1;
+
+UPDATE projects SET
+  game_alternate_titles = '["Empires in Arms", "EiA"]',
+  game_designers = '["Jim Dunnigan", "Richard Berg"]',
+  game_min_players = 1,
+  game_playing_time = 600,
+  game_era = "Napoleonic"
+WHERE project_id = 2;
+
+UPDATE projects SET
+  game_designers = '["Richard Berg"]',
+  game_min_players = 2,
+  game_max_players = 2,
+  game_era = "wwii",
+  game_external_id = "bgg:3"
+WHERE project_id = 3;
@@ -1,21 +1,29 @@
 use axum::{
     body::Bytes,
-    extract::{Path, Query, Request, State},
-    response::{Json, Redirect}
+    extract::{OriginalUri, Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header::{CACHE_CONTROL, CONTENT_TYPE, IF_MATCH, IF_NONE_MATCH}},
+    response::{IntoResponse, Json, Redirect, Response}
 };
 use axum_extra::{
     TypedHeader,
-    headers::{ContentLength, ContentType}
+    headers::{ContentLength, ContentType, ETag, HeaderMapExt, IfMatch, IfModifiedSince, IfNoneMatch, LastModified}
 };
+use chrono::{DateTime, Utc};
 use futures::{Stream, TryStreamExt};
-use std::io;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    io,
+    time::SystemTime
+};
 
 use crate::{
-    core::CoreArc,
+    core::{CoreArc, ImageUpload},
     errors::AppError,
     extractors::{ProjectPackage, ProjectPackageVersion, Wrapper},
-    model::{Owned, Package, PackageDataPost, ProjectData, ProjectDataPatch, ProjectDataPost, Project, Projects, Users, User},
-    params::ProjectsParams,
+    model::{Admin, Game, GamePatch, GameProjects, Images, Owned, OwnedOrAdmin, Owner, PackageDataPost, ProjectData, ProjectDataPatch, ProjectDataPost, ProjectNamePost, Project, ProjectDiff, Revisions, SearchIndexReport, SearchSuggestions, Users, User},
+    params::{DiffParams, MaybeProjectsParams, MaybeRevisionsParams, MaybeSuggestParams, ProjectsParams, RenderFormat, RenderParams, RevisionsParams, SuggestParams},
+    render::render_readme,
     version::Version
 };
 
@@ -28,20 +36,144 @@ pub async fn root_get() -> &'static str {
     "hello world"
 }
 
+fn revision_etag(revision: i64) -> ETag {
+    format!("\"{revision}\"").parse().expect("ETag must be valid")
+}
+
+fn last_modified(modified_at: &str) -> Option<SystemTime> {
+    modified_at.parse::<DateTime<Utc>>().ok().map(SystemTime::from)
+}
+
+fn not_modified(
+    headers: &HeaderMap,
+    etag: &ETag,
+    modified_at: Option<SystemTime>
+) -> bool
+{
+    // If-None-Match takes precedence over If-Modified-Since
+    if headers.contains_key(IF_NONE_MATCH) {
+        headers.typed_get::<IfNoneMatch>()
+            .is_some_and(|inm| !inm.precondition_passes(etag))
+    }
+    else {
+        match (headers.typed_get::<IfModifiedSince>(), modified_at) {
+            (Some(ims), Some(modified_at)) => !ims.is_modified(modified_at),
+            _ => false
+        }
+    }
+}
+
+fn body_etag(body: &[u8]) -> ETag {
+    format!("W/\"{:x}\"", Sha256::digest(body))
+        .parse()
+        .expect("ETag must be valid")
+}
+
+fn conditional_json<T: Serialize>(
+    headers: &HeaderMap,
+    etag: ETag,
+    modified_at: Option<SystemTime>,
+    data: T
+) -> Response
+{
+    conditional_response(headers, etag, modified_at, Json(data))
+}
+
+fn conditional_response<R: IntoResponse>(
+    headers: &HeaderMap,
+    etag: ETag,
+    modified_at: Option<SystemTime>,
+    body: R
+) -> Response
+{
+    let mut validators = HeaderMap::new();
+    validators.typed_insert(etag.clone());
+    if let Some(t) = modified_at {
+        validators.typed_insert(LastModified::from(t));
+    }
+
+    match not_modified(headers, &etag, modified_at) {
+        true => (StatusCode::NOT_MODIFIED, validators).into_response(),
+        false => (validators, body).into_response()
+    }
+}
+
 pub async fn projects_get(
-    Wrapper(Query(params)): Wrapper<Query<ProjectsParams>>,
+    Wrapper(Query(params)): Wrapper<Query<MaybeProjectsParams>>,
+    headers: HeaderMap,
     State(core): State<CoreArc>
-) -> Result<Json<Projects>, AppError>
+) -> Result<Response, AppError>
+{
+    let params = ProjectsParams::try_from(params)?;
+    let projects = core.get_projects(params).await?;
+
+    // the page changes whenever its body does
+    let body = serde_json::to_vec(&projects)
+        .or(Err(AppError::InternalError))?;
+    let etag = body_etag(&body);
+
+    let modified_at = projects.projects.iter()
+        .filter_map(|p| last_modified(&p.modified_at))
+        .max();
+
+    Ok(
+        conditional_response(
+            &headers,
+            etag,
+            modified_at,
+            ([(CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())], body)
+        )
+    )
+}
+
+fn if_match_revision(
+    headers: &HeaderMap
+) -> Result<Option<i64>, AppError>
 {
-    Ok(Json(core.get_projects(params).await?))
+    // requests without If-Match are unconditional
+    if !headers.contains_key(IF_MATCH) {
+        return Ok(None);
+    }
+
+    let if_match = headers.typed_get::<IfMatch>()
+        .ok_or(AppError::MalformedQuery)?;
+
+    if if_match == IfMatch::any() {
+        return Ok(None);
+    }
+
+    // our ETags are quoted revisions; anything else can never match. The
+    // revision is checked when the edit is written, so the project cannot
+    // change in between.
+    headers.get(IF_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().strip_prefix('"')?.strip_suffix('"'))
+        .and_then(|v| v.parse::<i64>().ok())
+        .map(Some)
+        .ok_or(AppError::PreconditionFailed)
+}
+
+fn render(proj_data: &mut ProjectData, format: Option<RenderFormat>, project_path: &str) {
+    if let Some(RenderFormat::Html) = format {
+        proj_data.readme_html = Some(
+            render_readme(&proj_data.readme, &format!("{project_path}/images/"))
+        );
+    }
 }
 
 pub async fn project_get(
     proj: Project,
+    OriginalUri(uri): OriginalUri,
+    Wrapper(Query(params)): Wrapper<Query<RenderParams>>,
+    headers: HeaderMap,
     State(core): State<CoreArc>
-) -> Result<Json<ProjectData>, AppError>
+) -> Result<Response, AppError>
 {
-    Ok(Json(core.get_project(proj).await?))
+    let mut proj_data = core.get_project(proj).await?;
+    render(&mut proj_data, params.render, uri.path());
+    let etag = revision_etag(proj_data.revision);
+    let modified_at = last_modified(&proj_data.modified_at);
+    Ok(conditional_json(&headers, etag, modified_at, proj_data))
 }
 
 pub async fn project_post(
@@ -56,28 +188,158 @@ pub async fn project_post(
 
 pub async fn project_patch(
     Owned(owner, proj): Owned,
+    headers: HeaderMap,
     State(core): State<CoreArc>,
     Wrapper(Json(proj_data)): Wrapper<Json<ProjectDataPatch>>
 ) -> Result<(), AppError>
 {
-    Ok(core.update_project(owner, proj, &proj_data).await?)
+    let expected = if_match_revision(&headers)?;
+    Ok(core.update_project(owner, proj, expected, &proj_data).await?)
+}
+
+pub async fn project_rename_post(
+    Owned(owner, proj): Owned,
+    State(core): State<CoreArc>,
+    Wrapper(Json(proj_name)): Wrapper<Json<ProjectNamePost>>
+) -> Result<(), AppError>
+{
+    Ok(core.rename_project(owner, proj, &proj_name.name).await?)
+}
+
+pub async fn project_delete(
+    OwnedOrAdmin(owner, proj): OwnedOrAdmin,
+    State(core): State<CoreArc>
+) -> Result<(), AppError>
+{
+    Ok(core.delete_project(owner, proj).await?)
+}
+
+pub async fn project_revert_post(
+    OwnedOrAdmin(owner, proj): OwnedOrAdmin,
+    Path((_, revision)): Path<(String, u32)>,
+    State(core): State<CoreArc>
+) -> Result<(), AppError>
+{
+    Ok(core.revert_project(owner, proj, revision as i64).await?)
+}
+
+pub async fn project_restore_post(
+    OwnedOrAdmin(owner, proj): OwnedOrAdmin,
+    State(core): State<CoreArc>
+) -> Result<(), AppError>
+{
+    Ok(core.restore_project(owner, proj).await?)
 }
 
 pub async fn project_revision_get(
     proj: Project,
     Path((_, revision)): Path<(String, u32)>,
+    OriginalUri(uri): OriginalUri,
+    Wrapper(Query(params)): Wrapper<Query<RenderParams>>,
     State(core): State<CoreArc>
-) -> Result<Json<ProjectData>, AppError>
+) -> Result<impl IntoResponse, AppError>
 {
-    Ok(Json(core.get_project_revision(proj, revision as i64).await?))
+    let mut proj_data = core.get_project_revision(proj, revision as i64).await?;
+
+    // strip the revision to get the project path
+    let project_path = uri.path()
+        .rsplit_once('/')
+        .map_or(uri.path(), |(path, _)| path);
+    render(&mut proj_data, params.render, project_path);
+
+    // old revisions never change
+    Ok((
+        [(CACHE_CONTROL, "public, max-age=31536000, immutable")],
+        Json(proj_data)
+    ))
+}
+
+pub async fn project_revisions_get(
+    proj: Project,
+    Wrapper(Query(params)): Wrapper<Query<MaybeRevisionsParams>>,
+    State(core): State<CoreArc>
+) -> Result<Json<Revisions>, AppError>
+{
+    let params = RevisionsParams::try_from(params)?;
+    Ok(Json(core.get_project_revisions(proj, params).await?))
+}
+
+pub async fn project_diff_get(
+    proj: Project,
+    Wrapper(Query(params)): Wrapper<Query<DiffParams>>,
+    State(core): State<CoreArc>
+) -> Result<Json<ProjectDiff>, AppError>
+{
+    Ok(
+        Json(
+            core.get_project_diff(
+                proj,
+                params.from as i64,
+                params.to as i64
+            ).await?
+        )
+    )
+}
+
+pub async fn game_get(
+    game: Game,
+    State(core): State<CoreArc>
+) -> Result<Json<GameProjects>, AppError>
+{
+    Ok(Json(core.get_game(game).await?))
+}
+
+pub async fn game_patch(
+    Admin(user): Admin,
+    game: Game,
+    State(core): State<CoreArc>,
+    Wrapper(Json(game_data)): Wrapper<Json<GamePatch>>
+) -> Result<(), AppError>
+{
+    // games are shared by projects with different owners, so only admins
+    // may change them
+    Ok(core.update_game(Owner(user.0), game, &game_data).await?)
+}
+
+pub async fn suggest_get(
+    Wrapper(Query(params)): Wrapper<Query<MaybeSuggestParams>>,
+    State(core): State<CoreArc>
+) -> Result<Json<SearchSuggestions>, AppError>
+{
+    let params = SuggestParams::try_from(params)?;
+    Ok(Json(core.get_search_suggestions(params).await?))
+}
+
+pub async fn search_reindex_post(
+    _: Admin,
+    State(core): State<CoreArc>
+) -> Result<Json<SearchIndexReport>, AppError>
+{
+    Ok(Json(core.reindex_search().await?))
 }
 
 pub async fn owners_get(
     proj: Project,
+    headers: HeaderMap,
     State(core): State<CoreArc>
-) -> Result<Json<Users>, AppError>
+) -> Result<Response, AppError>
 {
-    Ok(Json(core.get_owners(proj).await?))
+    let owners = core.get_owners(proj).await?;
+
+    // owner changes don't make project revisions, so the owners change
+    // whenever their body does
+    let body = serde_json::to_vec(&owners)
+        .or(Err(AppError::InternalError))?;
+    let etag = body_etag(&body);
+
+    Ok(
+        conditional_response(
+            &headers,
+            etag,
+            None,
+            ([(CONTENT_TYPE, mime::APPLICATION_JSON.as_ref())], body)
+        )
+    )
 }
 
 pub async fn owners_add(
@@ -127,11 +389,13 @@ pub async fn players_remove(
 pub async fn packages_post(
     Owned(owner, proj): Owned,
     Path((_, pkg)): Path<(String, String)>,
+    headers: HeaderMap,
     State(core): State<CoreArc>,
     Wrapper(Json(pkg_data)): Wrapper<Json<PackageDataPost>>
 ) -> Result<(), AppError>
 {
-    Ok(core.create_package(owner, proj, &pkg, &pkg_data).await?)
+    let expected = if_match_revision(&headers)?;
+    Ok(core.create_package(owner, proj, expected, &pkg, &pkg_data).await?)
 }
 
 // TODO
@@ -171,26 +435,33 @@ fn into_stream(
 pub async fn release_put(
     Owned(owner, proj): Owned,
     Path((_, pkg, version)): Path<(String, String, String)>,
+    headers: HeaderMap,
     State(core): State<CoreArc>,
     request: Request
 ) -> Result<(), AppError>
 {
     let version = version.parse::<Version>()
-        .or(Err(AppError::NotFound))?;
-
-/*
-    let stream = request.into_body()
-        .into_data_stream()
-        .map_err(|err| io::Error::new(io::ErrorKind::Other, err));
+        .or(Err(AppError::MalformedVersion))?;
 
-    core.add_image(&owner, proj, &img_name, Box::new(stream))
-        .await
-        .or(Err(AppError::InternalError))?;
-*/
+    // TODO: pass the expected revision to add_release once releases are
+    // stored; until then nothing is written, so checking here is enough
+    if let Some(expected) = if_match_revision(&headers)? {
+        if core.get_current_revision(proj).await? != expected {
+            return Err(AppError::PreconditionFailed);
+        }
+    }
 
     Ok(())
 }
 
+pub async fn images_get(
+    proj: Project,
+    State(core): State<CoreArc>
+) -> Result<Json<Images>, AppError>
+{
+    Ok(Json(core.get_images(proj).await?))
+}
+
 pub async fn image_get(
     proj: Project,
     Path((_, img_name)): Path<(String, String)>,
@@ -218,24 +489,39 @@ pub async fn image_post(
     Path((_, img_name)): Path<(String, String)>,
     content_type: Option<TypedHeader<ContentType>>,
     content_length: Option<TypedHeader<ContentLength>>,
+    headers: HeaderMap,
     State(core): State<CoreArc>,
     request: Request
 ) -> Result<(), AppError>
 {
+    let expected = if_match_revision(&headers)?;
+
     // NB: No ContentType header will result in BAD_REQUEST by default, so
     // have to make it optional and check manually
     Ok(
         core.add_image(
             owner,
             proj,
-            &img_name,
-            &content_type.ok_or(AppError::BadMimeType)?.0.into(),
-            content_length.map(|h| h.0.0),
+            expected,
+            &ImageUpload {
+                name: &img_name,
+                content_type: &content_type.ok_or(AppError::BadMimeType)?.0.into(),
+                content_length: content_length.map(|h| h.0.0)
+            },
             into_stream(request)
         ).await?
     )
 }
 
+pub async fn image_delete(
+    Owned(owner, proj): Owned,
+    Path((_, img_name)): Path<(String, String)>,
+    State(core): State<CoreArc>
+) -> Result<(), AppError>
+{
+    Ok(core.remove_image(owner, proj, &img_name).await?)
+}
+
 pub async fn flag_post(
     _requester: User,
     _proj: Project,
@@ -2,7 +2,7 @@ use axum::{
     Router, serve,
     body::{Body, Bytes},
     extract::Request,
-    http::StatusCode,
+    http::{StatusCode, header::{CONTENT_TYPE, LOCATION}},
     response::{IntoResponse, Json, Response},
     routing::{get, post}
 };
@@ -28,6 +28,7 @@ mod app;
 mod config;
 mod core;
 mod db;
+mod diff;
 mod errors;
 mod extractors;
 mod handlers;
@@ -37,20 +38,26 @@ mod module;
 mod pagination;
 mod params;
 mod prod_core;
+mod render;
+mod search;
+mod sort_key;
 mod sqlite;
 mod time;
 mod upload;
+mod validation;
 mod version;
 
 use crate::{
     app::AppState,
     config::Config,
-    core::CoreArc,
+    core::{CoreArc, CoreError},
+    db::DatabaseClient,
     prod_core::ProdCore,
     errors::AppError,
     jwt::DecodingKey,
     sqlite::SqlxDatabaseClient,
     upload::LocalUploader,
+    validation::FieldError
 };
 
 impl From<&AppError> for StatusCode {
@@ -58,36 +65,87 @@ impl From<&AppError> for StatusCode {
         match err {
             AppError::BadMimeType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             AppError::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
-            AppError::CannotRemoveLastOwner => StatusCode::BAD_REQUEST,
+            AppError::CannotRemoveLastOwner => StatusCode::CONFLICT,
+            AppError::ImageInUse => StatusCode::CONFLICT,
+            AppError::Conflict => StatusCode::CONFLICT,
+            AppError::GameExists => StatusCode::CONFLICT,
+            AppError::PackageExists => StatusCode::CONFLICT,
+            AppError::VersionExists => StatusCode::CONFLICT,
             AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
             AppError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::InvalidFields(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InvalidProjectName => StatusCode::BAD_REQUEST,
+            AppError::InvalidReference => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InvalidSearchQuery(_) => StatusCode::BAD_REQUEST,
             AppError::JsonError => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::LimitOutOfRange => StatusCode::BAD_REQUEST,
             AppError::MalformedQuery => StatusCode::BAD_REQUEST,
             AppError::MalformedVersion => StatusCode::BAD_REQUEST,
+            AppError::MovedPermanently(_) => StatusCode::MOVED_PERMANENTLY,
+            AppError::NotAGame => StatusCode::NOT_FOUND,
+            AppError::NotAPackage => StatusCode::NOT_FOUND,
+            AppError::NotAProject => StatusCode::NOT_FOUND,
+            AppError::NotARevision => StatusCode::NOT_FOUND,
             AppError::NotAUser => StatusCode::NOT_FOUND,
+            AppError::NotAVersion => StatusCode::NOT_FOUND,
             AppError::NotFound => StatusCode::NOT_FOUND,
+            AppError::PermanentRedirect(_) => StatusCode::PERMANENT_REDIRECT,
+            AppError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            AppError::ProjectNameInUse => StatusCode::CONFLICT,
             AppError::Unauthorized => StatusCode::UNAUTHORIZED
         }
     }
 }
 
+// Error bodies are RFC 7807 problem details, with the error code and
+// any invalid fields as extension members
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 struct HttpError {
-    error: String
+    status: u16,
+    code: String,
+    title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<FieldError>
 }
 
 impl From<AppError> for HttpError {
     fn from(err: AppError) -> Self {
-        HttpError { error: format!("{}", err) }
+        let status = StatusCode::from(&err).as_u16();
+        let code = err.code().into();
+        let title = format!("{}", err);
+        let (detail, fields) = match err {
+            AppError::InvalidFields(fields) => (None, fields),
+            AppError::InvalidSearchQuery(reason) => (Some(reason), vec![]),
+            _ => (None, vec![])
+        };
+
+        HttpError { status, code, title, detail, fields }
     }
 }
 
+const APPLICATION_PROBLEM_JSON: &str = "application/problem+json";
+
 impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let code = StatusCode::from(&self);
-        let body = Json(HttpError::from(self));
-        (code, body).into_response()
+        match self {
+            AppError::MovedPermanently(location) |
+            AppError::PermanentRedirect(location) =>
+                (code, [(LOCATION, location)]).into_response(),
+            _ => {
+                // database errors are for us, not for clients
+                if let AppError::DatabaseError(ref e) = self {
+                    eprintln!("database error: {e}");
+                }
+                (
+                    code,
+                    [(CONTENT_TYPE, APPLICATION_PROBLEM_JSON)],
+                    Json(HttpError::from(self))
+                ).into_response()
+            }
+        }
     }
 }
 
@@ -106,6 +164,27 @@ fn routes(api: &str) -> Router<AppState> {
             get(handlers::project_get)
             .post(handlers::project_post)
             .patch(handlers::project_patch)
+            .delete(handlers::project_delete)
+        )
+        .route(
+            &format!("{api}/projects/:proj/rename"),
+            post(handlers::project_rename_post)
+        )
+        .route(
+            &format!("{api}/projects/:proj/revert/:revision"),
+            post(handlers::project_revert_post)
+        )
+        .route(
+            &format!("{api}/projects/:proj/restore"),
+            post(handlers::project_restore_post)
+        )
+        .route(
+            &format!("{api}/projects/:proj/revisions"),
+            get(handlers::project_revisions_get)
+        )
+        .route(
+            &format!("{api}/projects/:proj/diff"),
+            get(handlers::project_diff_get)
         )
         .route(
             &format!("{api}/projects/:proj/:revision"),
@@ -133,10 +212,15 @@ fn routes(api: &str) -> Router<AppState> {
             get(handlers::release_version_get)
             .put(handlers::release_put)
         )
+        .route(
+            &format!("{api}/projects/:proj/images"),
+            get(handlers::images_get)
+        )
         .route(
             &format!("{api}/projects/:proj/images/:img_name"),
             get(handlers::image_get)
             .post(handlers::image_post)
+            .delete(handlers::image_delete)
         )
         .route(
             &format!("{api}/projects/:proj/images/:img_name/:revision"),
@@ -146,6 +230,19 @@ fn routes(api: &str) -> Router<AppState> {
             &format!("{api}/projects/:proj/flag"),
             post(handlers::flag_post)
         )
+        .route(
+            &format!("{api}/games/:game"),
+            get(handlers::game_get)
+            .patch(handlers::game_patch)
+        )
+        .route(
+            &format!("{api}/suggest"),
+            get(handlers::suggest_get)
+        )
+        .route(
+            &format!("{api}/admin/search/reindex"),
+            post(handlers::search_reindex_post)
+        )
         .fallback(handlers::not_found)
         .layer(
             ServiceBuilder::new()
@@ -165,6 +262,8 @@ enum StartupError {
     #[error("{0}")]
     DatabaseError(#[from] sqlx::Error),
     #[error("{0}")]
+    CoreError(#[from] CoreError),
+    #[error("{0}")]
     IOError(#[from] io::Error)
 }
 
@@ -197,16 +296,39 @@ async fn main() -> Result<(), StartupError> {
         .connect(&format!("sqlite://{}", &config.db_path))
         .await?;
 
+    let db = SqlxDatabaseClient(db_pool);
+
+    // the column weights are configurable, so are set on every startup
+    db.set_search_weights(&config.search_weights).await?;
+
     let core = ProdCore {
-        db: SqlxDatabaseClient(db_pool),
+        db,
         uploader: LocalUploader { uploads_directory: "uploads".into() },
         now: Utc::now,
-        max_image_size: (config.max_image_size as u64) << 20 // MB to bytes
+        max_image_size: (config.max_image_size as u64) << 20, // MB to bytes
+        deletion_grace_period: chrono::Duration::days(
+            config.deletion_grace_days.into()
+        ),
+        limits: config.limits
     };
 
+    let core = Arc::new(core) as CoreArc;
+
+    // periodically purge deleted projects which are past their grace period
+    let purge_core = core.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(3600));
+        loop {
+            interval.tick().await;
+            if let Err(e) = purge_core.purge_projects().await {
+                eprintln!("failed to purge deleted projects: {e}");
+            }
+        }
+    });
+
     let state = AppState {
         key: DecodingKey::from_secret(config.jwt_key.as_bytes()),
-        core: Arc::new(core) as CoreArc
+        core
     };
 
     let api = &config.api_base_path;
@@ -232,25 +354,26 @@ mod test {
         body::{self, Body, Bytes},
         http::{
             Method, Request,
-            header::{ACCEPT_ENCODING, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, LOCATION}
+            header::{ACCEPT_ENCODING, AUTHORIZATION, CACHE_CONTROL, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MATCH, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION}
         }
     };
     use futures::Stream;
-    use mime::{APPLICATION_JSON, IMAGE_PNG, TEXT_PLAIN, Mime};
+    use mime::{APPLICATION_JSON, IMAGE_PNG, TEXT_PLAIN};
     use once_cell::sync::Lazy;
     use nix::{
         sys::{self, signal::Signal},
         unistd::Pid
     };
+    use sha2::Digest;
     use std::future::IntoFuture;
     use tower::ServiceExt; // for oneshot
 
     use crate::{
-        core::{Core, CoreError},
+        core::{Core, CoreError, ImageUpload},
         jwt::{self, EncodingKey},
-        model::{GameData, Owner, PackageData, Package, ProjectData, ProjectDataPatch, ProjectDataPost, Project, Projects, ProjectSummary, FileData, User, Users},
+        model::{FieldDiff, Game, GameData, GameDataDiff, GameDataPatch, GamePatch, GameProjects, ImageData, Images, Owner, PackageData, PackageDataPost, Package, ProjectData, ProjectDataPatch, ProjectDataPost, Project, ProjectDiff, Projects, ProjectSummary, FileData, RevisionSummary, Revisions, SearchIndexMismatch, SearchIndexReport, SearchSuggestions, User, Users},
         pagination::{Anchor, Direction, Limit, SortBy, Pagination, Seek, SeekLink},
-        params::ProjectsParams,
+        params::{ProjectsParams, RevisionsParams, SuggestParams},
         version::Version
     };
 
@@ -269,6 +392,29 @@ mod test {
         body_bytes(r).await.is_empty()
     }
 
+    static PROJECT_DIFF: Lazy<ProjectDiff> = Lazy::new(||
+        ProjectDiff {
+            from: 1,
+            to: 2,
+            name: None,
+            description: Some(
+                FieldDiff {
+                    from: "whatever".into(),
+                    to: "something else".into()
+                }
+            ),
+            tags: None,
+            game: GameDataDiff::default(),
+            readme: None,
+            image: None,
+            gallery: None,
+            packages_added: vec![],
+            packages_removed: vec![],
+            releases_added: vec![],
+            releases_removed: vec![]
+        }
+    );
+
     static PROJECT_SUMMARY_A: Lazy<ProjectSummary> = Lazy::new(||
         ProjectSummary {
             name: "project_a".into(),
@@ -278,11 +424,14 @@ mod test {
             modified_at: "2024-03-29T16:51:08+00:00".into(),
             tags: vec![],
             game: GameData {
+                id: None,
                 title: "a".into(),
                 title_sort_key: "a".into(),
                 publisher: "p".into(),
-                year: "2024".into()
-            }
+                year: "2024".into(),
+                ..Default::default()
+            },
+            highlights: None
         }
     );
 
@@ -295,11 +444,14 @@ mod test {
             modified_at: "2024-03-29T17:00:23+00:00".into(),
             tags: vec![],
             game: GameData {
+                id: None,
                 title: "b".into(),
                 title_sort_key: "b".into(),
                 publisher: "p".into(),
-                year: "2024".into()
-            }
+                year: "2024".into(),
+                ..Default::default()
+            },
+            highlights: None
         }
     );
 
@@ -314,13 +466,17 @@ mod test {
             modified_at: "2023-10-30T18:53:53,056386142+00:00".into(),
             tags: vec![],
             game: GameData {
+                id: None,
                 title: "Empires in Arms".into(),
                 title_sort_key: "Empires in Arms".into(),
                 publisher: "Avalon Hill".into(),
-                year: "1983".into()
+                year: "1983".into(),
+                ..Default::default()
             },
             readme: "".into(),
+            readme_html: None,
             image: None,
+            gallery: vec![],
             owners: vec!["alice".into(), "bob".into()],
             packages: vec![
                 PackageData {
@@ -361,6 +517,55 @@ mod test {
             }
         }
 
+        async fn get_deleted_project_id(
+            &self,
+            proj: &str,
+        ) -> Result<Project, CoreError>
+        {
+            match proj {
+                "deleted_project" => Ok(Project(2)),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
+
+        async fn revert_project(
+            &self,
+            _owner: Owner,
+            _proj: Project,
+            revision: i64
+        ) -> Result<(), CoreError>
+        {
+            match revision {
+                1 => Ok(()),
+                _ => Err(CoreError::NotARevision)
+            }
+        }
+
+        async fn get_project_alias(
+            &self,
+            proj: &str,
+        ) -> Result<String, CoreError>
+        {
+            match proj {
+                "old_project" => Ok("a_project".into()),
+                _ => Err(CoreError::NotAProject)
+            }
+        }
+
+        async fn rename_project(
+            &self,
+            _owner: Owner,
+            _proj: Project,
+            name: &str
+        ) -> Result<(), CoreError>
+        {
+            match name {
+                "a_game" => Err(CoreError::ProjectNameInUse),
+                "-bad" => Err(CoreError::InvalidProjectName),
+                _ => Ok(())
+            }
+        }
+
         async fn get_package_id(
             &self,
             _proj: Project,
@@ -373,6 +578,22 @@ mod test {
             }
         }
 
+        async fn create_package(
+            &self,
+            _owner: Owner,
+            _proj: Project,
+            expected: Option<i64>,
+            pkg: &str,
+            _pkg_data: &PackageDataPost
+        ) -> Result<(), CoreError>
+        {
+            match (expected, pkg) {
+                (Some(r), _) if r != 1 => Err(CoreError::PreconditionFailed),
+                (_, "a_package") => Err(CoreError::PackageExists),
+                _ => Ok(())
+            }
+        }
+
         async fn user_is_owner(
             &self,
             user: User,
@@ -382,6 +603,32 @@ mod test {
             Ok(user == User(1) || user == User(2))
         }
 
+        async fn user_is_admin(
+            &self,
+            user: User
+        ) -> Result<bool, CoreError>
+        {
+            Ok(user == User(3))
+        }
+
+        async fn delete_project(
+            &self,
+            _owner: Owner,
+            _proj: Project
+        ) -> Result<(), CoreError>
+        {
+            Ok(())
+        }
+
+        async fn restore_project(
+            &self,
+            _owner: Owner,
+            _proj: Project
+        ) -> Result<(), CoreError>
+        {
+            Ok(())
+        }
+
         async fn add_owners(
             &self,
             _owners: &Users,
@@ -432,7 +679,8 @@ mod test {
                                 &Seek {
                                     anchor: Anchor::Before("project_a".into(), 0),
                                     sort_by: SortBy::ProjectName,
-                                    dir: Direction::Ascending
+                                    dir: Direction::Ascending,
+                                    ..Default::default()
                                 },
                                 params.limit
                             ).unwrap()
@@ -442,12 +690,14 @@ mod test {
                                 &Seek {
                                     anchor: Anchor::After("project_b".into(), 0),
                                     sort_by: SortBy::ProjectName,
-                                    dir: Direction::Ascending
+                                    dir: Direction::Ascending,
+                                    ..Default::default()
                                 },
                                 params.limit
                             ).unwrap()
                         ),
-                        total: 1234
+                        total: 1234,
+                        facets: None
                     }
                 }
             )
@@ -471,14 +721,39 @@ mod test {
             Ok(())
         }
 
+        async fn get_current_revision(
+            &self,
+            _proj: Project
+        ) -> Result<i64, CoreError>
+        {
+            Ok(1)
+        }
+
         async fn update_project(
             &self,
             _owner: Owner,
             _proj: Project,
-            _proj_data: &ProjectDataPatch
+            expected: Option<i64>,
+            proj_data: &ProjectDataPatch
         ) -> Result<(), CoreError>
         {
-            Ok(())
+            if expected.is_some_and(|r| r != 1) {
+                return Err(CoreError::PreconditionFailed);
+            }
+
+            match proj_data.game.title.as_deref() {
+                Some("") => Err(
+                    CoreError::InvalidFields(
+                        vec![
+                            FieldError {
+                                field: "game.title".into(),
+                                reason: "empty".into()
+                            }
+                        ]
+                    )
+                ),
+                _ => Ok(())
+            }
         }
 
         async fn get_project_revision(
@@ -493,6 +768,45 @@ mod test {
             }
         }
 
+        async fn get_project_diff(
+            &self,
+            _proj: Project,
+            from: i64,
+            to: i64
+        ) -> Result<ProjectDiff, CoreError>
+        {
+            match (from, to) {
+                (1, 2) => Ok(PROJECT_DIFF.clone()),
+                _ => Err(CoreError::NotARevision)
+            }
+        }
+
+        async fn get_project_revisions(
+            &self,
+            _proj: Project,
+            _params: RevisionsParams
+        ) -> Result<Revisions, CoreError>
+        {
+            Ok(
+                Revisions {
+                    revisions: vec![
+                        RevisionSummary {
+                            revision: 1,
+                            modified_at: "2024-03-29T16:51:08+00:00".into(),
+                            modified_by: "bob".into(),
+                            changes: vec!["created".into()]
+                        }
+                    ],
+                    meta: Pagination {
+                        prev_page: None,
+                        next_page: None,
+                        total: 1,
+                        facets: None
+                    }
+                }
+            )
+        }
+
         async fn get_release(
             &self,
             _proj: Project,
@@ -564,20 +878,131 @@ mod test {
             }
         }
 
+        async fn get_images(
+            &self,
+            _proj: Project
+        ) -> Result<Images, CoreError>
+        {
+            Ok(
+                Images {
+                    images: vec![
+                        ImageData {
+                            filename: "img.png".into(),
+                            url: "https://example.com/img.png".into(),
+                            content_type: "image/png".into(),
+                            size: 1234,
+                            published_at: "2023-10-30T18:53:53,056386142+00:00".into(),
+                            published_by: "alice".into()
+                        }
+                    ]
+                }
+            )
+        }
+
+        async fn remove_image(
+            &self,
+            _owner: Owner,
+            proj: Project,
+            img_name: &str
+        ) -> Result<(), CoreError>
+        {
+            if proj == Project(1) && img_name == "img.png" {
+                Ok(())
+            }
+            else if proj == Project(1) && img_name == "current.png" {
+                Err(CoreError::ImageInUse)
+            }
+            else {
+                Err(CoreError::NotFound)
+            }
+        }
+
+        async fn get_game(
+            &self,
+            game: Game
+        ) -> Result<GameProjects, CoreError>
+        {
+            match game {
+                Game(1) => Ok(
+                    GameProjects {
+                        game: GameData {
+                            id: Some(1),
+                            title: "A Game of Tests".into(),
+                            title_sort_key: "Game of Tests, A".into(),
+                            publisher: "Test Game Company".into(),
+                            year: "1979".into(),
+                            ..Default::default()
+                        },
+                        projects: vec!["a_project".into()]
+                    }
+                ),
+                _ => Err(CoreError::NotAGame)
+            }
+        }
+
+        async fn update_game(
+            &self,
+            _owner: Owner,
+            game: Game,
+            _game_data: &GamePatch
+        ) -> Result<(), CoreError>
+        {
+            match game {
+                Game(1) => Ok(()),
+                _ => Err(CoreError::NotAGame)
+            }
+        }
+
+        async fn get_search_suggestions(
+            &self,
+            params: SuggestParams
+        ) -> Result<SearchSuggestions, CoreError>
+        {
+            match params.q.as_str() {
+                "tes" => Ok(
+                    SearchSuggestions {
+                        projects: vec![],
+                        titles: vec!["A Game of Tests".into()],
+                        publishers: vec!["Test Game Company".into()]
+                    }
+                ),
+                _ => Ok(SearchSuggestions::default())
+            }
+        }
+
+        async fn reindex_search(
+            &self
+        ) -> Result<SearchIndexReport, CoreError>
+        {
+            Ok(
+                SearchIndexReport {
+                    mismatched: vec![
+                        SearchIndexMismatch {
+                            project_id: 42,
+                            name: Some("a_project".into())
+                        }
+                    ],
+                    unresolved: vec![]
+                }
+            )
+        }
+
         async fn add_image(
             &self,
             _owner: Owner,
             _proj: Project,
-            _img_name: &str,
-            content_type: &Mime,
-            content_length: Option<u64>,
+            expected: Option<i64>,
+            img: &ImageUpload<'_>,
             _stream: Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>
         ) -> Result<(), CoreError>
         {
-            if content_length > Some(1 << 20) {
+            if expected.is_some_and(|r| r != 1) {
+                Err(CoreError::PreconditionFailed)
+            }
+            else if img.content_length > Some(1 << 20) {
                 Err(CoreError::TooLarge)
             }
-            else if content_type == &TEXT_PLAIN {
+            else if img.content_type == &TEXT_PLAIN {
                 Err(CoreError::BadMimeType)
             }
             else {
@@ -783,8 +1208,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
-
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
@@ -794,12 +1219,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -830,7 +1257,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
@@ -840,77 +1268,110 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
     }
 
     #[tokio::test]
-    async fn get_projects_limit_zero() {
+    async fn get_projects_validators() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects?limit=0"))
+                .uri(&format!("{API_V1}/projects"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::LimitOutOfRange)
+            response.headers().get(LAST_MODIFIED).unwrap(),
+            "Fri, 29 Mar 2024 17:00:23 GMT"
+        );
+
+        // the ETag is the SHA-256 of the body
+        let etag = response.headers().get(ETAG).unwrap().clone();
+        let body = body_bytes(response).await;
+        assert_eq!(
+            etag,
+            format!("W/\"{:x}\"", sha2::Sha256::digest(&body)).as_str()
         );
     }
 
     #[tokio::test]
-    async fn get_projects_limit_too_large() {
+    async fn get_projects_if_none_match() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects?limit=100000"))
+                .uri(&format!("{API_V1}/projects"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::LimitOutOfRange)
-        );
+        let etag = response.headers().get(ETAG).unwrap().clone();
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects"))
+                .header(IF_NONE_MATCH, etag.clone())
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn get_projects_limit_empty() {
+    async fn get_projects_if_modified_since() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects?limit="))
+                .uri(&format!("{API_V1}/projects"))
+                .header(IF_MODIFIED_SINCE, "Fri, 29 Mar 2024 17:00:23 GMT")
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::LimitOutOfRange)
-        );
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn get_projects_limit_not_a_number() {
+    async fn get_projects_modified_since() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects?limit=eleventeen"))
+                .uri(&format!("{API_V1}/projects"))
+                .header(IF_MODIFIED_SINCE, "Fri, 29 Mar 2024 17:00:22 GMT")
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn get_projects_limit_zero() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects?limit=0"))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -924,26 +1385,125 @@ mod test {
     }
 
     #[tokio::test]
-    async fn get_projects_seek_start_ok() {
-        let query = SeekLink::new(
-            &Seek {
-                anchor: Anchor::Start,
-                sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
-            },
-            None
-        ).unwrap();
-
+    async fn get_projects_unterminated_phrase() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects{query}"))
+                .uri(&format!("{API_V1}/projects?q=%22unterminated"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError {
+                status: 400,
+                code: "invalid_search_query".into(),
+                title: "Invalid search query".into(),
+                detail: Some("phrase is unterminated".into()),
+                fields: vec![]
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_projects_unknown_field() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects?q=col:x"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(
+                AppError::InvalidSearchQuery("field col is unknown".into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn get_projects_limit_too_large() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects?limit=100000"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::LimitOutOfRange)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_projects_limit_empty() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects?limit="))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::LimitOutOfRange)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_projects_limit_not_a_number() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects?limit=eleventeen"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::LimitOutOfRange)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_projects_seek_start_ok() {
+        let query = SeekLink::new(
+            &Seek {
+                anchor: Anchor::Start,
+                sort_by: SortBy::ProjectName,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            None
+        ).unwrap();
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects{query}"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
             body_as::<Projects>(response).await,
             Projects {
@@ -957,7 +1517,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
@@ -967,12 +1528,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -984,7 +1547,8 @@ mod test {
             &Seek {
                 anchor: Anchor::Start,
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Descending
+                dir: Direction::Descending,
+                ..Default::default()
             },
             None
         ).unwrap();
@@ -1012,7 +1576,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
@@ -1022,12 +1587,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -1039,7 +1606,8 @@ mod test {
             &Seek {
                 anchor: Anchor::Before("xyz".into(), 0),
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
+                dir: Direction::Ascending,
+                ..Default::default()
             },
             None
         ).unwrap();
@@ -1067,7 +1635,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
@@ -1077,12 +1646,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -1094,7 +1665,8 @@ mod test {
             &Seek {
                 anchor: Anchor::After("xyz".into(), 0),
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
+                dir: Direction::Ascending,
+                ..Default::default()
             },
             None
         ).unwrap();
@@ -1122,7 +1694,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
@@ -1132,12 +1705,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             None
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -1187,7 +1762,8 @@ mod test {
             &Seek {
                 anchor: Anchor::Before(long, 0),
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
+                dir: Direction::Ascending,
+                ..Default::default()
             },
             Limit::new(5)
         ).unwrap();
@@ -1214,7 +1790,8 @@ mod test {
             &Seek {
                 anchor: Anchor::Start,
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
+                dir: Direction::Ascending,
+                ..Default::default()
             },
             Limit::new(5)
         ).unwrap();
@@ -1242,7 +1819,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
@@ -1252,12 +1830,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -1269,7 +1849,8 @@ mod test {
             &Seek {
                 anchor: Anchor::Start,
                 sort_by: SortBy::ProjectName,
-                dir: Direction::Ascending
+                dir: Direction::Ascending,
+                ..Default::default()
             },
             Limit::new(5)
         ).unwrap();
@@ -1297,7 +1878,8 @@ mod test {
                             &Seek {
                                 anchor: Anchor::Before("project_a".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
@@ -1307,12 +1889,14 @@ mod test {
                             &Seek {
                                 anchor: Anchor::After("project_b".into(), 0),
                                 sort_by: SortBy::ProjectName,
-                                dir: Direction::Ascending
+                                dir: Direction::Ascending,
+                                ..Default::default()
                             },
                             Limit::new(5)
                         ).unwrap()
                     ),
-                    total: 1234
+                    total: 1234,
+                    facets: None
                 }
             }
         );
@@ -1330,6 +1914,7 @@ mod test {
         .await;
 
         assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(ETAG).unwrap(), "\"1\"");
         assert_eq!(
             body_as::<ProjectData>(response).await,
             *EIA_PROJECT_DATA
@@ -1337,358 +1922,305 @@ mod test {
     }
 
     #[tokio::test]
-    async fn get_project_not_a_project() {
+    async fn get_project_render_html() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .uri(&format!("{API_V1}/projects/a_project?render=html"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            body_as::<ProjectData>(response).await,
+            ProjectData {
+                readme_html: Some("".into()),
+                ..EIA_PROJECT_DATA.clone()
+            }
         );
     }
 
     #[tokio::test]
-    async fn post_project_ok() {
-        let proj_data = ProjectDataPost {
-            description: "A module for Empires in Arms".into(),
-            tags: vec![],
-            game: GameData {
-                title: "Empires in Arms".into(),
-                title_sort_key: "Empires in Arms".into(),
-                publisher: "Avalon Hill".into(),
-                year: "1983".into()
-            },
-            readme: "".into(),
-            image: None
-        };
-
+    async fn get_project_render_bad() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/not_a_project"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project?render=pdf"))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::MalformedQuery)
+        );
     }
 
     #[tokio::test]
-    async fn post_project_unauth() {
-        let proj_data = ProjectDataPost {
-            description: "A module for Empires in Arms".into(),
-            tags: vec![],
-            game: GameData {
-                title: "Empires in Arms".into(),
-                title_sort_key: "Empires in Arms".into(),
-                publisher: "Avalon Hill".into(),
-                year: "1983".into()
-            },
-            readme: "".into(),
-            image: None
-        };
-
+    async fn get_project_if_none_match() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/not_a_project"))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(IF_NONE_MATCH, "\"1\"")
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::Unauthorized)
-        );
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(ETAG).unwrap(), "\"1\"");
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn post_project_wrong_json() {
+    async fn get_project_if_none_match_stale() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/not_a_project"))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(IF_NONE_MATCH, "\"0\"")
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers().get(ETAG).unwrap(), "\"1\"");
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::JsonError)
+            body_as::<ProjectData>(response).await,
+            *EIA_PROJECT_DATA
         );
     }
 
     #[tokio::test]
-    async fn post_project_wrong_mime_type() {
+    async fn get_project_not_a_project() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
+                .method(Method::GET)
                 .uri(&format!("{API_V1}/projects/not_a_project"))
-                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
-                .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from("stuff"))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            APPLICATION_PROBLEM_JSON
+        );
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn post_project_no_mime_type() {
+    async fn get_project_deleted_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/deleted_project"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from("stuff"))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            body_as::<ProjectData>(response).await,
+            *EIA_PROJECT_DATA
         );
     }
 
     #[tokio::test]
-    async fn patch_project_ok() {
-        let proj_data = ProjectDataPatch {
-            description: Some("A module for Empires in Arms".into()),
-            ..Default::default()
-        };
-
+    async fn get_project_deleted_admin() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
-                .uri(&format!("{API_V1}/projects/a_project"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/deleted_project"))
+                .header(AUTHORIZATION, token(3))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
         assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(
+            body_as::<ProjectData>(response).await,
+            *EIA_PROJECT_DATA
+        );
     }
 
     #[tokio::test]
-    async fn patch_project_clear_image_ok() {
-        let proj_data = ProjectDataPatch {
-            image: Some(None),
-            ..Default::default()
-        };
-
+    async fn get_project_deleted_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
-                .uri(&format!("{API_V1}/projects/a_project"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/deleted_project"))
+                .header(AUTHORIZATION, token(4))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
     }
 
     #[tokio::test]
-    async fn patch_project_no_data() {
+    async fn get_project_deleted_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
-                .uri(&format!("{API_V1}/projects/a_project"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from("{}"))
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/deleted_project"))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(),  StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::JsonError)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn patch_project_unauth() {
-        let proj_data = ProjectDataPatch {
-            description: Some("A module for Empires in Arms".into()),
-            ..Default::default()
-        };
-
+    async fn delete_project_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
+                .method(Method::DELETE)
                 .uri(&format!("{API_V1}/projects/a_project"))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::Unauthorized)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn patch_project_not_owner() {
-        let proj_data = ProjectDataPatch {
-            description: Some("A module for Empires in Arms".into()),
-            ..Default::default()
-        };
-
+    async fn delete_project_admin() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
+                .method(Method::DELETE)
                 .uri(&format!("{API_V1}/projects/a_project"))
-                .header(AUTHORIZATION, token(0))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .header(AUTHORIZATION, token(3))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::Unauthorized)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn patch_project_wrong_json() {
+    async fn delete_project_not_a_project() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
-                .uri(&format!("{API_V1}/projects/a_project"))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::JsonError)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn patch_project_wrong_mime_type() {
+    async fn delete_project_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
+                .method(Method::DELETE)
                 .uri(&format!("{API_V1}/projects/a_project"))
-                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
-                .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from("stuff"))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn patch_project_no_mime_type() {
+    async fn delete_project_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::PATCH)
+                .method(Method::DELETE)
                 .uri(&format!("{API_V1}/projects/a_project"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from("stuff"))
+                .header(AUTHORIZATION, token(4))
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn get_project_revision_ok() {
+    async fn restore_project_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/1"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/deleted_project/restore"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
         assert_eq!(response.status(), StatusCode::OK);
-        assert_eq!(
-            body_as::<ProjectData>(response).await,
-            *EIA_PROJECT_DATA
-        );
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn get_project_revision_not_a_project() {
+    async fn restore_project_admin() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/1"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/deleted_project/restore"))
+                .header(AUTHORIZATION, token(3))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn get_project_revision_not_a_revision() {
+    async fn restore_project_not_a_project() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/2"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project/restore"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -1697,34 +2229,35 @@ mod test {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn get_package_ok() {
+    async fn restore_project_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/packages/a_package"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/deleted_project/restore"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
-            response.headers().get(LOCATION).unwrap(),
-            "https://example.com/package"
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn get_package_not_a_project() {
+    async fn restore_project_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/packages/a_package"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/deleted_project/restore"))
+                .header(AUTHORIZATION, token(4))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -1733,54 +2266,51 @@ mod test {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
+
+
     #[tokio::test]
-    async fn get_package_not_a_package() {
+    async fn revert_project_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/packages/not_a_package"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/revert/1"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
-// TODO: get_release tests
-
     #[tokio::test]
-    async fn get_release_version_ok() {
+    async fn revert_project_admin() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/packages/a_package/1.2.3"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/revert/1"))
+                .header(AUTHORIZATION, token(3))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::SEE_OTHER);
-        assert_eq!(
-            response.headers().get(LOCATION).unwrap(),
-            "https://example.com/package-1.2.3"
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn get_release_version_not_a_project() {
+    async fn revert_project_not_a_revision() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/packages/a_package/1.2.3"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/revert/2"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -1789,132 +2319,127 @@ mod test {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::NotARevision)
         );
     }
 
     #[tokio::test]
-    async fn get_release_version_not_a_package() {
+    async fn revert_project_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/packages/not_a_package/1.2.3"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/revert/1"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn get_release_version_not_a_version() {
+    async fn revert_project_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/packages/a_package/bogus"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/revert/1"))
+                .header(AUTHORIZATION, token(4))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::Unauthorized)
         );
     }
-
     #[tokio::test]
-    async fn get_owners_ok() {
+    async fn get_project_old_name_redirect() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .uri(&format!("{API_V1}/projects/old_project"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
         assert_eq!(
-            body_as::<Users>(response).await,
-            Users {
-                users: vec![
-                    "alice".into(),
-                    "bob".into()
-                ]
-            }
+            response.headers().get(LOCATION).unwrap(),
+            &format!("{API_V1}/projects/a_project")
         );
     }
 
     #[tokio::test]
-    async fn get_owners_bad_project() {
+    async fn get_images_old_name_redirect() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .uri(&format!("{API_V1}/projects/old_project/images?x=1"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::MOVED_PERMANENTLY);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            response.headers().get(LOCATION).unwrap(),
+            &format!("{API_V1}/projects/a_project/images?x=1")
         );
     }
 
     #[tokio::test]
-    async fn put_owners_ok() {
+    async fn patch_project_old_name_redirect() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/old_project"))
                 .header(AUTHORIZATION, token(BOB_UID))
                 .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .body(Body::from(r#"{ "description": "A module" }"#))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            &format!("{API_V1}/projects/a_project")
+        );
     }
 
     #[tokio::test]
-    async fn put_owners_bad_project() {
+    async fn rename_project_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
-                .header(AUTHORIZATION, token(BOB_UID))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/rename"))
                 .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "name": "new_name" }"#))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn put_owners_unauth() {
+    async fn rename_project_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/rename"))
                 .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .body(Body::from(r#"{ "name": "new_name" }"#))
                 .unwrap()
         )
         .await;
@@ -1927,14 +2452,14 @@ mod test {
     }
 
     #[tokio::test]
-    async fn put_owners_not_owner() {
+    async fn rename_project_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
-                .header(AUTHORIZATION, token(0))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/rename"))
                 .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(AUTHORIZATION, token(4))
+                .body(Body::from(r#"{ "name": "new_name" }"#))
                 .unwrap()
         )
         .await;
@@ -1947,73 +2472,1505 @@ mod test {
     }
 
     #[tokio::test]
-    async fn put_owners_wrong_json() {
+    async fn rename_project_invalid_name() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
-                .header(AUTHORIZATION, token(BOB_UID))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/rename"))
                 .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "name": "-bad" }"#))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::JsonError)
+            HttpError::from(AppError::InvalidProjectName)
         );
     }
 
     #[tokio::test]
-    async fn put_owners_wrong_mime_type() {
+    async fn rename_project_name_in_use() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/rename"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
                 .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
-                .body(Body::from("stuff"))
+                .body(Body::from(r#"{ "name": "a_game" }"#))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::ProjectNameInUse)
         );
     }
-
     #[tokio::test]
-    async fn put_owners_no_mime_type() {
+    async fn post_project_ok() {
+        let proj_data = ProjectDataPost {
+            description: "A module for Empires in Arms".into(),
+            tags: vec![],
+            game: GameData {
+                id: None,
+                title: "Empires in Arms".into(),
+                title_sort_key: "Empires in Arms".into(),
+                publisher: "Avalon Hill".into(),
+                year: "1983".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
-        assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
-        );
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
     }
 
     #[tokio::test]
-    async fn delete_owners_ok() {
+    async fn post_project_unauth() {
+        let proj_data = ProjectDataPost {
+            description: "A module for Empires in Arms".into(),
+            tags: vec![],
+            game: GameData {
+                id: None,
+                title: "Empires in Arms".into(),
+                title_sort_key: "Empires in Arms".into(),
+                publisher: "Avalon Hill".into(),
+                year: "1983".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn post_project_wrong_json() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::JsonError)
+        );
+    }
+
+    #[tokio::test]
+    async fn post_project_wrong_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn post_project_no_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_ok() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn patch_project_deleted() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/deleted_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_invalid_fields() {
+        let proj_data = ProjectDataPatch {
+            game: GameDataPatch {
+                title: Some("".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError {
+                status: 422,
+                code: "invalid_fields".into(),
+                title: "Invalid fields".into(),
+                detail: None,
+                fields: vec![
+                    FieldError {
+                        field: "game.title".into(),
+                        reason: "empty".into()
+                    }
+                ]
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_if_match_ok() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(IF_MATCH, "\"1\"")
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn patch_project_if_match_any() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(IF_MATCH, "*")
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn patch_project_if_match_stale() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(IF_MATCH, "\"0\"")
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::PreconditionFailed)
+        );
+    }
+
+    #[tokio::test]
+    async fn post_package_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/packages/b_package"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "description": "" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn post_package_already_exists() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/packages/a_package"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "description": "" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::PackageExists)
+        );
+    }
+
+    #[test]
+    fn http_error_database_error_hides_sql() {
+        let err = HttpError::from(
+            AppError::DatabaseError("UNIQUE constraint failed: x.y".into())
+        );
+        assert_eq!(err.title, "Database error");
+        assert_eq!(err.code, "database_error");
+    }
+
+    #[tokio::test]
+    async fn post_package_if_match_stale() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/packages/b_package"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(IF_MATCH, "\"0\"")
+                .body(Body::from(r#"{ "description": "" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::PreconditionFailed)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_release_if_match_stale() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/packages/a_package/1.2.3"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(IF_MATCH, "\"0\"")
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::PreconditionFailed)
+        );
+    }
+    #[tokio::test]
+    async fn patch_project_clear_image_ok() {
+        let proj_data = ProjectDataPatch {
+            image: Some(None),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn patch_project_no_data() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from("{}"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(),  StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::JsonError)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_unauth() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_not_owner() {
+        let proj_data = ProjectDataPatch {
+            description: Some("A module for Empires in Arms".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(0))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&proj_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_wrong_json() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::JsonError)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_wrong_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn patch_project_no_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/projects/a_project"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revision_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/1"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(CACHE_CONTROL).unwrap(),
+            "public, max-age=31536000, immutable"
+        );
+        assert_eq!(
+            body_as::<ProjectData>(response).await,
+            *EIA_PROJECT_DATA
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revision_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/1"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revision_not_a_revision() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/2"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotARevision)
+        );
+    }
+
+
+    #[tokio::test]
+    async fn get_project_revisions_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/revisions"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_as::<Revisions>(response).await,
+            Revisions {
+                revisions: vec![
+                    RevisionSummary {
+                        revision: 1,
+                        modified_at: "2024-03-29T16:51:08+00:00".into(),
+                        modified_by: "bob".into(),
+                        changes: vec!["created".into()]
+                    }
+                ],
+                meta: Pagination {
+                    prev_page: None,
+                    next_page: None,
+                    total: 1,
+                    facets: None
+                }
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revisions_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/revisions"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revisions_limit_zero() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/revisions?limit=0"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::LimitOutOfRange)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_revisions_bad_seek() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/revisions?seek=cCxhLGEsYWJjLCww"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::MalformedQuery)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_diff_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/diff?from=1&to=2"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_as::<ProjectDiff>(response).await,
+            *PROJECT_DIFF
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_diff_unchanged_null() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/diff?from=1&to=2"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // unchanged fields are present, as null
+        let diff = body_as::<serde_json::Value>(response).await;
+        for field in ["name", "tags", "readme", "image", "gallery"] {
+            assert_eq!(diff.get(field), Some(&serde_json::Value::Null));
+        }
+
+        let game = diff["game"].as_object().unwrap();
+        assert_eq!(game.len(), 11);
+        assert!(game.values().all(|v| v.is_null()));
+    }
+
+    #[tokio::test]
+    async fn get_project_diff_not_a_revision() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/diff?from=1&to=5"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotARevision)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_diff_no_to() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/diff?from=1"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::MalformedQuery)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_project_diff_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/diff?from=1&to=2"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+    #[tokio::test]
+    async fn get_package_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/packages/a_package"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "https://example.com/package"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_package_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/packages/a_package"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_package_not_a_package() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/packages/not_a_package"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAPackage)
+        );
+    }
+
+// TODO: get_release tests
+
+    #[tokio::test]
+    async fn get_release_version_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/packages/a_package/1.2.3"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "https://example.com/package-1.2.3"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_release_version_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/packages/a_package/1.2.3"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_release_version_not_a_package() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/packages/not_a_package/1.2.3"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAPackage)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_release_version_not_a_version() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/packages/a_package/bogus"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAVersion)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_owners_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_as::<Users>(response).await,
+            Users {
+                users: vec![
+                    "alice".into(),
+                    "bob".into()
+                ]
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_owners_if_none_match() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        let etag = response.headers().get(ETAG).unwrap().clone();
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(IF_NONE_MATCH, etag.clone())
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(ETAG).unwrap(), etag);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn get_owners_bad_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn put_owners_bad_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_unauth() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_not_owner() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(0))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_wrong_json() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::JsonError)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_wrong_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_owners_no_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn delete_owners_bad_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_unauth() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_not_owner() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(0))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_wrong_json() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::JsonError)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_wrong_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
+                .body(Body::from("stuff"))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_owners_no_mime_type() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::BadMimeType)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_players_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            body_as::<Users>(response).await,
+            Users {
+                users: vec![
+                    "player 1".into(),
+                    "player 2".into()
+                ]
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn get_players_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/players"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_players_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn put_players_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn put_players_unauth() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_players_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .header(AUTHORIZATION, token(8))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn delete_players_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/not_a_project/players"))
+                .header(AUTHORIZATION, token(8))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_players_unauth() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
+    }
+
+    #[tokio::test]
+    async fn get_image_ok() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(
+            response.headers().get(LOCATION).unwrap(),
+            "https://example.com/img.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn get_image_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/not_a_project/images/img.png"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+   #[tokio::test]
+    async fn get_image_not_an_image() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/projects/a_project/images/not_a.png"))
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn post_image_not_a_project() {
+        let response = try_request(
+            Request::builder()
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/not_a_project/images/img.png"))
+                .header(AUTHORIZATION, token(BOB_UID))
+                .header(CONTENT_LENGTH, 1234)
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .body(Body::empty())
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::NotAProject)
+        );
+    }
+
+   #[tokio::test]
+    async fn post_image_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(CONTENT_LENGTH, 1234)
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
@@ -2022,34 +3979,37 @@ mod test {
         assert!(body_empty(response).await);
     }
 
+
     #[tokio::test]
-    async fn delete_owners_bad_project() {
+    async fn post_image_if_match_stale() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(CONTENT_LENGTH, 1234)
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .header(IF_MATCH, "\"0\"")
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::PreconditionFailed)
         );
     }
-
     #[tokio::test]
-    async fn delete_owners_unauth() {
+    async fn post_image_unauth() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
+                .header(CONTENT_LENGTH, 1234)
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
@@ -2062,14 +4022,15 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_owners_not_owner() {
+    async fn post_image_not_owner() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(0))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(CONTENT_LENGTH, 1234)
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
@@ -2082,34 +4043,35 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_owners_wrong_json() {
+    async fn post_image_no_mime_type() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
-                .body(Body::from(r#"{ "garbage": "whatever" }"#))
+                .header(CONTENT_LENGTH, 1234)
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::JsonError)
+            HttpError::from(AppError::BadMimeType)
         );
     }
 
     #[tokio::test]
-    async fn delete_owners_wrong_mime_type() {
+    async fn post_image_bad_mime_type() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
                 .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
-                .body(Body::from("stuff"))
+                .header(CONTENT_LENGTH, 1234)
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
@@ -2122,30 +4084,32 @@ mod test {
     }
 
     #[tokio::test]
-    async fn delete_owners_no_mime_type() {
+    async fn post_image_too_large() {
         let response = try_request(
             Request::builder()
-                .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/owners"))
+                .method(Method::POST)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .body(Body::from(r#"{ "users": ["alice", "bob"] }"#))
+                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .header(CONTENT_LENGTH, u64::MAX)
+                .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::TooLarge)
         );
     }
 
     #[tokio::test]
-    async fn get_players_ok() {
+    async fn get_images_ok() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .uri(&format!("{API_V1}/projects/a_project/images"))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -2153,22 +4117,28 @@ mod test {
 
         assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<Users>(response).await,
-            Users {
-                users: vec![
-                    "player 1".into(),
-                    "player 2".into()
+            body_as::<Images>(response).await,
+            Images {
+                images: vec![
+                    ImageData {
+                        filename: "img.png".into(),
+                        url: "https://example.com/img.png".into(),
+                        content_type: "image/png".into(),
+                        size: 1234,
+                        published_at: "2023-10-30T18:53:53,056386142+00:00".into(),
+                        published_by: "alice".into()
+                    }
                 ]
             }
         );
     }
 
     #[tokio::test]
-    async fn get_players_not_a_project() {
+    async fn get_images_not_a_project() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/players"))
+                .uri(&format!("{API_V1}/projects/not_a_project/images"))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -2177,16 +4147,16 @@ mod test {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn put_players_ok() {
+    async fn delete_image_ok() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
@@ -2198,11 +4168,11 @@ mod test {
     }
 
     #[tokio::test]
-    async fn put_players_not_a_project() {
+    async fn delete_image_not_a_project() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/not_a_project/owners"))
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/not_a_project/images/img.png"))
                 .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
@@ -2212,69 +4182,73 @@ mod test {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::NotAProject)
         );
     }
 
     #[tokio::test]
-    async fn put_players_unauth() {
+    async fn delete_image_not_an_image() {
         let response = try_request(
             Request::builder()
-                .method(Method::PUT)
-                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .method(Method::DELETE)
+                .uri(&format!("{API_V1}/projects/a_project/images/bogus.png"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::Unauthorized)
+            HttpError::from(AppError::NotFound)
         );
     }
 
     #[tokio::test]
-    async fn delete_players_ok() {
+    async fn delete_image_in_use() {
         let response = try_request(
             Request::builder()
                 .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/players"))
-                .header(AUTHORIZATION, token(8))
+                .uri(&format!("{API_V1}/projects/a_project/images/current.png"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::ImageInUse)
+        );
     }
 
     #[tokio::test]
-    async fn delete_players_not_a_project() {
+    async fn delete_image_unauth() {
         let response = try_request(
             Request::builder()
                 .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/not_a_project/players"))
-                .header(AUTHORIZATION, token(8))
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn delete_players_unauth() {
+    async fn delete_image_not_owner() {
         let response = try_request(
             Request::builder()
                 .method(Method::DELETE)
-                .uri(&format!("{API_V1}/projects/a_project/players"))
+                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
+                .header(AUTHORIZATION, token(0))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -2288,106 +4262,125 @@ mod test {
     }
 
     #[tokio::test]
-    async fn get_image_ok() {
+    async fn get_game_ok() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
+                .uri(&format!("{API_V1}/games/1"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            response.headers().get(LOCATION).unwrap(),
-            "https://example.com/img.png"
+            body_as::<GameProjects>(response).await,
+            GameProjects {
+                game: GameData {
+                    id: Some(1),
+                    title: "A Game of Tests".into(),
+                    title_sort_key: "Game of Tests, A".into(),
+                    publisher: "Test Game Company".into(),
+                    year: "1979".into(),
+                    ..Default::default()
+                },
+                projects: vec!["a_project".into()]
+            }
         );
     }
 
     #[tokio::test]
-    async fn get_image_not_a_project() {
+    async fn get_suggest_ok() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/not_a_project/images/img.png"))
+                .uri(&format!("{API_V1}/suggest?q=tes"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            body_as::<SearchSuggestions>(response).await,
+            SearchSuggestions {
+                projects: vec![],
+                titles: vec!["A Game of Tests".into()],
+                publishers: vec!["Test Game Company".into()]
+            }
         );
     }
 
-   #[tokio::test]
-    async fn get_image_not_an_image() {
+    #[tokio::test]
+    async fn get_suggest_no_q() {
         let response = try_request(
             Request::builder()
                 .method(Method::GET)
-                .uri(&format!("{API_V1}/projects/a_project/images/not_a.png"))
+                .uri(&format!("{API_V1}/suggest"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            HttpError::from(AppError::MalformedQuery)
         );
     }
 
     #[tokio::test]
-    async fn post_image_not_a_project() {
+    async fn post_search_reindex_ok() {
         let response = try_request(
             Request::builder()
                 .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/not_a_project/images/img.png"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_LENGTH, 1234)
-                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .uri(&format!("{API_V1}/admin/search/reindex"))
+                .header(AUTHORIZATION, token(3))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(response.status(), StatusCode::OK);
         assert_eq!(
-            body_as::<HttpError>(response).await,
-            HttpError::from(AppError::NotFound)
+            body_as::<SearchIndexReport>(response).await,
+            SearchIndexReport {
+                mismatched: vec![
+                    SearchIndexMismatch {
+                        project_id: 42,
+                        name: Some("a_project".into())
+                    }
+                ],
+                unresolved: vec![]
+            }
         );
     }
 
-   #[tokio::test]
-    async fn post_image_ok() {
+    #[tokio::test]
+    async fn post_search_reindex_unauth() {
         let response = try_request(
             Request::builder()
                 .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_LENGTH, 1234)
-                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .uri(&format!("{API_V1}/admin/search/reindex"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::OK);
-        assert!(body_empty(response).await);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            body_as::<HttpError>(response).await,
+            HttpError::from(AppError::Unauthorized)
+        );
     }
 
     #[tokio::test]
-    async fn post_image_unauth() {
+    async fn post_search_reindex_not_admin() {
         let response = try_request(
             Request::builder()
                 .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
-                .header(CONTENT_LENGTH, 1234)
-                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .uri(&format!("{API_V1}/admin/search/reindex"))
+                .header(AUTHORIZATION, token(BOB_UID))
                 .body(Body::empty())
                 .unwrap()
         )
@@ -2401,85 +4394,110 @@ mod test {
     }
 
     #[tokio::test]
-    async fn post_image_not_owner() {
+    async fn get_game_not_a_game() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
-                .header(AUTHORIZATION, token(0))
-                .header(CONTENT_LENGTH, 1234)
-                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/games/2"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::Unauthorized)
+            HttpError::from(AppError::NotAGame)
         );
     }
 
     #[tokio::test]
-    async fn post_image_no_mime_type() {
+    async fn get_game_not_an_id() {
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_LENGTH, 1234)
+                .method(Method::GET)
+                .uri(&format!("{API_V1}/games/bogus"))
                 .body(Body::empty())
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::NotAGame)
         );
     }
 
     #[tokio::test]
-    async fn post_image_bad_mime_type() {
+    async fn patch_game_ok() {
+        let game_data = GamePatch {
+            publisher: Some("Test Game Company, Inc.".into()),
+            ..Default::default()
+        };
+
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/games/1"))
+                .header(AUTHORIZATION, token(3))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&game_data).unwrap()))
+                .unwrap()
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(body_empty(response).await);
+    }
+
+    #[tokio::test]
+    async fn patch_game_not_admin() {
+        let game_data = GamePatch {
+            publisher: Some("Test Game Company, Inc.".into()),
+            ..Default::default()
+        };
+
+        let response = try_request(
+            Request::builder()
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/games/1"))
                 .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, TEXT_PLAIN.as_ref())
-                .header(CONTENT_LENGTH, 1234)
-                .body(Body::empty())
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&game_data).unwrap()))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::BadMimeType)
+            HttpError::from(AppError::Unauthorized)
         );
     }
 
     #[tokio::test]
-    async fn post_image_too_large() {
+    async fn patch_game_not_a_game() {
+        let game_data = GamePatch {
+            publisher: Some("Test Game Company, Inc.".into()),
+            ..Default::default()
+        };
+
         let response = try_request(
             Request::builder()
-                .method(Method::POST)
-                .uri(&format!("{API_V1}/projects/a_project/images/img.png"))
-                .header(AUTHORIZATION, token(BOB_UID))
-                .header(CONTENT_TYPE, IMAGE_PNG.as_ref())
-                .header(CONTENT_LENGTH, u64::MAX)
-                .body(Body::empty())
+                .method(Method::PATCH)
+                .uri(&format!("{API_V1}/games/2"))
+                .header(AUTHORIZATION, token(3))
+                .header(CONTENT_TYPE, APPLICATION_JSON.as_ref())
+                .body(Body::from(serde_json::to_vec(&game_data).unwrap()))
                 .unwrap()
         )
         .await;
 
-        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
         assert_eq!(
             body_as::<HttpError>(response).await,
-            HttpError::from(AppError::TooLarge)
+            HttpError::from(AppError::NotAGame)
         );
     }
 
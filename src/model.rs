@@ -16,18 +16,53 @@ pub struct Package(pub i64);
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Project(pub i64);
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Game(pub i64);
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Owner(pub i64);
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct Owned(pub Owner, pub Project);
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Eq, PartialEq)]
+pub struct OwnedOrAdmin(pub Owner, pub Project);
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Admin(pub User);
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GameData {
+    // links the project to an existing game, whose title, publisher, and
+    // year then take the place of those given; otherwise the game is found
+    // by its title, publisher, and year. The sort key belongs to the
+    // project, and is the game's unless the owner overrides it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
     pub title: String,
+    // derived from the title if empty
+    #[serde(default)]
     pub title_sort_key: String,
     pub publisher: String,
-    pub year: String
+    pub year: String,
+    // other names by which the game is known, e.g., abbreviations,
+    // translations, or titles of earlier editions
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alternate_titles: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub designers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_players: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_players: Option<i64>,
+    // in minutes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playing_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub era: Option<String>,
+    // e.g., a BoardGameGeek ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -43,6 +78,27 @@ pub struct FileData {
     pub authors: Vec<String>
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ImageData {
+    pub filename: String,
+    pub url: String,
+    pub content_type: String,
+    pub size: i64,
+    pub published_at: String,
+    pub published_by: String
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Images {
+    pub images: Vec<ImageData>
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GalleryItem {
+    pub filename: String,
+    pub caption: String
+}
+
 // TODO: probably needs slug
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct PackageData {
@@ -69,17 +125,34 @@ pub struct ProjectData {
     pub tags: Vec<String>,
     pub game: GameData,
     pub readme: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme_html: Option<String>,
     pub image: Option<String>,
+    pub gallery: Vec<GalleryItem>,
     pub owners: Vec<String>,
     pub packages: Vec<PackageData>
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct GameDataPatch {
+    // moves the project to an existing game, as for GameData
+    pub id: Option<i64>,
     pub title: Option<String>,
     pub title_sort_key: Option<String>,
     pub publisher: Option<String>,
-    pub year: Option<String>
+    pub year: Option<String>,
+    pub alternate_titles: Option<Vec<String>>,
+    pub designers: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub min_players: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub max_players: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub playing_time: Option<Option<i64>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub era: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub external_id: Option<Option<String>>
 }
 
 fn double_option<'de, T, D>(de: D) -> Result<Option<Option<T>>, D::Error>
@@ -98,7 +171,8 @@ pub struct MaybeProjectDataPatch {
     pub game: Option<GameDataPatch>,
     pub readme: Option<String>,
     #[serde(default, deserialize_with = "double_option")]
-    pub image: Option<Option<String>>
+    pub image: Option<Option<String>>,
+    pub gallery: Option<Vec<GalleryItem>>
 }
 
 impl MaybeProjectDataPatch {
@@ -108,13 +182,22 @@ impl MaybeProjectDataPatch {
                 description: None,
                 tags: None,
                 game: None | Some(GameDataPatch {
+                    id: None,
                     title: None,
                     title_sort_key: None,
                     publisher: None,
-                    year: None
+                    year: None,
+                    alternate_titles: None,
+                    designers: None,
+                    min_players: None,
+                    max_players: None,
+                    playing_time: None,
+                    era: None,
+                    external_id: None
                 }),
                 readme: None,
-                image: None
+                image: None,
+                gallery: None
             }
             => true,
             _ => false
@@ -130,7 +213,8 @@ pub struct ProjectDataPatch {
     #[serde(default)]
     pub game: GameDataPatch,
     pub readme: Option<String>,
-    pub image: Option<Option<String>>
+    pub image: Option<Option<String>>,
+    pub gallery: Option<Vec<GalleryItem>>
 }
 
 #[derive(Debug, thiserror::Error, Eq, PartialEq)]
@@ -152,7 +236,8 @@ impl TryFrom<MaybeProjectDataPatch> for ProjectDataPatch {
                     tags: m.tags,
                     game: m.game.unwrap_or_default(),
                     readme: m.readme,
-                    image: m.image
+                    image: m.image,
+                    gallery: m.gallery
                 }
             )
         }
@@ -165,7 +250,67 @@ pub struct ProjectDataPost {
     pub tags: Vec<String>,
     pub game: GameData,
     pub readme: String,
-    pub image: Option<String>
+    pub image: Option<String>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryItem>
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FieldDiff<T> {
+    pub from: T,
+    pub to: T
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GameDataDiff {
+    pub title: Option<FieldDiff<String>>,
+    pub title_sort_key: Option<FieldDiff<String>>,
+    pub publisher: Option<FieldDiff<String>>,
+    pub year: Option<FieldDiff<String>>,
+    pub alternate_titles: Option<FieldDiff<Vec<String>>>,
+    pub designers: Option<FieldDiff<Vec<String>>>,
+    pub min_players: Option<FieldDiff<Option<i64>>>,
+    pub max_players: Option<FieldDiff<Option<i64>>>,
+    pub playing_time: Option<FieldDiff<Option<i64>>>,
+    pub era: Option<FieldDiff<Option<String>>>,
+    pub external_id: Option<FieldDiff<Option<String>>>
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct ReleaseRef {
+    pub package: String,
+    pub version: String
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProjectDiff {
+    pub from: i64,
+    pub to: i64,
+    pub name: Option<FieldDiff<String>>,
+    pub description: Option<FieldDiff<String>>,
+    pub tags: Option<FieldDiff<Vec<String>>>,
+    pub game: GameDataDiff,
+    pub readme: Option<String>,
+    pub image: Option<FieldDiff<Option<String>>>,
+    pub gallery: Option<FieldDiff<Vec<GalleryItem>>>,
+    pub packages_added: Vec<String>,
+    pub packages_removed: Vec<String>,
+    pub releases_added: Vec<ReleaseRef>,
+    pub releases_removed: Vec<ReleaseRef>
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProjectNamePost {
+    pub name: String
+}
+
+// Highlights are HTML, with the matched terms wrapped in <mark> elements;
+// the description and readme are excerpted around the matches
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Highlights {
+    pub title: String,
+    pub description: String,
+    pub readme: String
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -176,7 +321,9 @@ pub struct ProjectSummary {
     pub created_at: String,
     pub modified_at: String,
     pub tags: Vec<String>,
-    pub game: GameData
+    pub game: GameData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Highlights>
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -185,6 +332,88 @@ pub struct Projects {
     pub meta: Pagination
 }
 
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchSuggestions {
+    pub projects: Vec<String>,
+    pub titles: Vec<String>,
+    pub publishers: Vec<String>
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchIndexMismatch {
+    pub project_id: i64,
+    // purged projects have no names
+    pub name: Option<String>
+}
+
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchIndexReport {
+    // rows which differed from their projects before the rebuild
+    pub mismatched: Vec<SearchIndexMismatch>,
+    // rows which still differ after the rebuild
+    pub unresolved: Vec<SearchIndexMismatch>
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct GameProjects {
+    pub game: GameData,
+    pub projects: Vec<String>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MaybeGamePatch {
+    pub title: Option<String>,
+    pub title_sort_key: Option<String>,
+    pub publisher: Option<String>,
+    pub year: Option<String>
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(try_from = "MaybeGamePatch")]
+pub struct GamePatch {
+    pub title: Option<String>,
+    pub title_sort_key: Option<String>,
+    pub publisher: Option<String>,
+    pub year: Option<String>
+}
+
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+#[error("invalid data {0:?}")]
+pub struct GamePatchError(MaybeGamePatch);
+
+impl TryFrom<MaybeGamePatch> for GamePatch {
+    type Error = GamePatchError;
+
+    fn try_from(m: MaybeGamePatch) -> Result<Self, Self::Error> {
+        // at least one element must be present to be a valid request
+        match m {
+            MaybeGamePatch {
+                title: None,
+                title_sort_key: None,
+                publisher: None,
+                year: None
+            } => Err(GamePatchError(m)),
+            MaybeGamePatch { title, title_sort_key, publisher, year } => Ok(
+                GamePatch { title, title_sort_key, publisher, year }
+            )
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RevisionSummary {
+    pub revision: i64,
+    pub modified_at: String,
+    pub modified_by: String,
+    pub changes: Vec<String>
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Revisions {
+    pub revisions: Vec<RevisionSummary>,
+    pub meta: Pagination
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -228,6 +457,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn maybe_project_data_patch_from_json_gallery() {
+        let json = "{\"gallery\": [{\"filename\": \"a.png\", \"caption\": \"A\"}]}";
+        assert_eq!(
+            serde_json::from_str::<MaybeProjectDataPatch>(json).unwrap(),
+            MaybeProjectDataPatch {
+                gallery: Some(vec![
+                    GalleryItem {
+                        filename: "a.png".into(),
+                        caption: "A".into()
+                    }
+                ]),
+                ..Default::default()
+            }
+        );
+    }
+
     #[test]
     fn maybe_project_data_patch_default_empty() {
         assert!(MaybeProjectDataPatch::default().empty());
@@ -253,6 +499,16 @@ mod test {
         );
     }
 
+    #[test]
+    fn maybe_project_data_patch_gallery_clear_not_empty() {
+        assert!(
+            !MaybeProjectDataPatch {
+                gallery: Some(vec![]),
+                ..Default::default()
+            }.empty()
+        );
+    }
+
     #[test]
     fn maybe_project_data_patch_game_not_empty() {
         assert!(
@@ -305,4 +561,34 @@ mod test {
             ProjectDataPatchError(MaybeProjectDataPatch::default())
         );
     }
+
+    #[test]
+    fn maybe_project_data_patch_game_id_not_empty() {
+        assert!(
+            !MaybeProjectDataPatch {
+                game: Some(GameDataPatch {
+                    id: Some(1),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }.empty()
+        );
+    }
+
+    #[test]
+    fn game_patch_from_json_title() {
+        let json = "{\"title\":\"foo\"}";
+        assert_eq!(
+            serde_json::from_str::<GamePatch>(json).unwrap(),
+            GamePatch {
+                title: Some("foo".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn game_patch_from_json_empty() {
+        assert!(serde_json::from_str::<GamePatch>("{}").is_err());
+    }
 }
@@ -122,6 +122,17 @@ pub enum Anchor {
     AfterQuery(String, String, u32)
 }
 
+impl Anchor {
+    pub fn query(&self) -> Option<&str> {
+        match self {
+            Anchor::StartQuery(q) |
+            Anchor::AfterQuery(q, ..) |
+            Anchor::BeforeQuery(q, ..) => Some(q),
+            _ => None
+        }
+    }
+}
+
 #[derive(Debug, thiserror::Error, Eq, PartialEq)]
 #[error("anchor {0:?} invalid")]
 pub struct AnchorError(RawAnchor);
@@ -336,11 +347,31 @@ impl PartialEq for SeekError {
     }
 }
 
+// Filters restrict the projects listed; they are part of the seek so that
+// every page of a listing has the same filters
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Filters {
+    pub designer: Option<String>,
+    pub players: Option<u32>,
+    pub max_playing_time: Option<u32>,
+    pub era: Option<String>,
+    pub external_id: Option<String>,
+    pub publisher: Option<String>,
+    pub year_min: Option<u32>,
+    pub year_max: Option<u32>,
+    pub tag: Option<String>,
+    pub has_release: Option<bool>,
+    pub owner: Option<String>
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Seek {
     pub sort_by: SortBy,
     pub dir: Direction,
-    pub anchor: Anchor
+    pub anchor: Anchor,
+    // absent from seeks made before there were filters
+    #[serde(default)]
+    pub filters: Filters
 }
 
 impl Default for Seek {
@@ -348,7 +379,8 @@ impl Default for Seek {
         Seek {
             anchor: Anchor::Start,
             sort_by: SortBy::ProjectName,
-            dir: Direction::Ascending
+            dir: Direction::Ascending,
+            filters: Filters::default()
         }
     }
 }
@@ -418,6 +450,11 @@ impl SeekLink {
             None => Ok(SeekLink(format!("?seek={}", s)))
         }
     }
+
+    // options which are not part of the seek are carried separately
+    pub fn with_param(self, param: &str) -> SeekLink {
+        SeekLink(format!("{}&{}", self.0, param))
+    }
 }
 
 impl fmt::Display for SeekLink {
@@ -426,11 +463,35 @@ impl fmt::Display for SeekLink {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Facet {
+    Publisher,
+    Decade,
+    Tag
+}
+
+#[derive(Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64
+}
+
+// Facets are counted over every project matching the query and filters,
+// so they are the same for every page of a listing
+#[derive(Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Facets {
+    pub publisher: Vec<FacetCount>,
+    pub decade: Vec<FacetCount>,
+    pub tag: Vec<FacetCount>
+}
+
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Pagination {
     pub prev_page: Option<SeekLink>,
     pub next_page: Option<SeekLink>,
-    pub total: i64
+    pub total: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facets: Option<Facets>
 }
 
 #[cfg(test)]
@@ -620,6 +681,7 @@ mod test {
             sort_by: SortBy::ProjectName,
             dir: Direction::Ascending,
             anchor: Anchor::Start,
+            ..Default::default()
         };
 
         assert_eq!(
@@ -638,10 +700,11 @@ mod test {
                 Seek {
                     sort_by: SortBy::ProjectName,
                     dir: Direction::Ascending,
-                    anchor: Anchor::Start
+                    anchor: Anchor::Start,
+                    ..Default::default()
                 }
             ).unwrap(),
-            "p,a,s,,,"
+            "p,a,s,,,,,,,,,,,,,,"
         );
     }
 
@@ -652,10 +715,11 @@ mod test {
                 Seek {
                     sort_by: SortBy::ProjectName,
                     dir: Direction::Descending,
-                    anchor: Anchor::Start
+                    anchor: Anchor::Start,
+                    ..Default::default()
                 }
             ).unwrap(),
-            "p,d,s,,,"
+            "p,d,s,,,,,,,,,,,,,,"
         );
     }
 
@@ -667,9 +731,10 @@ mod test {
                     sort_by: SortBy::ProjectName,
                     dir: Direction::Ascending,
                     anchor: Anchor::Before("abc".into(), 0),
+                    ..Default::default()
                 }
             ).unwrap(),
-            "p,a,b,abc,,0"
+            "p,a,b,abc,,0,,,,,,,,,,,"
         );
     }
 
@@ -680,10 +745,11 @@ mod test {
                 Seek {
                     sort_by: SortBy::ProjectName,
                     dir: Direction::Ascending,
-                    anchor: Anchor::After("abc".into(), 0)
+                    anchor: Anchor::After("abc".into(), 0),
+                    ..Default::default()
                 }
             ).unwrap(),
-            "p,a,a,abc,,0"
+            "p,a,a,abc,,0,,,,,,,,,,,"
         );
     }
 
@@ -694,7 +760,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             }
         );
     }
@@ -706,7 +773,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             }
         );
     }
@@ -718,7 +786,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Before("abc".into(), 0)
+                anchor: Anchor::Before("abc".into(), 0),
+                ..Default::default()
             }
         );
     }
@@ -730,11 +799,37 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::After("abc".into(), 0)
+                anchor: Anchor::After("abc".into(), 0),
+                ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn seek_roundtrip_filters() {
+        let seek = Seek {
+            sort_by: SortBy::GameTitle,
+            dir: Direction::Ascending,
+            anchor: Anchor::After("abc".into(), 3),
+            filters: Filters {
+                designer: Some("Jim Dunnigan".into()),
+                players: Some(2),
+                max_playing_time: Some(120),
+                era: Some("WWII".into()),
+                external_id: Some("bgg:1234".into()),
+                ..Default::default()
+            }
+        };
+
+        assert_eq!(
+            String::try_from(&seek)
+                .unwrap()
+                .parse::<Seek>()
+                .unwrap(),
+            seek
+        );
+    }
+
     #[test]
     fn string_to_seek_err() {
         assert!("$$$".parse::<Seek>().is_err());
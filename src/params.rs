@@ -2,7 +2,10 @@ use base64::{Engine as _};
 use serde::Deserialize;
 use std::str;
 
-use crate::pagination::{Anchor, Limit, Direction, SortBy, Seek, SeekError};
+use crate::{
+    pagination::{Anchor, Filters, Limit, LimitError, Direction, SortBy, Seek, SeekError},
+    search::{QueryError, parse_query}
+};
 
 #[derive(Debug, Default, Deserialize, Eq, PartialEq)]
 pub struct MaybeProjectsParams {
@@ -11,12 +14,39 @@ pub struct MaybeProjectsParams {
     pub order: Option<Direction>,
     pub from: Option<String>,
     pub seek: Option<String>,
-    pub limit: Option<Limit>
+    pub limit: Option<String>,
+    pub designer: Option<String>,
+    pub players: Option<u32>,
+    pub max_playing_time: Option<u32>,
+    pub era: Option<String>,
+    pub external_id: Option<String>,
+    pub publisher: Option<String>,
+    pub year_min: Option<u32>,
+    pub year_max: Option<u32>,
+    pub tag: Option<String>,
+    pub has_release: Option<bool>,
+    pub owner: Option<String>,
+    pub facets: Option<bool>,
+    pub highlight: Option<bool>
 }
 
 impl MaybeProjectsParams {
+    fn has_filters(&self) -> bool {
+        self.designer.is_some() ||
+        self.players.is_some() ||
+        self.max_playing_time.is_some() ||
+        self.era.is_some() ||
+        self.external_id.is_some() ||
+        self.publisher.is_some() ||
+        self.year_min.is_some() ||
+        self.year_max.is_some() ||
+        self.tag.is_some() ||
+        self.has_release.is_some() ||
+        self.owner.is_some()
+    }
+
     fn valid(&self) -> bool {
-        // sort, order, query, from are incompatible with seek
+        // sort, order, query, from, filters are incompatible with seek
         // from is incompatible with query
         !(
             (
@@ -25,7 +55,8 @@ impl MaybeProjectsParams {
                     self.sort.is_some() ||
                     self.order.is_some() ||
                     self.from.is_some() ||
-                    self.q.is_some()
+                    self.q.is_some() ||
+                    self.has_filters()
                 )
             )
             ||
@@ -34,23 +65,100 @@ impl MaybeProjectsParams {
     }
 }
 
-#[derive(Debug, Default, Deserialize, Eq, PartialEq)]
-#[serde(try_from = "MaybeProjectsParams")]
+#[derive(Debug, Default, Eq, PartialEq)]
 pub struct ProjectsParams {
+    pub seek: Seek,
+    pub limit: Option<Limit>,
+    pub facets: bool,
+    pub highlight: bool
+}
+
+#[derive(Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct MaybeRevisionsParams {
+    pub order: Option<Direction>,
+    pub seek: Option<String>,
+    pub limit: Option<String>
+}
+
+impl MaybeRevisionsParams {
+    fn valid(&self) -> bool {
+        // order is incompatible with seek
+        !(self.seek.is_some() && self.order.is_some())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct RevisionsParams {
     pub seek: Seek,
     pub limit: Option<Limit>
 }
 
+impl Default for RevisionsParams {
+    fn default() -> Self {
+        RevisionsParams {
+            // newest revisions first
+            seek: Seek {
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Descending,
+                anchor: Anchor::Start,
+                filters: Filters::default()
+            },
+            limit: None
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct MaybeSuggestParams {
+    pub q: String,
+    pub limit: Option<String>
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct SuggestParams {
+    pub q: String,
+    pub limit: Option<Limit>
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+pub struct DiffParams {
+    pub from: u32,
+    pub to: u32
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderFormat {
+    Html
+}
+
+#[derive(Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct RenderParams {
+    pub render: Option<RenderFormat>
+}
+
 #[derive(Debug, thiserror::Error, PartialEq)]
 pub enum Error {
     #[error("invalid combination {0:?}")]
-    InvalidCombination(MaybeProjectsParams),
+    InvalidCombination(Box<MaybeProjectsParams>),
+    #[error("invalid combination {0:?}")]
+    InvalidRevisionsCombination(MaybeRevisionsParams),
+    #[error("invalid revisions seek {0:?}")]
+    InvalidRevisionsSeek(Box<Seek>),
     #[error("invalid base64 {0}")]
     Base64DecodeError(#[from] base64::DecodeError),
     #[error("invalid UTF-8 {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
     #[error("{0}")]
-    SeekError(#[from] SeekError)
+    SeekError(#[from] SeekError),
+    #[error("{0}")]
+    LimitError(#[from] LimitError),
+    #[error("invalid search query: {0}")]
+    QueryError(#[from] QueryError)
+}
+
+fn parse_limit(limit: Option<&str>) -> Result<Option<Limit>, Error> {
+    Ok(limit.map(Limit::try_from).transpose()?)
 }
 
 fn decode_seek(enc: &str) -> Result<Seek, Error> {
@@ -58,18 +166,27 @@ fn decode_seek(enc: &str) -> Result<Seek, Error> {
     let buf = base64::engine::general_purpose::URL_SAFE_NO_PAD
         .decode(enc)?;
 
-    Ok(
-        str::from_utf8(&buf)?
-            .parse::<Seek>()?
-    )
+    let seek = str::from_utf8(&buf)?.parse::<Seek>()?;
+
+    // seeks come from clients, so their queries must be checked as if
+    // they had come in q
+    if let Some(query) = seek.anchor.query() {
+        parse_query(query)?;
+    }
+
+    Ok(seek)
 }
 
-fn convert_non_seek(m: MaybeProjectsParams) -> Seek {
+fn convert_non_seek(m: MaybeProjectsParams) -> Result<Seek, Error> {
     let (sort_by, anchor) = match m.q {
-        Some(query) => (
-            m.sort.unwrap_or(SortBy::Relevance),
-            Anchor::StartQuery(query)
-        ),
+        Some(query) => {
+            // anchors keep the query as written; check it now
+            parse_query(&query)?;
+            (
+                m.sort.unwrap_or(SortBy::Relevance),
+                Anchor::StartQuery(query)
+            )
+        },
         None => (
             m.sort.unwrap_or_default(),
             match m.from {
@@ -83,7 +200,21 @@ fn convert_non_seek(m: MaybeProjectsParams) -> Seek {
 
     let dir = m.order.unwrap_or_else(|| sort_by.default_direction());
 
-    Seek { sort_by, dir, anchor }
+    let filters = Filters {
+        designer: m.designer,
+        players: m.players,
+        max_playing_time: m.max_playing_time,
+        era: m.era,
+        external_id: m.external_id,
+        publisher: m.publisher,
+        year_min: m.year_min,
+        year_max: m.year_max,
+        tag: m.tag,
+        has_release: m.has_release,
+        owner: m.owner
+    };
+
+    Ok(Seek { sort_by, dir, anchor, filters })
 }
 
 impl TryFrom<MaybeProjectsParams> for ProjectsParams {
@@ -93,18 +224,70 @@ impl TryFrom<MaybeProjectsParams> for ProjectsParams {
         match m.valid() {
             true => Ok(
                 ProjectsParams {
-                    limit: m.limit,
+                    limit: parse_limit(m.limit.as_deref())?,
+                    facets: m.facets.unwrap_or(false),
+                    highlight: m.highlight.unwrap_or(false),
                     seek: match m.seek {
                         Some(enc) => decode_seek(&enc)?,
-                        None => convert_non_seek(m)
+                        None => convert_non_seek(m)?
+                    }
+                }
+            ),
+            false => Err(Error::InvalidCombination(Box::new(m)))
+        }
+    }
+}
+
+fn check_revisions_seek(seek: Seek) -> Result<Seek, Error> {
+    // revisions are ordered only by time and have no queries or filters
+    match seek {
+        Seek {
+            sort_by: SortBy::ModificationTime,
+            anchor: Anchor::Start | Anchor::Before(..) | Anchor::After(..),
+            ref filters,
+            ..
+        } if *filters == Filters::default() => Ok(seek),
+        _ => Err(Error::InvalidRevisionsSeek(Box::new(seek)))
+    }
+}
+
+impl TryFrom<MaybeRevisionsParams> for RevisionsParams {
+    type Error = Error;
+
+    fn try_from(m: MaybeRevisionsParams) -> Result<Self, Self::Error> {
+        match m.valid() {
+            true => Ok(
+                RevisionsParams {
+                    limit: parse_limit(m.limit.as_deref())?,
+                    seek: match m.seek {
+                        Some(enc) => check_revisions_seek(decode_seek(&enc)?)?,
+                        None => Seek {
+                            sort_by: SortBy::ModificationTime,
+                            dir: m.order.unwrap_or(Direction::Descending),
+                            anchor: Anchor::Start,
+                            filters: Filters::default()
+                        }
                     }
                 }
             ),
-            false => Err(Error::InvalidCombination(m))
+            false => Err(Error::InvalidRevisionsCombination(m))
         }
     }
 }
 
+impl TryFrom<MaybeSuggestParams> for SuggestParams {
+    type Error = Error;
+
+    fn try_from(m: MaybeSuggestParams) -> Result<Self, Self::Error> {
+        Ok(
+            SuggestParams {
+                limit: parse_limit(m.limit.as_deref())?,
+                q: m.q
+            }
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -169,6 +352,16 @@ mod test {
         assert!(!mpp.valid());
     }
 
+    #[test]
+    fn maybe_projects_params_invalid_seek_and_filter() {
+        let mpp = MaybeProjectsParams {
+            seek: Some("whatever".into()),
+            era: Some("WWII".into()),
+            ..Default::default()
+        };
+        assert!(!mpp.valid());
+    }
+
     #[test]
     fn decode_seek_ok() {
         assert_eq!(
@@ -176,11 +369,33 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::After("abc".into(), 0)
+                anchor: Anchor::After("abc".into(), 0),
+                ..Default::default()
             }
         );
     }
 
+    #[test]
+    fn decode_seek_bad_query() {
+        // a hand-made seek with a query which q would reject
+        let seek = Seek {
+            sort_by: SortBy::Relevance,
+            dir: Direction::Ascending,
+            anchor: Anchor::StartQuery("\"third reich".into()),
+            ..Default::default()
+        };
+
+        let enc = base64::engine::general_purpose::URL_SAFE_NO_PAD
+            .encode(String::try_from(&seek).unwrap());
+
+        assert!(
+            matches!(
+                decode_seek(&enc).unwrap_err(),
+                Error::QueryError(QueryError::UnterminatedPhrase)
+            )
+        );
+    }
+
     #[test]
     fn decode_seek_bad_base64() {
         assert!(
@@ -214,14 +429,105 @@ mod test {
             seek: Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             },
-            limit: None
+            limit: None,
+            facets: false,
+            highlight: false
+        };
+
+        assert_eq!(ProjectsParams::try_from(mpp).unwrap(), pp);
+    }
+
+    #[test]
+    fn maybe_projects_params_try_from_filters() {
+        let mpp = MaybeProjectsParams {
+            designer: Some("Jim Dunnigan".into()),
+            players: Some(2),
+            max_playing_time: Some(90),
+            ..Default::default()
+        };
+
+        let pp = ProjectsParams {
+            seek: Seek {
+                sort_by: SortBy::GameTitle,
+                dir: Direction::Ascending,
+                anchor: Anchor::Start,
+                filters: Filters {
+                    designer: Some("Jim Dunnigan".into()),
+                    players: Some(2),
+                    max_playing_time: Some(90),
+                    ..Default::default()
+                }
+            },
+            limit: None,
+            facets: false,
+            highlight: false
         };
 
         assert_eq!(ProjectsParams::try_from(mpp).unwrap(), pp);
     }
 
+    #[test]
+    fn maybe_projects_params_try_from_seek_and_facets() {
+        let mpp = MaybeProjectsParams {
+            seek: Some("cCxhLGEsYWJjLCww".into()),
+            facets: Some(true),
+            ..Default::default()
+        };
+
+        assert!(ProjectsParams::try_from(mpp).unwrap().facets);
+    }
+
+    #[test]
+    fn maybe_projects_params_try_from_query() {
+        let mpp = MaybeProjectsParams {
+            q: Some("publisher:gmt \"paths of glory".into()),
+            ..Default::default()
+        };
+
+        assert!(
+            matches!(
+                ProjectsParams::try_from(mpp).unwrap_err(),
+                Error::QueryError(QueryError::UnterminatedPhrase)
+            )
+        );
+
+        let mpp = MaybeProjectsParams {
+            q: Some("publisher:gmt glory".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ProjectsParams::try_from(mpp).unwrap().seek.anchor,
+            Anchor::StartQuery("publisher:gmt glory".into())
+        );
+    }
+
+    #[test]
+    fn maybe_projects_params_try_from_bad_limit() {
+        let mpp = MaybeProjectsParams {
+            limit: Some("0".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ProjectsParams::try_from(mpp).unwrap_err(),
+            Error::LimitError(LimitError::OutOfRange(0))
+        );
+
+        let mpp = MaybeProjectsParams {
+            limit: Some("eleventeen".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ProjectsParams::try_from(mpp).unwrap_err(),
+            Error::LimitError(LimitError::Malformed("eleventeen".into()))
+        );
+    }
+
     #[test]
     fn maybe_projects_params_try_from_invalid() {
         let mpp = MaybeProjectsParams {
@@ -268,4 +574,127 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn maybe_revisions_params_try_from_default() {
+        assert_eq!(
+            RevisionsParams::try_from(MaybeRevisionsParams::default())
+                .unwrap(),
+            RevisionsParams::default()
+        );
+    }
+
+    #[test]
+    fn maybe_revisions_params_try_from_order() {
+        let mrp = MaybeRevisionsParams {
+            order: Some(Direction::Ascending),
+            ..Default::default()
+        };
+
+        let rp = RevisionsParams {
+            seek: Seek {
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Ascending,
+                anchor: Anchor::Start,
+                ..Default::default()
+            },
+            limit: None
+        };
+
+        assert_eq!(RevisionsParams::try_from(mrp).unwrap(), rp);
+    }
+
+    #[test]
+    fn maybe_revisions_params_try_from_seek() {
+        let mrp = MaybeRevisionsParams {
+            seek: Some("bSxkLGEsMjAyMy0xMi0xNFQxNjowMzowOS40MTk1MzgwNjdaLCw0".into()),
+            ..Default::default()
+        };
+
+        let rp = RevisionsParams {
+            seek: Seek {
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Descending,
+                anchor: Anchor::After(
+                    "2023-12-14T16:03:09.419538067Z".into(),
+                    4
+                ),
+                ..Default::default()
+            },
+            limit: None
+        };
+
+        assert_eq!(RevisionsParams::try_from(mrp).unwrap(), rp);
+    }
+
+    #[test]
+    fn maybe_revisions_params_try_from_seek_and_order() {
+        let mrp = MaybeRevisionsParams {
+            seek: Some("bSxkLGEsMjAyMy0xMi0xNFQxNjowMzowOS40MTk1MzgwNjdaLCw0".into()),
+            order: Some(Direction::Ascending),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            RevisionsParams::try_from(mrp).unwrap_err(),
+            Error::InvalidRevisionsCombination(
+                MaybeRevisionsParams {
+                    seek: Some("bSxkLGEsMjAyMy0xMi0xNFQxNjowMzowOS40MTk1MzgwNjdaLCw0".into()),
+                    order: Some(Direction::Ascending),
+                    ..Default::default()
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn maybe_revisions_params_try_from_bad_seek() {
+        let mrp = MaybeRevisionsParams {
+            seek: Some("cCxhLGEsYWJjLCww".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            RevisionsParams::try_from(mrp).unwrap_err(),
+            Error::InvalidRevisionsSeek(
+                Box::new(
+                    Seek {
+                        sort_by: SortBy::ProjectName,
+                        dir: Direction::Ascending,
+                        anchor: Anchor::After("abc".into(), 0),
+                        ..Default::default()
+                    }
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn maybe_suggest_params_try_from_ok() {
+        let msp = MaybeSuggestParams {
+            q: "gm".into(),
+            limit: Some("5".into())
+        };
+
+        assert_eq!(
+            SuggestParams::try_from(msp).unwrap(),
+            SuggestParams {
+                q: "gm".into(),
+                limit: Limit::new(5)
+            }
+        );
+    }
+
+    #[test]
+    fn maybe_suggest_params_try_from_bad_limit() {
+        let msp = MaybeSuggestParams {
+            q: "gm".into(),
+            limit: Some("101".into())
+        };
+
+        assert_eq!(
+            SuggestParams::try_from(msp).unwrap_err(),
+            Error::LimitError(LimitError::OutOfRange(101))
+        );
+    }
 }
@@ -2,7 +2,7 @@ use axum::{
     async_trait,
     body::Bytes
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::Stream;
 use futures_util::{
     StreamExt,
@@ -12,18 +12,24 @@ use mime::Mime;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::{
+    collections::HashMap,
     future::Future,
     io
 };
 
 use crate::{
-    core::{Core, CoreError},
-    db::{DatabaseClient, PackageRow, ProjectRow, ProjectSummaryRow, FileRow},
-    model::{GameData, Owner, Package, PackageData, PackageDataPost, ProjectData, ProjectDataPatch, ProjectDataPost, Project, Projects, ProjectSummary, FileData, User, Users},
-    pagination::{Anchor, Direction, Limit, SortBy, Pagination, Seek, SeekLink},
-    params::ProjectsParams,
+    core::{Core, CoreError, ImageUpload},
+    db::{DatabaseClient, HIGHLIGHT_END, HIGHLIGHT_START, ImageFileRow, ImageRow, PackageRow, ProjectRow, ProjectSummaryRow, FileRow, RevisionSummaryRow, SearchIndexMismatchRow},
+    diff::diff_project_data,
+    model::{Game, GameData, GamePatch, GameProjects, Highlights, ImageData, Images, Owner, Package, PackageData, PackageDataPost, ProjectData, ProjectDataPatch, ProjectDataPost, Project, ProjectDiff, Projects, ProjectSummary, FileData, RevisionSummary, Revisions, SearchIndexMismatch, SearchIndexReport, SearchSuggestions, User, Users},
+    pagination::{Anchor, Direction, Facet, FacetCount, Facets, Filters, Limit, SortBy, Pagination, Seek, SeekLink},
+    params::{ProjectsParams, RevisionsParams, SuggestParams},
+    render::render_highlight,
+    search::{SuggestionField, parse_query, prefix_query},
+    sort_key::title_sort_key,
     time::nanos_to_rfc3339,
     upload::{LocalUploader, Uploader},
+    validation::{Limits, Validate, validate_filename},
     version::Version
 };
 
@@ -32,7 +38,9 @@ pub struct ProdCore<C: DatabaseClient, U: Uploader> {
     pub db: C,
     pub uploader: U,
     pub now: fn() -> DateTime<Utc>,
-    pub max_image_size: u64
+    pub max_image_size: u64,
+    pub deletion_grace_period: Duration,
+    pub limits: Limits
 }
 
 #[async_trait]
@@ -57,6 +65,22 @@ where
         self.db.get_project_id(proj).await
     }
 
+    async fn get_deleted_project_id(
+         &self,
+        proj: &str
+    ) -> Result<Project, CoreError>
+    {
+        self.db.get_deleted_project_id(proj).await
+    }
+
+    async fn get_project_alias(
+        &self,
+        proj: &str
+    ) -> Result<String, CoreError>
+    {
+        self.db.get_project_alias(proj).await
+    }
+
     async fn get_owners(
         &self,
         proj: Project
@@ -92,23 +116,57 @@ where
         self.db.user_is_owner(user, proj).await
     }
 
+    async fn user_is_admin(
+        &self,
+        user: User
+    ) -> Result<bool, CoreError>
+    {
+        self.db.user_is_admin(user).await
+    }
+
     async fn get_projects(
         &self,
         params: ProjectsParams
     ) -> Result<Projects, CoreError>
     {
-        let ProjectsParams { seek, limit } = params;
-        let (prev, next, projects, total) = self.get_projects_from(
+        let ProjectsParams { seek, limit, facets, highlight } = params;
+
+        let facets = match facets {
+            true => Some(self.get_facets(&seek).await?),
+            false => None
+        };
+
+        // only queries have matches to highlight
+        let query = match highlight {
+            true => seek.anchor.query().map(fts_query).transpose()?,
+            false => None
+        };
+
+        let (prev, next, mut projects, total) = self.get_projects_from(
             seek, limit.unwrap_or_default()
         ).await?;
 
+        if let Some(query) = query {
+            self.add_highlights(&query, &mut projects).await?;
+        }
+
+        let link = |seek: &Seek| -> Result<SeekLink, CoreError> {
+            let link = SeekLink::new(seek, limit)?;
+            Ok(
+                match highlight {
+                    true => link.with_param("highlight=true"),
+                    false => link
+                }
+            )
+        };
+
         let prev_page = match prev {
-            Some(prev) => Some(SeekLink::new(&prev, limit)?),
+            Some(prev) => Some(link(&prev)?),
             None => None
         };
 
         let next_page = match next {
-            Some(next) => Some(SeekLink::new(&next, limit)?),
+            Some(next) => Some(link(&next)?),
             None => None
         };
 
@@ -118,7 +176,8 @@ where
                 meta: Pagination {
                     prev_page,
                     next_page,
-                    total
+                    total,
+                    facets
                 }
             },
         )
@@ -138,7 +197,14 @@ where
         ).await
     }
 
-// TODO: length limits on strings
+    async fn get_current_revision(
+        &self,
+        proj: Project
+    ) -> Result<i64, CoreError>
+    {
+        Ok(self.db.get_project_row(proj).await?.revision)
+    }
+
 // TODO: packages might need display names?
 
     async fn create_project(
@@ -148,19 +214,102 @@ where
         proj_data: &ProjectDataPost
     ) -> Result<(), CoreError>
     {
+        proj_data.validate(&self.limits)
+            .map_err(CoreError::InvalidFields)?;
+
+        // derive the sort key if the owner did not supply one; a project
+        // linked to an existing game takes the sort key of the game
+        let mut proj_data = proj_data.clone();
+        if proj_data.game.title_sort_key.trim().is_empty() {
+            proj_data.game.title_sort_key = match proj_data.game.id {
+                Some(id) => self.db.get_game_row(Game(id)).await?.title_sort,
+                None => title_sort_key(&proj_data.game.title)
+            };
+        }
+
         let now = self.now_nanos()?;
-        self.db.create_project(user, proj, proj_data, now).await
+        self.db.create_project(user, proj, &proj_data, now).await
     }
 
     async fn update_project(
         &self,
         owner: Owner,
         proj: Project,
+        expected: Option<i64>,
         proj_data: &ProjectDataPatch
     ) -> Result<(), CoreError>
+    {
+        proj_data.validate(&self.limits)
+            .map_err(CoreError::InvalidFields)?;
+
+        let mut proj_data = proj_data.clone();
+        if let Some(key) = self.derived_title_sort_key(proj, &proj_data).await? {
+            proj_data.game.title_sort_key = Some(key);
+        }
+
+        let now = self.now_nanos()?;
+        self.db.update_project(owner, proj, expected, &proj_data, now).await
+    }
+
+    async fn rename_project(
+        &self,
+        owner: Owner,
+        proj: Project,
+        name: &str
+    ) -> Result<(), CoreError>
+    {
+        check_new_project_name(name)?;
+        let now = self.now_nanos()?;
+        self.db.rename_project(owner, proj, name, now).await
+    }
+
+    async fn revert_project(
+        &self,
+        owner: Owner,
+        proj: Project,
+        revision: i64
+    ) -> Result<(), CoreError>
+    {
+        let now = self.now_nanos()?;
+        self.db.revert_project(owner, proj, revision, now).await
+    }
+
+    async fn delete_project(
+        &self,
+        owner: Owner,
+        proj: Project
+    ) -> Result<(), CoreError>
     {
         let now = self.now_nanos()?;
-        self.db.update_project(owner, proj, proj_data, now).await
+        self.db.delete_project(owner, proj, now).await
+    }
+
+    async fn restore_project(
+        &self,
+        _owner: Owner,
+        proj: Project
+    ) -> Result<(), CoreError>
+    {
+        let cutoff = self.grace_period_cutoff()?;
+        self.db.restore_project(proj, cutoff).await
+    }
+
+    async fn purge_projects(
+        &self
+    ) -> Result<(), CoreError>
+    {
+        let cutoff = self.grace_period_cutoff()?;
+        let urls = self.db.purge_projects(cutoff).await?;
+
+        // the rows are gone, so try to remove every blob even if one fails
+        let mut result = Ok(());
+        for url in urls {
+            if self.uploader.delete(&url).await.is_err() {
+                result = Err(CoreError::InternalError);
+            }
+        }
+
+        result
     }
 
     async fn get_project_revision(
@@ -184,16 +333,142 @@ where
         ).await
     }
 
+    async fn get_project_diff(
+        &self,
+        proj: Project,
+        from: i64,
+        to: i64
+    ) -> Result<ProjectDiff, CoreError>
+    {
+        let from = self.get_project_revision(proj, from).await?;
+        let to = self.get_project_revision(proj, to).await?;
+        Ok(diff_project_data(&from, &to))
+    }
+
+    async fn get_project_revisions(
+        &self,
+        proj: Project,
+        params: RevisionsParams
+    ) -> Result<Revisions, CoreError>
+    {
+        let RevisionsParams { seek, limit } = params;
+        let Seek { sort_by, dir, anchor, .. } = seek;
+
+        // try to get one extra so we can tell if we're at an endpoint
+        let limit_extra = limit.unwrap_or_default().get() as u32 + 1;
+
+        // before anchors read the window backwards from the anchor
+        let (wdir, from) = match anchor {
+            Anchor::Before(_, id) => (dir.rev(), id as i64),
+            Anchor::After(_, id) => (dir, id as i64),
+            _ => (
+                dir,
+                match dir {
+                    Direction::Ascending => 0,
+                    Direction::Descending => i64::MAX
+                }
+            )
+        };
+
+        let mut revisions = self.db.get_revisions_window(
+            proj,
+            wdir,
+            from,
+            limit_extra
+        ).await?;
+
+        // remove the "extra" item which proves we are not at the end
+        let more = revisions.len() == limit_extra as usize;
+        if more {
+            revisions.pop();
+        }
+
+        let seek = |r: &RevisionSummaryRow, a: fn(String, u32) -> Anchor| {
+            Ok::<_, CoreError>(
+                Seek {
+                    anchor: a(nanos_to_rfc3339(r.modified_at)?, r.revision as u32),
+                    sort_by,
+                    dir,
+                    filters: Filters::default()
+                }
+            )
+        };
+
+        let (prev, next) = match anchor {
+            Anchor::Before(..) => {
+                // the prev page is before the last item
+                let prev = match revisions.last() {
+                    Some(last) if more => Some(seek(last, Anchor::Before)?),
+                    _ => None
+                };
+
+                // the next page is after the first item
+                let next = match revisions.first() {
+                    Some(first) => Some(seek(first, Anchor::After)?),
+                    None => None
+                };
+
+                revisions.reverse();
+                (prev, next)
+            },
+            _ => {
+                // the prev page is before the first item
+                let prev = match revisions.first() {
+                    Some(first) if matches!(anchor, Anchor::After(..)) =>
+                        Some(seek(first, Anchor::Before)?),
+                    _ => None
+                };
+
+                // the next page is after the last item
+                let next = match revisions.last() {
+                    Some(last) if more => Some(seek(last, Anchor::After)?),
+                    _ => None
+                };
+
+                (prev, next)
+            }
+        };
+
+        let prev_page = match prev {
+            Some(prev) => Some(SeekLink::new(&prev, limit)?),
+            None => None
+        };
+
+        let next_page = match next {
+            Some(next) => Some(SeekLink::new(&next, limit)?),
+            None => None
+        };
+
+        Ok(
+            Revisions {
+                revisions: revisions.into_iter()
+                    .map(RevisionSummary::try_from)
+                    .collect::<Result<Vec<_>, _>>()?,
+                meta: Pagination {
+                    prev_page,
+                    next_page,
+                    total: self.db.get_revisions_count(proj).await?,
+                    facets: None
+                }
+            }
+        )
+    }
+
     async fn create_package(
         &self,
         owner: Owner,
         proj: Project,
+        expected: Option<i64>,
         pkg: &str,
         pkg_data: &PackageDataPost
     ) -> Result<(), CoreError>
     {
+        pkg_data.validate(&self.limits)
+            .map_err(CoreError::InvalidFields)?;
+
         let now = self.now_nanos()?;
-        self.db.create_package(owner, proj, pkg, pkg_data, now).await
+        self.db.create_package(owner, proj, expected, pkg, pkg_data, now)
+            .await
     }
 
     async fn get_release(
@@ -262,38 +537,184 @@ where
         self.db.get_image_url_at(proj, img_name, mtime).await
     }
 
+    async fn get_images(
+        &self,
+        proj: Project
+    ) -> Result<Images, CoreError>
+    {
+        Ok(
+            Images {
+                images: self.db.get_images(proj)
+                    .await?
+                    .into_iter()
+                    .map(ImageData::try_from)
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+        )
+    }
+
 // TODO: tests
     async fn add_image(
         &self,
         owner: Owner,
         proj: Project,
-        img_name: &str,
-        content_type: &Mime,
-        content_length: Option<u64>,
+        expected: Option<i64>,
+        img: &ImageUpload<'_>,
         stream: Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>
     ) -> Result<(), CoreError>
     {
         // santiy checks
-        if !image_mime_type_ok(content_type) {
+        validate_filename("img_name", img.name, &self.limits)
+            .map_err(CoreError::InvalidFields)?;
+
+        if !image_mime_type_ok(img.content_type) {
           return Err(CoreError::BadMimeType);
         }
 
-        if content_length > Some(self.max_image_size) {
+        if img.content_length > Some(self.max_image_size) {
           return Err(CoreError::TooLarge);
         }
 
         let now = self.now_nanos()?;
 
         // write file
-        let url = self.uploader.upload(img_name, Box::into_pin(stream))
+        let (url, size) = self.uploader.upload(img.name, Box::into_pin(stream))
             .await
             .or(Err(CoreError::InternalError))?;
 
         // update record
-        self.db.add_image_url(owner, proj, img_name, &url, now).await?;
+        let img = ImageFileRow {
+            filename: img.name,
+            url: &url,
+            content_type: img.content_type.as_ref(),
+            size: size as i64
+        };
+
+        self.db.add_image_url(owner, proj, expected, &img, now).await?;
 
         Ok(())
     }
+
+    async fn remove_image(
+        &self,
+        owner: Owner,
+        proj: Project,
+        img_name: &str
+    ) -> Result<(), CoreError>
+    {
+        let now = self.now_nanos()?;
+        self.db.remove_image(owner, proj, img_name, now).await
+    }
+
+    async fn get_game(
+        &self,
+        game: Game
+    ) -> Result<GameProjects, CoreError>
+    {
+        let row = self.db.get_game_row(game).await?;
+        Ok(
+            GameProjects {
+                game: GameData {
+                    id: Some(row.game_id),
+                    title: row.title,
+                    title_sort_key: row.title_sort,
+                    publisher: row.publisher,
+                    year: row.year,
+                    // the remaining fields belong to projects
+                    ..Default::default()
+                },
+                projects: self.db.get_game_projects(game).await?
+            }
+        )
+    }
+
+    async fn update_game(
+        &self,
+        owner: Owner,
+        game: Game,
+        game_data: &GamePatch
+    ) -> Result<(), CoreError>
+    {
+        game_data.validate(&self.limits)
+            .map_err(CoreError::InvalidFields)?;
+
+        // derive the sort key as for the game of a project
+        let row = self.db.get_game_row(game).await?;
+        let mut game_data = game_data.clone();
+        match (&game_data.title, &game_data.title_sort_key) {
+            (_, Some(key)) if !key.trim().is_empty() => {},
+            (title, Some(_)) => {
+                let title = title.as_ref().unwrap_or(&row.title);
+                game_data.title_sort_key = Some(title_sort_key(title));
+            },
+            (Some(title), None) => {
+                if row.title_sort == row.title ||
+                    row.title_sort == title_sort_key(&row.title)
+                {
+                    game_data.title_sort_key = Some(title_sort_key(title));
+                }
+            },
+            (None, None) => {}
+        }
+
+        let now = self.now_nanos()?;
+        self.db.update_game(owner, game, &game_data, now).await
+    }
+
+    async fn get_search_suggestions(
+        &self,
+        params: SuggestParams
+    ) -> Result<SearchSuggestions, CoreError>
+    {
+        // input with no words has no suggestions
+        let query = match prefix_query(&params.q) {
+            Some(query) => query,
+            None => return Ok(SearchSuggestions::default())
+        };
+
+        let limit = params.limit.unwrap_or_default().get() as u32;
+
+        Ok(
+            SearchSuggestions {
+                projects: self.db.get_search_suggestions(
+                    SuggestionField::ProjectName, &query, limit
+                ).await?,
+                titles: self.db.get_search_suggestions(
+                    SuggestionField::GameTitle, &query, limit
+                ).await?,
+                publishers: self.db.get_search_suggestions(
+                    SuggestionField::GamePublisher, &query, limit
+                ).await?
+            }
+        )
+    }
+
+    async fn reindex_search(
+        &self
+    ) -> Result<SearchIndexReport, CoreError>
+    {
+        let (mismatched, unresolved) = self.db.reindex_search().await?;
+
+        Ok(
+            SearchIndexReport {
+                mismatched: mismatched.into_iter()
+                    .map(SearchIndexMismatch::from)
+                    .collect(),
+                unresolved: unresolved.into_iter()
+                    .map(SearchIndexMismatch::from)
+                    .collect()
+            }
+        )
+    }
+}
+
+// Only the most common values of each facet are counted
+const MAX_FACET_VALUES: u32 = 10;
+
+// Anchors carry the query as the user wrote it; only the parsed query
+// is safe to pass to the full-text index
+fn fts_query(q: &str) -> Result<String, CoreError> {
+    parse_query(q).or(Err(CoreError::MalformedQuery))
 }
 
 fn image_mime_type_ok(mime: &Mime) -> bool {
@@ -320,6 +741,66 @@ where
             .ok_or(CoreError::InternalError)
     }
 
+    // projects deleted before the cutoff are past their grace period
+    fn grace_period_cutoff(&self) -> Result<i64, CoreError> {
+        ((self.now)() - self.deletion_grace_period)
+            .timestamp_nanos_opt()
+            .ok_or(CoreError::InternalError)
+    }
+
+    async fn derived_title_sort_key(
+        &self,
+        proj: Project,
+        proj_data: &ProjectDataPatch
+    ) -> Result<Option<String>, CoreError>
+    {
+        let game = &proj_data.game;
+
+        // a game id replaces the title given with that of the game
+        let linked = match game.id {
+            Some(id) => Some(self.db.get_game_row(Game(id)).await?),
+            None => None
+        };
+
+        let title = linked.as_ref().map(|g| &g.title).or(game.title.as_ref());
+
+        match (title, &game.title_sort_key) {
+            // a nonempty sort key is an override by the owner
+            (_, Some(key)) if !key.trim().is_empty() => Ok(None),
+            // an empty sort key asks for the derived one
+            (Some(title), Some(_)) => Ok(Some(title_sort_key(title))),
+            (None, Some(_)) => {
+                let row = self.db.get_project_row(proj).await?;
+                Ok(Some(title_sort_key(&row.game_title)))
+            },
+            // a new title or game gets a new sort key unless the old one
+            // was an override
+            (Some(title), None) => {
+                let row = self.db.get_project_row(proj).await?;
+                if linked.is_none() && *title == row.game_title {
+                    return Ok(None);
+                }
+
+                let game_key = match row.game_id {
+                    Some(id) => Some(self.db.get_game_row(Game(id)).await?.title_sort),
+                    None => None
+                };
+
+                let derived = row.game_title_sort == row.game_title ||
+                    row.game_title_sort == title_sort_key(&row.game_title) ||
+                    game_key.is_some_and(|k| k == row.game_title_sort);
+
+                Ok(
+                    derived.then(|| match &linked {
+                        Some(g) => g.title_sort.clone(),
+                        None => title_sort_key(title)
+                    })
+                )
+            },
+            (None, None) => Ok(None)
+        }
+    }
+
     async fn make_version_data(
         &self,
         r: FileRow
@@ -398,6 +879,8 @@ where
             .await?
             .users;
 
+        let gallery = self.db.get_gallery(proj, proj_row.revision).await?;
+
         let packages = try_join_all(
             package_rows
                 .into_iter()
@@ -415,15 +898,25 @@ where
                 revision: proj_row.revision,
                 created_at: nanos_to_rfc3339(proj_row.created_at)?,
                 modified_at: nanos_to_rfc3339(proj_row.modified_at)?,
-                tags: vec![],
+                tags: proj_row.tags.0,
                 game: GameData {
+                    id: proj_row.game_id,
                     title: proj_row.game_title,
                     title_sort_key: proj_row.game_title_sort,
                     publisher: proj_row.game_publisher,
-                    year: proj_row.game_year
+                    year: proj_row.game_year,
+                    alternate_titles: proj_row.game_alternate_titles.0,
+                    designers: proj_row.game_designers.0,
+                    min_players: proj_row.game_min_players,
+                    max_players: proj_row.game_max_players,
+                    playing_time: proj_row.game_playing_time,
+                    era: proj_row.game_era,
+                    external_id: proj_row.game_external_id
                 },
                 readme: proj_row.readme,
+                readme_html: None,
                 image: proj_row.image,
+                gallery,
                 owners,
                 packages
             }
@@ -435,14 +928,22 @@ where
         anchor: &Anchor,
         sort_by: SortBy,
         dir: Direction,
+        filters: &Filters,
         limit_extra: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>
     {
+        // only the query anchors use the query
+        let query = anchor.query()
+            .map(fts_query)
+            .transpose()?
+            .unwrap_or_default();
+
         match anchor {
             Anchor::Start =>
                 self.db.get_projects_end_window(
                     sort_by,
                     dir,
+                    filters,
                     limit_extra
                 ),
             Anchor::After(field, id) =>
@@ -451,6 +952,7 @@ where
                     dir,
                     field,
                     *id,
+                    filters,
                     limit_extra
                 ),
             Anchor::Before(field, id) =>
@@ -459,36 +961,111 @@ where
                     dir.rev(),
                     field,
                     *id,
+                    filters,
                     limit_extra
                 ),
-            Anchor::StartQuery(query) =>
+            Anchor::StartQuery(_) =>
                 self.db.get_projects_query_end_window(
-                    query,
+                    &query,
                     sort_by,
                     dir,
+                    filters,
                     limit_extra
                 ),
-            Anchor::AfterQuery(query, field, id) =>
+            Anchor::AfterQuery(_, field, id) =>
                 self.db.get_projects_query_mid_window(
-                    query,
+                    &query,
                     sort_by,
                     dir,
                     field,
                     *id,
+                    filters,
                     limit_extra
                 ),
-            Anchor::BeforeQuery(query, field, id) =>
+            Anchor::BeforeQuery(_, field, id) =>
                 self.db.get_projects_query_mid_window(
-                    query,
+                    &query,
                     sort_by,
                     dir.rev(),
                     field,
                     *id,
+                    filters,
                     limit_extra
                 )
         }.await
     }
 
+    async fn add_highlights(
+        &self,
+        query: &str,
+        projects: &mut [ProjectSummary]
+    ) -> Result<(), CoreError>
+    {
+        let names = projects.iter()
+            .map(|p| p.name.clone())
+            .collect::<Vec<_>>();
+
+        let mut rows = self.db.get_projects_highlights(query, &names)
+            .await?
+            .into_iter()
+            .map(|r| (r.name.clone(), r))
+            .collect::<HashMap<_, _>>();
+
+        let render = |s: &str| render_highlight(s, HIGHLIGHT_START, HIGHLIGHT_END);
+
+        for p in projects {
+            p.highlights = rows.remove(&p.name).map(|r|
+                Highlights {
+                    title: render(&r.title),
+                    description: render(&r.description),
+                    readme: render(&r.readme)
+                }
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn get_facet(
+        &self,
+        facet: Facet,
+        query: Option<&str>,
+        filters: &Filters
+    ) -> Result<Vec<FacetCount>, CoreError>
+    {
+        Ok(
+            self.db.get_projects_facet(facet, query, filters, MAX_FACET_VALUES)
+                .await?
+                .into_iter()
+                .map(|(value, count)| FacetCount { value, count })
+                .collect()
+        )
+    }
+
+    async fn get_facets(
+        &self,
+        seek: &Seek
+    ) -> Result<Facets, CoreError>
+    {
+        // facets cover all the matching projects, not just one page
+        let query = seek.anchor.query().map(fts_query).transpose()?;
+        let query = query.as_deref();
+
+        Ok(
+            Facets {
+                publisher: self.get_facet(
+                    Facet::Publisher, query, &seek.filters
+                ).await?,
+                decade: self.get_facet(
+                    Facet::Decade, query, &seek.filters
+                ).await?,
+                tag: self.get_facet(
+                    Facet::Tag, query, &seek.filters
+                ).await?
+            }
+        )
+    }
+
     async fn get_projects_from(
         &self,
         seek: Seek,
@@ -496,7 +1073,7 @@ where
     ) -> Result<(Option<Seek>, Option<Seek>, Vec<ProjectSummary>, i64), CoreError>
     {
         // unpack the seek
-        let Seek { sort_by, dir, anchor } = seek;
+        let Seek { sort_by, dir, anchor, filters } = seek;
 
         // try to get one extra so we can tell if we're at an endpoint
         let limit_extra = limit.get() as u32 + 1;
@@ -506,6 +1083,7 @@ where
             &anchor,
             sort_by,
             dir,
+            &filters,
             limit_extra
         ).await?;
 
@@ -514,18 +1092,18 @@ where
             &anchor,
             sort_by,
             dir,
+            &filters,
             limit_extra,
             &mut projects
         )?;
 
         // get the total number of responsive items
-        let total = match anchor {
-            Anchor::StartQuery(ref q) |
-            Anchor::AfterQuery(ref q, ..) |
-            Anchor::BeforeQuery(ref q, ..) =>
-                self.db.get_projects_query_count(q),
-            _ => self.db.get_projects_count()
-        }.await?;
+        let total = match anchor.query() {
+            Some(q) =>
+                self.db.get_projects_query_count(&fts_query(q)?, &filters)
+                    .await?,
+            None => self.db.get_projects_count(&filters).await?
+        };
 
         // convert the rows to summaries
         let pi = projects.into_iter().map(ProjectSummary::try_from);
@@ -558,6 +1136,7 @@ fn get_prev_for_before(
     anchor: &Anchor,
     sort_by: SortBy,
     dir: Direction,
+    filters: &Filters,
     limit_extra: u32,
     projects: &mut Vec<ProjectSummaryRow>
 ) -> Result<Option<Seek>, CoreError>
@@ -588,7 +1167,12 @@ fn get_prev_for_before(
             Anchor::AfterQuery(..) => unreachable!()
         };
 
-        Ok(Some(Seek { anchor: prev_anchor, sort_by, dir }))
+        Ok(Some(Seek {
+            anchor: prev_anchor,
+            sort_by,
+            dir,
+            filters: filters.clone()
+        }))
     }
     else {
         // there are no pages in the forward direction
@@ -600,6 +1184,7 @@ fn get_next_for_before(
     anchor: &Anchor,
     sort_by: SortBy,
     dir: Direction,
+    filters: &Filters,
     projects: &[ProjectSummaryRow]
 ) -> Result<Option<Seek>, CoreError>
 {
@@ -627,7 +1212,12 @@ fn get_next_for_before(
             Anchor::AfterQuery(..) => unreachable!()
         };
 
-        Ok(Some(Seek { anchor: next_anchor, sort_by, dir }))
+        Ok(Some(Seek {
+            anchor: next_anchor,
+            sort_by,
+            dir,
+            filters: filters.clone()
+        }))
     }
 }
 
@@ -635,6 +1225,7 @@ fn get_next_for_after(
     anchor: &Anchor,
     sort_by: SortBy,
     dir: Direction,
+    filters: &Filters,
     limit_extra: u32,
     projects: &mut Vec<ProjectSummaryRow>
 ) -> Result<Option<Seek>, CoreError>
@@ -665,7 +1256,12 @@ fn get_next_for_after(
             Anchor::BeforeQuery(..) => unreachable!()
         };
 
-        Ok(Some(Seek { anchor: next_anchor, sort_by, dir }))
+        Ok(Some(Seek {
+            anchor: next_anchor,
+            sort_by,
+            dir,
+            filters: filters.clone()
+        }))
     }
     else {
         // there are no pages in the forward direction
@@ -677,6 +1273,7 @@ fn get_prev_for_after(
     anchor: &Anchor,
     sort_by: SortBy,
     dir: Direction,
+    filters: &Filters,
     projects: &[ProjectSummaryRow]
 ) -> Result<Option<Seek>, CoreError>
 {
@@ -706,7 +1303,12 @@ fn get_prev_for_after(
                 Anchor::BeforeQuery(..) => unreachable!()
             };
 
-            Ok(Some(Seek { anchor: prev_anchor, sort_by, dir }))
+            Ok(Some(Seek {
+                anchor: prev_anchor,
+                sort_by,
+                dir,
+                filters: filters.clone()
+            }))
         },
         Anchor::Before(..) |
         Anchor::BeforeQuery(..) => unreachable!()
@@ -717,6 +1319,7 @@ fn get_links(
     anchor: &Anchor,
     sort_by: SortBy,
     dir: Direction,
+    filters: &Filters,
     limit_extra: u32,
     projects: &mut Vec<ProjectSummaryRow>
 ) -> Result<(Option<Seek>, Option<Seek>), CoreError>
@@ -728,6 +1331,7 @@ fn get_links(
                 anchor,
                 sort_by,
                 dir,
+                filters,
                 limit_extra,
                 projects
             )?;
@@ -736,6 +1340,7 @@ fn get_links(
                 anchor,
                 sort_by,
                 dir,
+                filters,
                 projects
             )?;
 
@@ -749,6 +1354,7 @@ fn get_links(
                 anchor,
                 sort_by,
                 dir,
+                filters,
                 limit_extra,
                 projects
             )?;
@@ -757,6 +1363,7 @@ fn get_links(
                 anchor,
                 sort_by,
                 dir,
+                filters,
                 projects
             )?;
 
@@ -779,6 +1386,60 @@ impl ProjectSummaryRow {
     }
 }
 
+impl TryFrom<RevisionSummaryRow> for RevisionSummary {
+    type Error = CoreError;
+
+    fn try_from(r: RevisionSummaryRow) -> Result<Self, Self::Error> {
+        let changes = [
+            (r.created, "created"),
+            (r.metadata, "metadata"),
+            (r.renamed, "name"),
+            (r.image, "image"),
+            (r.package, "package"),
+            (r.release, "release")
+        ]
+        .into_iter()
+        .filter(|(changed, _)| *changed)
+        .map(|(_, change)| change.into())
+        .collect();
+
+        Ok(
+            RevisionSummary {
+                revision: r.revision,
+                modified_at: nanos_to_rfc3339(r.modified_at)?,
+                modified_by: r.modified_by,
+                changes
+            }
+        )
+    }
+}
+
+impl TryFrom<ImageRow> for ImageData {
+    type Error = CoreError;
+
+    fn try_from(r: ImageRow) -> Result<Self, Self::Error> {
+        Ok(
+            ImageData {
+                filename: r.filename,
+                url: r.url,
+                content_type: r.content_type,
+                size: r.size,
+                published_at: nanos_to_rfc3339(r.published_at)?,
+                published_by: r.published_by
+            }
+        )
+    }
+}
+
+impl From<SearchIndexMismatchRow> for SearchIndexMismatch {
+    fn from(r: SearchIndexMismatchRow) -> Self {
+        SearchIndexMismatch {
+            project_id: r.project_id,
+            name: r.name
+        }
+    }
+}
+
 impl TryFrom<ProjectSummaryRow> for ProjectSummary {
     type Error = CoreError;
 
@@ -790,13 +1451,22 @@ impl TryFrom<ProjectSummaryRow> for ProjectSummary {
                 revision: r.revision,
                 created_at: nanos_to_rfc3339(r.created_at)?,
                 modified_at: nanos_to_rfc3339(r.modified_at)?,
-                tags: vec![],
+                tags: r.tags.0,
                 game: GameData {
+                    id: r.game_id,
                     title: r.game_title,
                     title_sort_key: r.game_title_sort,
                     publisher: r.game_publisher,
-                    year: r.game_year
-                }
+                    year: r.game_year,
+                    alternate_titles: r.game_alternate_titles.0,
+                    designers: r.game_designers.0,
+                    min_players: r.game_min_players,
+                    max_players: r.game_max_players,
+                    playing_time: r.game_playing_time,
+                    era: r.game_era,
+                    external_id: r.game_external_id
+                },
+                highlights: None
             }
         )
     }
@@ -807,10 +1477,11 @@ mod test {
     use super::*;
 
     use crate::{
-        model::GameDataPatch,
+        model::{FieldDiff, GameDataDiff, GameDataPatch, ReleaseRef},
         pagination::Direction,
         sqlite::{Pool, SqlxDatabaseClient},
-        upload::UploadError
+        upload::{UploadError, stream_to_writer},
+        validation::FieldError
     };
 
     const NOW: &str = "2023-11-12T15:50:06.419538067+00:00";
@@ -825,19 +1496,32 @@ mod test {
         *NOW_DT
     }
 
+    fn fake_after_grace_period() -> DateTime<Utc> {
+        *NOW_DT + Duration::days(31)
+    }
+
     struct FakeUploader {}
 
     #[async_trait]
     impl Uploader for FakeUploader {
         async fn upload<S>(
             &self,
-            _filename: &str,
-            _stream: S
-        ) -> Result<String, UploadError>
+            filename: &str,
+            stream: S
+        ) -> Result<(String, u64), UploadError>
         where
             S: Stream<Item = Result<Bytes, io::Error>> + Send
         {
-            unreachable!();
+            let size = stream_to_writer(stream, tokio::io::sink()).await?;
+            Ok((format!("https://example.com/images/{filename}"), size))
+        }
+
+        async fn delete(
+            &self,
+            _url: &str
+        ) -> Result<(), UploadError>
+        {
+            Ok(())
         }
     }
 
@@ -851,7 +1535,9 @@ mod test {
             db: SqlxDatabaseClient(pool),
             uploader: FakeUploader {},
             now,
-            max_image_size
+            max_image_size,
+            deletion_grace_period: Duration::days(30),
+            limits: Limits::default()
         }
     }
 
@@ -867,11 +1553,14 @@ mod test {
             ),
             tags: vec![],
             game: GameData {
+                id: None,
                 title: "".into(),
                 title_sort_key: "".into(),
                 publisher: "".into(),
-                year: "".into()
-            }
+                year: "".into(),
+                ..Default::default()
+            },
+            highlights: None
         }
     }
 
@@ -920,7 +1609,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -944,12 +1634,247 @@ mod test {
                 Seek {
                     anchor: Anchor::After("c".into(), 3),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
+                }
+            )
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects", "proj_facets"))]
+    async fn get_projects_filters_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let filters = Filters {
+            publisher: Some("GMT".into()),
+            ..Default::default()
+        };
+
+        let (prev, next, summaries, total) = core.get_projects_from(
+            Seek {
+                sort_by: SortBy::ProjectName,
+                dir: Direction::Ascending,
+                anchor: Anchor::Start,
+                filters: filters.clone()
+            },
+            Limit::new(2).unwrap()
+        ).await.unwrap();
+
+        assert_eq!(
+            summaries.into_iter().map(|s| s.name).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+
+        assert_eq!(total, 3);
+
+        assert_eq!(prev, None);
+
+        // the next page keeps the filters
+        assert_eq!(
+            next,
+            Some(
+                Seek {
+                    anchor: Anchor::After("c".into(), 3),
+                    sort_by: SortBy::ProjectName,
+                    dir: Direction::Ascending,
+                    filters
+                }
+            )
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects", "proj_facets"))]
+    async fn get_projects_facets_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = ProjectsParams {
+            seek: Seek {
+                anchor: Anchor::StartQuery("gmt".into()),
+                sort_by: SortBy::Relevance,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            limit: Limit::new(1),
+            facets: true,
+            highlight: false
+        };
+
+        let projects = core.get_projects(params).await.unwrap();
+
+        // the facets count every match, not just the one on the page
+        assert_eq!(projects.projects.len(), 1);
+        assert_eq!(
+            projects.meta.facets,
+            Some(
+                Facets {
+                    publisher: vec![
+                        FacetCount { value: "GMT".into(), count: 3 }
+                    ],
+                    decade: vec![
+                        FacetCount { value: "1970s".into(), count: 1 },
+                        FacetCount { value: "2000s".into(), count: 1 }
+                    ],
+                    tag: vec![
+                        FacetCount { value: "card driven".into(), count: 1 },
+                        FacetCount { value: "hex and counter".into(), count: 1 },
+                        FacetCount { value: "WWII".into(), count: 1 }
+                    ]
+                }
+            )
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects", "proj_metadata"))]
+    async fn get_projects_alternate_title_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = ProjectsParams {
+            seek: Seek {
+                anchor: Anchor::StartQuery("title:eia".into()),
+                sort_by: SortBy::Relevance,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let projects = core.get_projects(params).await.unwrap();
+
+        assert_eq!(projects.projects.len(), 1);
+        assert_eq!(projects.projects[0].name, "b");
+        // alternate titles are not the primary title
+        assert_eq!(projects.projects[0].game.title, "");
+        assert_eq!(
+            projects.projects[0].game.alternate_titles,
+            ["Empires in Arms", "EiA"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_highlight_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = ProjectsParams {
+            seek: Seek {
+                anchor: Anchor::StartQuery("xyz".into()),
+                sort_by: SortBy::Relevance,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            highlight: true,
+            ..Default::default()
+        };
+
+        let projects = core.get_projects(params).await.unwrap();
+
+        assert_eq!(
+            projects.projects[0].highlights,
+            Some(
+                Highlights {
+                    title: "".into(),
+                    description: "abc <mark>xyz</mark>".into(),
+                    readme: "".into()
                 }
             )
         );
     }
 
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_highlight_links(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = ProjectsParams {
+            seek: Seek {
+                anchor: Anchor::StartQuery("abc".into()),
+                sort_by: SortBy::Relevance,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            limit: Limit::new(1),
+            highlight: true,
+            ..Default::default()
+        };
+
+        let projects = core.get_projects(params).await.unwrap();
+
+        assert!(projects.projects[0].highlights.is_some());
+        assert!(
+            projects.meta.next_page.unwrap()
+                .to_string()
+                .ends_with("&highlight=true")
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects", "proj_facets"))]
+    async fn get_search_suggestions_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = SuggestParams { q: "gm".into(), limit: None };
+        assert_eq!(
+            core.get_search_suggestions(params).await.unwrap(),
+            SearchSuggestions {
+                projects: vec![],
+                titles: vec![],
+                publishers: vec!["GMT".into()]
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects"))]
+    async fn reindex_search_ok(pool: Pool) {
+        // make the index drift by changing a project behind its back
+        sqlx::query("DROP TRIGGER projects_au")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("UPDATE projects SET game_title = 'x' WHERE project_id = 2")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let core = make_core(pool, fake_now, 0);
+
+        assert_eq!(
+            core.reindex_search().await.unwrap(),
+            SearchIndexReport {
+                mismatched: vec![
+                    SearchIndexMismatch {
+                        project_id: 2,
+                        name: Some("b".into())
+                    }
+                ],
+                unresolved: vec![]
+            }
+        );
+
+        assert_eq!(
+            core.reindex_search().await.unwrap(),
+            SearchIndexReport::default()
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects"))]
+    async fn get_search_suggestions_empty(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let params = SuggestParams { q: " ".into(), limit: None };
+        assert_eq!(
+            core.get_search_suggestions(params).await.unwrap(),
+            SearchSuggestions::default()
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "ten_projects"))]
+    async fn get_projects_no_facets_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let projects = core.get_projects(ProjectsParams::default())
+            .await
+            .unwrap();
+        assert_eq!(projects.meta.facets, None);
+        assert!(projects.projects.iter().all(|p| p.highlights.is_none()));
+    }
+
     #[sqlx::test(fixtures("users", "ten_projects"))]
     async fn get_projects_pname_end_ok(pool: Pool) {
         let core = make_core(pool, fake_now, 0);
@@ -958,7 +1883,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -982,7 +1908,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("h".into(), 8),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -996,7 +1923,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::After("a".into(), 1)
+                anchor: Anchor::After("a".into(), 1),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1018,7 +1946,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("b".into(), 2),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1029,7 +1958,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("d".into(), 4),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1043,7 +1973,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::After("h".into(), 8)
+                anchor: Anchor::After("h".into(), 8),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1065,7 +1996,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("g".into(), 7),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1076,7 +2008,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("e".into(), 5),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1090,7 +2023,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Before("e".into(), 5)
+                anchor: Anchor::Before("e".into(), 5),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1112,7 +2046,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("b".into(), 2),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1123,7 +2058,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("d".into(), 4),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1136,7 +2072,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::Before("e".into(), 5)
+                anchor: Anchor::Before("e".into(), 5),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1158,7 +2095,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("h".into(), 8),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1169,7 +2107,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("f".into(), 6),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1183,7 +2122,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::Before("d".into(), 4)
+                anchor: Anchor::Before("d".into(), 4),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1207,7 +2147,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("c".into(), 3),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1221,7 +2162,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::Before("g".into(), 7)
+                anchor: Anchor::Before("g".into(), 7),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1245,7 +2187,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("h".into(), 8),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1259,7 +2202,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Ascending,
-                anchor: Anchor::After("g".into(), 7)
+                anchor: Anchor::After("g".into(), 7),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1281,7 +2225,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("h".into(), 8),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1297,7 +2242,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::After("d".into(), 4)
+                anchor: Anchor::After("d".into(), 4),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1319,7 +2265,8 @@ mod test {
                 Seek {
                     anchor: Anchor::Before("c".into(), 3),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1335,7 +2282,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ModificationTime,
                 dir: Direction::Descending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1362,7 +2310,8 @@ mod test {
                         8
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1376,7 +2325,8 @@ mod test {
             Seek {
                 sort_by: SortBy::ProjectName,
                 dir: Direction::Descending,
-                anchor: Anchor::Start
+                anchor: Anchor::Start,
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1400,7 +2350,8 @@ mod test {
                 Seek {
                     anchor: Anchor::After("h".into(), 8),
                     sort_by: SortBy::ProjectName,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1417,7 +2368,8 @@ mod test {
                 anchor: Anchor::After(
                     "1970-01-01T00:00:00.000000001+00:00".into(),
                     1
-                )
+                ),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1442,7 +2394,8 @@ mod test {
                         2
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1456,7 +2409,8 @@ mod test {
                         4
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1473,7 +2427,8 @@ mod test {
                 anchor: Anchor::After(
                     "1970-01-01T00:00:00.000000008+00:00".into(),
                     8
-                )
+                ),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1498,7 +2453,8 @@ mod test {
                         7
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1512,7 +2468,8 @@ mod test {
                         5
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1529,7 +2486,8 @@ mod test {
                 anchor: Anchor::Before(
                     "1970-01-01T00:00:00.000000005+00:00".into(),
                     5
-                )
+                ),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1554,7 +2512,8 @@ mod test {
                         2
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1568,7 +2527,8 @@ mod test {
                         4
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Ascending
+                    dir: Direction::Ascending,
+                    ..Default::default()
                 }
             )
         );
@@ -1585,7 +2545,8 @@ mod test {
                 anchor: Anchor::Before(
                     "1970-01-01T00:00:00.000000006+00:00".into(),
                     5
-                )
+                ),
+                ..Default::default()
             },
             Limit::new(3).unwrap()
         ).await.unwrap();
@@ -1610,7 +2571,8 @@ mod test {
                         8
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1624,7 +2586,8 @@ mod test {
                         6
                     ),
                     sort_by: SortBy::ModificationTime,
-                    dir: Direction::Descending
+                    dir: Direction::Descending,
+                    ..Default::default()
                 }
             )
         );
@@ -1643,13 +2606,17 @@ mod test {
                 modified_at: "2023-12-14T15:50:06.419538067+00:00".into(),
                 tags: vec![],
                 game: GameData {
+                    id: None,
                     title: "A Game of Tests".into(),
                     title_sort_key: "Game of Tests, A".into(),
                     publisher: "Test Game Company".into(),
-                    year: "1979".into()
+                    year: "1979".into(),
+                    ..Default::default()
                 },
                 readme: "".into(),
+                readme_html: None,
                 image: None,
+                gallery: vec![],
                 owners: vec!["alice".into(), "bob".into()],
                 packages: vec![
                     PackageData {
@@ -1723,13 +2690,17 @@ mod test {
                 modified_at: "2023-12-14T15:50:06.419538067+00:00".into(),
                 tags: vec![],
                 game: GameData {
+                    id: None,
                     title: "A Game of Tests".into(),
                     title_sort_key: "Game of Tests, A".into(),
                     publisher: "Test Game Company".into(),
-                    year: "1979".into()
+                    year: "1979".into(),
+                    ..Default::default()
                 },
                 readme: "".into(),
+                readme_html: None,
                 image: None,
+                gallery: vec![],
                 owners: vec!["alice".into(), "bob".into()],
                 packages: vec![
                     PackageData {
@@ -1791,13 +2762,17 @@ mod test {
                 modified_at: "2023-11-12T15:50:06.419538067+00:00".into(),
                 tags: vec![],
                 game: GameData {
+                    id: None,
                     title: "A Game of Tests".into(),
                     title_sort_key: "Game of Tests, A".into(),
                     publisher: "Test Game Company".into(),
-                    year: "1978".into()
+                    year: "1978".into(),
+                    ..Default::default()
                 },
                 readme: "".into(),
+                readme_html: None,
                 image: None,
+                gallery: vec![],
                 owners: vec!["alice".into(), "bob".into()],
                 packages: vec![
                     PackageData {
@@ -1817,111 +2792,453 @@ mod test {
         );
     }
 
-    #[sqlx::test(fixtures("users", "projects", "packages"))]
-    async fn create_project_ok(pool: Pool) {
-        let core = make_core(pool, fake_now, 0);
-
-        let user = User(1);
-        let name = "newproj";
-        let data = ProjectData {
-            name: name.into(),
-            description: "A New Game".into(),
-            revision: 1,
-            created_at: NOW.into(),
-            modified_at: NOW.into(),
-            tags: vec![],
-            game: GameData {
-                title: "Some New Game".into(),
-                title_sort_key: "Some New Game".into(),
-                publisher: "XYZ Games".into(),
-                year: "1999".into()
-            },
-            readme: "".into(),
-            image: None,
-            owners: vec!["bob".into()],
-            packages: vec![]
-        };
-
-        let cdata = ProjectDataPost {
-            description: data.description.clone(),
-            tags: vec![],
-            game: GameData {
-                title: data.game.title.clone(),
-                title_sort_key: data.game.title_sort_key.clone(),
-                publisher: data.game.publisher.clone(),
-                year: data.game.year.clone()
-            },
-            readme: "".into(),
-            image: None
-        };
 
-        core.create_project(user, name, &cdata).await.unwrap();
-        let proj = core.get_project_id(name).await.unwrap();
-        assert_eq!(core.get_project(proj).await.unwrap(), data);
+    fn revision_summary(
+        revision: i64,
+        modified_at: &str,
+        change: &str
+    ) -> RevisionSummary
+    {
+        RevisionSummary {
+            revision,
+            modified_at: modified_at.into(),
+            modified_by: "bob".into(),
+            changes: vec![change.into()]
+        }
     }
 
-    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
-    async fn update_project_ok(pool: Pool) {
-        let core = make_core(pool, fake_now, 0);
-
-        let name = "test_game";
-        let new_data = ProjectData {
-            name: name.into(),
-            description: "new description".into(),
-            revision: 4,
-            created_at: "2023-11-12T15:50:06.419538067+00:00".into(),
-            modified_at: NOW.into(),
-            tags: vec![],
-            game: GameData {
-                title: "Some New Game".into(),
-                title_sort_key: "Some New Game".into(),
-                publisher: "XYZ Games".into(),
-                year: "1999".into()
-            },
-            readme: "".into(),
-            image: None,
-            owners: vec!["bob".into()],
-            packages: vec![]
-        };
-
-        let cdata = ProjectDataPatch {
-            description: Some(new_data.description.clone()),
-            tags: Some(vec![]),
-            game: GameDataPatch {
-                title: Some(new_data.game.title.clone()),
-                title_sort_key: Some(new_data.game.title_sort_key.clone()),
-                publisher: Some(new_data.game.publisher.clone()),
-                year: Some(new_data.game.year.clone())
-            },
-            readme: Some("".into()),
-            image: None
-        };
 
-        let proj = core.get_project_id(name).await.unwrap();
-        let old_data = core.get_project(proj).await.unwrap();
-        core.update_project(Owner(1), Project(42), &cdata).await.unwrap();
-        // project has new data
-        assert_eq!(core.get_project(proj).await.unwrap(), new_data);
-        // old data is kept as a revision
+    #[sqlx::test(fixtures("users", "projects", "two_owners", "packages"))]
+    async fn get_project_diff_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
         assert_eq!(
-            core.get_project_revision(proj, 3).await.unwrap(),
-            old_data
+            core.get_project_diff(Project(42), 1, 3).await.unwrap(),
+            ProjectDiff {
+                from: 1,
+                to: 3,
+                name: None,
+                description: None,
+                tags: None,
+                game: GameDataDiff {
+                    year: Some(
+                        FieldDiff {
+                            from: "1978".into(),
+                            to: "1979".into()
+                        }
+                    ),
+                    ..Default::default()
+                },
+                readme: None,
+                image: None,
+                gallery: None,
+                packages_added: vec!["a_package".into()],
+                packages_removed: vec![],
+                releases_added: vec![
+                    ReleaseRef {
+                        package: "a_package".into(),
+                        version: "1.2.3".into()
+                    },
+                    ReleaseRef {
+                        package: "a_package".into(),
+                        version: "1.2.4".into()
+                    }
+                ],
+                releases_removed: vec![]
+            }
         );
     }
 
-    #[sqlx::test(fixtures("users", "projects", "packages"))]
-    async fn get_release_ok(pool: Pool) {
+    #[sqlx::test(fixtures("users", "projects", "two_owners", "packages"))]
+    async fn get_project_diff_not_a_revision(pool: Pool) {
         let core = make_core(pool, fake_now, 0);
         assert_eq!(
-            core.get_release(Project(42), Package(1)).await.unwrap(),
-            "https://example.com/a_package-1.2.4"
+            core.get_project_diff(Project(42), 1, 4).await.unwrap_err(),
+            CoreError::NotARevision
         );
     }
 
-    #[sqlx::test(fixtures("users", "projects", "packages"))]
-    async fn get_release_version_ok(pool: Pool) {
+    #[sqlx::test(fixtures("users", "projects", "two_owners", "packages"))]
+    async fn revert_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let old = core.get_project_revision(Project(42), 1).await.unwrap();
+        let prev = core.get_project(Project(42)).await.unwrap();
+
+        core.revert_project(Owner(1), Project(42), 1).await.unwrap();
+
+        let cur = core.get_project(Project(42)).await.unwrap();
+
+        // the project data reverts, but the packages do not
+        assert_eq!(cur.revision, 4);
+        assert_eq!(cur.game, GameData { id: cur.game.id, ..old.game });
+        assert_eq!(cur.description, old.description);
+        assert_ne!(cur.packages, old.packages);
+        assert_eq!(cur.packages, prev.packages);
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "two_owners", "packages"))]
+    async fn revert_project_not_a_revision(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.revert_project(Owner(1), Project(42), 5).await.unwrap_err(),
+            CoreError::NotARevision
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_current_revision_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(core.get_current_revision(Project(42)).await.unwrap(), 3);
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_current_revision_not_a_project(pool: Pool) {
         let core = make_core(pool, fake_now, 0);
-        let version = "1.2.3".parse::<Version>().unwrap();
+        assert_eq!(
+            core.get_current_revision(Project(1)).await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_project_revisions_start(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let params = RevisionsParams {
+            limit: Limit::new(2),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            core.get_project_revisions(Project(42), params).await.unwrap(),
+            Revisions {
+                revisions: vec![
+                    revision_summary(6, "2023-12-15T16:00:00+00:00", "image"),
+                    revision_summary(5, "2023-12-15T15:43:20+00:00", "release")
+                ],
+                meta: Pagination {
+                    prev_page: None,
+                    next_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::After(
+                                    "2023-12-15T15:43:20+00:00".into(),
+                                    5
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Descending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    total: 6,
+                    facets: None
+                }
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_project_revisions_after(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let params = RevisionsParams {
+            seek: Seek {
+                anchor: Anchor::After("2023-12-15T15:43:20+00:00".into(), 5),
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Descending,
+                ..Default::default()
+            },
+            limit: Limit::new(2)
+        };
+
+        assert_eq!(
+            core.get_project_revisions(Project(42), params).await.unwrap(),
+            Revisions {
+                revisions: vec![
+                    revision_summary(4, "2023-12-15T15:26:40+00:00", "package"),
+                    revision_summary(3, "2023-12-15T15:10:00+00:00", "name")
+                ],
+                meta: Pagination {
+                    prev_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::Before(
+                                    "2023-12-15T15:26:40+00:00".into(),
+                                    4
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Descending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    next_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::After(
+                                    "2023-12-15T15:10:00+00:00".into(),
+                                    3
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Descending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    total: 6,
+                    facets: None
+                }
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_project_revisions_before(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let params = RevisionsParams {
+            seek: Seek {
+                anchor: Anchor::Before("2023-12-15T15:10:00+00:00".into(), 3),
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Descending,
+                ..Default::default()
+            },
+            limit: Limit::new(2)
+        };
+
+        assert_eq!(
+            core.get_project_revisions(Project(42), params).await.unwrap(),
+            Revisions {
+                revisions: vec![
+                    revision_summary(5, "2023-12-15T15:43:20+00:00", "release"),
+                    revision_summary(4, "2023-12-15T15:26:40+00:00", "package")
+                ],
+                meta: Pagination {
+                    prev_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::Before(
+                                    "2023-12-15T15:43:20+00:00".into(),
+                                    5
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Descending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    next_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::After(
+                                    "2023-12-15T15:26:40+00:00".into(),
+                                    4
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Descending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    total: 6,
+                    facets: None
+                }
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_project_revisions_ascending_end(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let params = RevisionsParams {
+            seek: Seek {
+                anchor: Anchor::After("2023-12-15T15:26:40+00:00".into(), 4),
+                sort_by: SortBy::ModificationTime,
+                dir: Direction::Ascending,
+                ..Default::default()
+            },
+            limit: Limit::new(2)
+        };
+
+        assert_eq!(
+            core.get_project_revisions(Project(42), params).await.unwrap(),
+            Revisions {
+                revisions: vec![
+                    revision_summary(5, "2023-12-15T15:43:20+00:00", "release"),
+                    revision_summary(6, "2023-12-15T16:00:00+00:00", "image")
+                ],
+                meta: Pagination {
+                    prev_page: Some(
+                        SeekLink::new(
+                            &Seek {
+                                anchor: Anchor::Before(
+                                    "2023-12-15T15:43:20+00:00".into(),
+                                    5
+                                ),
+                                sort_by: SortBy::ModificationTime,
+                                dir: Direction::Ascending,
+                                ..Default::default()
+                            },
+                            Limit::new(2)
+                        ).unwrap()
+                    ),
+                    next_page: None,
+                    total: 6,
+                    facets: None
+                }
+            }
+        );
+    }
+    #[sqlx::test(fixtures("users", "projects", "packages"))]
+    async fn create_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let user = User(1);
+        let name = "newproj";
+        let data = ProjectData {
+            name: name.into(),
+            description: "A New Game".into(),
+            revision: 1,
+            created_at: NOW.into(),
+            modified_at: NOW.into(),
+            tags: vec!["WWII".into(), "solitaire".into()],
+            game: GameData {
+                id: Some(1),
+                title: "Some New Game".into(),
+                title_sort_key: "Some New Game".into(),
+                publisher: "XYZ Games".into(),
+                year: "1999".into(),
+                alternate_titles: vec!["SNG".into()],
+                ..Default::default()
+            },
+            readme: "".into(),
+            readme_html: None,
+            image: None,
+            gallery: vec![],
+            owners: vec!["bob".into()],
+            packages: vec![]
+        };
+
+        let cdata = ProjectDataPost {
+            description: data.description.clone(),
+            tags: data.tags.clone(),
+            game: GameData {
+                id: None,
+                title: data.game.title.clone(),
+                title_sort_key: data.game.title_sort_key.clone(),
+                publisher: data.game.publisher.clone(),
+                year: data.game.year.clone(),
+                alternate_titles: data.game.alternate_titles.clone(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
+        core.create_project(user, name, &cdata).await.unwrap();
+        let proj = core.get_project_id(name).await.unwrap();
+        assert_eq!(core.get_project(proj).await.unwrap(), data);
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
+    async fn update_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let name = "test_game";
+        let new_data = ProjectData {
+            name: name.into(),
+            description: "new description".into(),
+            revision: 4,
+            created_at: "2023-11-12T15:50:06.419538067+00:00".into(),
+            modified_at: NOW.into(),
+            tags: vec!["WWII".into(), "solitaire".into()],
+            game: GameData {
+                id: Some(1),
+                title: "Some New Game".into(),
+                title_sort_key: "Some New Game".into(),
+                publisher: "XYZ Games".into(),
+                year: "1999".into(),
+                alternate_titles: vec!["SNG".into()],
+                ..Default::default()
+            },
+            readme: "".into(),
+            readme_html: None,
+            image: None,
+            gallery: vec![],
+            owners: vec!["bob".into()],
+            packages: vec![]
+        };
+
+        let cdata = ProjectDataPatch {
+            description: Some(new_data.description.clone()),
+            tags: Some(new_data.tags.clone()),
+            game: GameDataPatch {
+                title: Some(new_data.game.title.clone()),
+                title_sort_key: Some(new_data.game.title_sort_key.clone()),
+                publisher: Some(new_data.game.publisher.clone()),
+                year: Some(new_data.game.year.clone()),
+                alternate_titles: Some(new_data.game.alternate_titles.clone()),
+                ..Default::default()
+            },
+            readme: Some("".into()),
+            image: None,
+            gallery: None
+        };
+
+        let proj = core.get_project_id(name).await.unwrap();
+        let old_data = core.get_project(proj).await.unwrap();
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+        // project has new data
+        assert_eq!(core.get_project(proj).await.unwrap(), new_data);
+        // old data is kept as a revision
+        assert_eq!(
+            core.get_project_revision(proj, 3).await.unwrap(),
+            old_data
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
+    async fn update_project_invalid_fields(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let proj_data = ProjectDataPatch {
+            description: Some("x".repeat(1025)),
+            ..Default::default()
+        };
+
+        match core.update_project(Owner(1), Project(42), None, &proj_data).await {
+            Err(CoreError::InvalidFields(fields)) => assert_eq!(
+                fields,
+                vec![
+                    FieldError {
+                        field: "description".into(),
+                        reason: "longer than 1024 characters".into()
+                    }
+                ]
+            ),
+            r => panic!("unexpected result {r:?}")
+        }
+
+        // nothing was changed
+        assert_eq!(core.get_current_revision(Project(42)).await.unwrap(), 3);
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "packages"))]
+    async fn get_release_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.get_release(Project(42), Package(1)).await.unwrap(),
+            "https://example.com/a_package-1.2.4"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "packages"))]
+    async fn get_release_version_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        let version = "1.2.3".parse::<Version>().unwrap();
         assert_eq!(
             core.get_release_version(Project(42), Package(1), &version)
                 .await
@@ -1963,6 +3280,142 @@ mod test {
         assert!(!core.user_is_owner(User(2), Project(42)).await.unwrap());
     }
 
+    #[sqlx::test(fixtures("users", "admins"))]
+    async fn user_is_admin_true(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert!(core.user_is_admin(User(3)).await.unwrap());
+    }
+
+    #[sqlx::test(fixtures("users", "admins"))]
+    async fn user_is_admin_false(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert!(!core.user_is_admin(User(1)).await.unwrap());
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn delete_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        core.delete_project(Owner(1), Project(42)).await.unwrap();
+
+        assert_eq!(
+            core.get_project_id("test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+
+        assert_eq!(
+            core.get_deleted_project_id("test_game").await.unwrap(),
+            Project(42)
+        );
+
+        assert_eq!(
+            core.get_projects(ProjectsParams::default())
+                .await
+                .unwrap()
+                .meta
+                .total,
+            1
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        core.rename_project(Owner(1), Project(42), "new_name")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            core.get_project_id("new_name").await.unwrap(),
+            Project(42)
+        );
+
+        assert_eq!(
+            core.get_project_alias("test_game").await.unwrap(),
+            "new_name"
+        );
+
+        assert_eq!(
+            core.get_project(Project(42)).await.unwrap().name,
+            "new_name"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_invalid_name(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.rename_project(Owner(1), Project(42), "-bad")
+                .await
+                .unwrap_err(),
+            CoreError::InvalidProjectName
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_name_in_use(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.rename_project(Owner(1), Project(42), "a_game")
+                .await
+                .unwrap_err(),
+            CoreError::ProjectNameInUse
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn restore_project_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        core.delete_project(Owner(1), Project(42)).await.unwrap();
+        core.restore_project(Owner(1), Project(42)).await.unwrap();
+
+        assert_eq!(
+            core.get_project_id("test_game").await.unwrap(),
+            Project(42)
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn restore_project_after_grace_period(pool: Pool) {
+        let core = make_core(pool.clone(), fake_now, 0);
+        core.delete_project(Owner(1), Project(42)).await.unwrap();
+
+        let core = make_core(pool, fake_after_grace_period, 0);
+        assert_eq!(
+            core.restore_project(Owner(1), Project(42)).await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "packages", "images"))]
+    async fn purge_projects_in_grace_period(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        core.delete_project(Owner(1), Project(42)).await.unwrap();
+        core.purge_projects().await.unwrap();
+
+        assert_eq!(
+            core.get_deleted_project_id("test_game").await.unwrap(),
+            Project(42)
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "packages", "images"))]
+    async fn purge_projects_after_grace_period(pool: Pool) {
+        let core = make_core(pool.clone(), fake_now, 0);
+        core.delete_project(Owner(1), Project(42)).await.unwrap();
+
+        let core = make_core(pool, fake_after_grace_period, 0);
+        core.purge_projects().await.unwrap();
+
+        assert_eq!(
+            core.get_deleted_project_id("test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
     #[sqlx::test(fixtures("users", "projects", "one_owner"))]
     async fn add_owners_ok(pool: Pool) {
         let core = make_core(pool, fake_now, 0);
@@ -2066,4 +3519,353 @@ mod test {
             CoreError::NotFound
         );
     }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn get_images_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.get_images(Project(42)).await.unwrap(),
+            Images {
+                images: vec![
+                    ImageData {
+                        filename: "img.png".into(),
+                        url: "https://example.com/images/img.png".into(),
+                        content_type: "image/png".into(),
+                        size: 1234,
+                        published_at: "2023-09-15T18:56:46.419538067+00:00".into(),
+                        published_by: "bob".into()
+                    }
+                ]
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        core.remove_image(Owner(1), Project(42), "img.png").await.unwrap();
+        assert_eq!(
+            core.get_images(Project(42)).await.unwrap(),
+            Images { images: vec![] }
+        );
+        assert_eq!(
+            core.get_image(Project(42), "img.png").await.unwrap_err(),
+            CoreError::NotFound
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_not_an_image(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.remove_image(Owner(1), Project(42), "bogus")
+                .await
+                .unwrap_err(),
+            CoreError::NotFound
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_ok(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.get_game(Game(1)).await.unwrap(),
+            GameProjects {
+                game: GameData {
+                    id: Some(1),
+                    title: "A Game of Tests".into(),
+                    title_sort_key: "Game of Tests, A".into(),
+                    publisher: "Test Game Company".into(),
+                    year: "1979".into(),
+                    ..Default::default()
+                },
+                projects: vec!["test_game".into()]
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_not_a_game(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+        assert_eq!(
+            core.get_game(Game(0)).await.unwrap_err(),
+            CoreError::NotAGame
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn create_project_existing_game(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPost {
+            description: "".into(),
+            tags: vec![],
+            game: GameData {
+                id: None,
+                title: "A Game of Tests".into(),
+                title_sort_key: "Game of Tests, A".into(),
+                publisher: "Test Game Company".into(),
+                year: "1979".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
+        core.create_project(User(1), "another_test_game", &cdata)
+            .await
+            .unwrap();
+
+        let proj = core.get_project_id("another_test_game").await.unwrap();
+        assert_eq!(core.get_project(proj).await.unwrap().game.id, Some(1));
+        assert_eq!(
+            core.get_game(Game(1)).await.unwrap().projects,
+            vec!["another_test_game".to_string(), "test_game".into()]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn create_project_game_id(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPost {
+            description: "".into(),
+            tags: vec![],
+            game: GameData {
+                id: Some(1),
+                title: "Game of Tests".into(),
+                publisher: "TGC".into(),
+                year: "1979".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
+        core.create_project(User(1), "another_test_game", &cdata)
+            .await
+            .unwrap();
+
+        // the fields of the game replace those given
+        let proj = core.get_project_id("another_test_game").await.unwrap();
+        assert_eq!(
+            core.get_project(proj).await.unwrap().game,
+            GameData {
+                id: Some(1),
+                title: "A Game of Tests".into(),
+                title_sort_key: "Game of Tests, A".into(),
+                publisher: "Test Game Company".into(),
+                year: "1979".into(),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_game_derived_title_sort_key(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        // an empty sort key asks for the derived one
+        let gd = GamePatch {
+            title: Some("The Game of Tests".into()),
+            title_sort_key: Some("".into()),
+            ..Default::default()
+        };
+
+        core.update_game(Owner(1), Game(1), &gd).await.unwrap();
+
+        assert_eq!(
+            core.get_game(Game(1)).await.unwrap().game.title_sort_key,
+            "Game of Tests"
+        );
+        // the project sort key was the game's, so it follows the game
+        let game = core.get_project(Project(42)).await.unwrap().game;
+        assert_eq!(game.title, "The Game of Tests");
+        assert_eq!(game.title_sort_key, "Game of Tests");
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "games"))]
+    async fn update_game_keeps_project_override(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title_sort_key: Some("Tests, A Game of".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+
+        let gd = GamePatch {
+            title: Some("The Game of Tests".into()),
+            title_sort_key: Some("".into()),
+            ..Default::default()
+        };
+
+        core.update_game(Owner(1), Game(1), &gd).await.unwrap();
+
+        let game = core.get_project(Project(42)).await.unwrap().game;
+        assert_eq!(game.title, "The Game of Tests");
+        assert_eq!(game.title_sort_key, "Tests, A Game of");
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_game_invalid_fields(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let gd = GamePatch {
+            title: Some("".into()),
+            ..Default::default()
+        };
+
+        assert!(
+            matches!(
+                core.update_game(Owner(1), Game(1), &gd).await.unwrap_err(),
+                CoreError::InvalidFields(_)
+            )
+        );
+    }
+
+    #[sqlx::test(fixtures("users"))]
+    async fn create_project_derived_title_sort_key(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPost {
+            description: "".into(),
+            tags: vec![],
+            game: GameData {
+                id: None,
+                title: "The Russian Campaign".into(),
+                title_sort_key: "".into(),
+                publisher: "".into(),
+                year: "".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: None,
+            gallery: vec![]
+        };
+
+        core.create_project(User(1), "trc", &cdata).await.unwrap();
+        let proj = core.get_project_id("trc").await.unwrap();
+        assert_eq!(
+            core.get_project(proj).await.unwrap().game.title_sort_key,
+            "Russian Campaign"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
+    async fn update_project_empty_title_sort_key(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title: Some("The 2nd Game of Tests".into()),
+                title_sort_key: Some("".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+        assert_eq!(
+            core.get_project(Project(42)).await.unwrap().game.title_sort_key,
+            "00000002nd Game of Tests"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
+    async fn update_project_title_keeps_override(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title: Some("The Game of Tests".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+        assert_eq!(
+            core.get_project(Project(42)).await.unwrap().game.title_sort_key,
+            "Game of Tests, A"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner"))]
+    async fn update_project_title_rederives(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title_sort_key: Some("".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title: Some("Das Testspiel".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+        assert_eq!(
+            core.get_project(Project(42)).await.unwrap().game.title_sort_key,
+            "Testspiel"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "games"))]
+    async fn update_project_title_sort_key_override_shared_game(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title_sort_key: Some("Tests, A Game of".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+
+        // the override is the project's, not the game's
+        let game = core.get_project(Project(42)).await.unwrap().game;
+        assert_eq!(game.id, Some(1));
+        assert_eq!(game.title_sort_key, "Tests, A Game of");
+        assert_eq!(
+            core.get_game(Game(1)).await.unwrap().game.title_sort_key,
+            "Game of Tests, A"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "games"))]
+    async fn update_project_empty_title_sort_key_shared_game(pool: Pool) {
+        let core = make_core(pool, fake_now, 0);
+
+        let cdata = ProjectDataPatch {
+            game: GameDataPatch {
+                title_sort_key: Some("".into()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        core.update_project(Owner(1), Project(42), None, &cdata).await.unwrap();
+
+        let game = core.get_project(Project(42)).await.unwrap().game;
+        assert_eq!(game.id, Some(1));
+        assert_eq!(game.title_sort_key, "Game of Tests");
+    }
 }
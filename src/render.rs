@@ -0,0 +1,134 @@
+use ammonia::Builder;
+use pulldown_cmark::{html, Options, Parser};
+use std::borrow::Cow;
+
+fn is_relative(url: &str) -> bool {
+    // a URL is relative if it has no scheme and no leading slash
+    !url.is_empty() &&
+    !url.starts_with(['/', '#', '?']) &&
+    !url.split_once(':').is_some_and(|(scheme, _)|
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic()) &&
+        scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    )
+}
+
+fn image_url<'a>(url: &'a str, images_base: &str) -> Cow<'a, str> {
+    match is_relative(url) {
+        true => Cow::Owned(
+            format!("{images_base}{}", url.trim_start_matches("./"))
+        ),
+        false => Cow::Borrowed(url)
+    }
+}
+
+pub fn render_readme(readme: &str, images_base: &str) -> String {
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, Parser::new_ext(readme, Options::empty()));
+
+    let images_base = images_base.to_owned();
+
+    Builder::default()
+        .attribute_filter(move |element, attribute, value|
+            match (element, attribute) {
+                ("img", "src") => Some(image_url(value, &images_base)),
+                _ => Some(Cow::Borrowed(value))
+            }
+        )
+        .clean(&unsafe_html)
+        .to_string()
+}
+
+// Highlighted text is plain text with its matches delimited by the given
+// markers; it is escaped and the matches are wrapped in <mark> elements
+pub fn render_highlight(text: &str, start: &str, end: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c)
+        }
+    }
+    out.replace(start, "<mark>").replace(end, "</mark>")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BASE: &str = "/api/v1/projects/a_project/images/";
+
+    #[test]
+    fn is_relative_ok() {
+        assert!(is_relative("map.png"));
+        assert!(is_relative("./map.png"));
+        assert!(is_relative("maps/map.png"));
+        assert!(!is_relative("/map.png"));
+        assert!(!is_relative("https://example.com/map.png"));
+        assert!(!is_relative("data:image/png;base64,AAAA"));
+        assert!(!is_relative("#top"));
+        assert!(!is_relative(""));
+    }
+
+    #[test]
+    fn render_readme_markdown() {
+        assert_eq!(
+            render_readme("# Title\n\nSome *text*.\n", BASE),
+            "<h1>Title</h1>\n<p>Some <em>text</em>.</p>\n"
+        );
+    }
+
+    #[test]
+    fn render_readme_relative_image() {
+        assert_eq!(
+            render_readme("![map](map.png)", BASE),
+            "<p><img src=\"/api/v1/projects/a_project/images/map.png\" alt=\"map\"></p>\n"
+        );
+    }
+
+    #[test]
+    fn render_readme_relative_html_image() {
+        assert_eq!(
+            render_readme("<img src=\"./map.png\">", BASE),
+            "<img src=\"/api/v1/projects/a_project/images/map.png\">"
+        );
+    }
+
+    #[test]
+    fn render_readme_absolute_image() {
+        assert_eq!(
+            render_readme("![map](https://example.com/map.png)", BASE),
+            "<p><img src=\"https://example.com/map.png\" alt=\"map\"></p>\n"
+        );
+    }
+
+    #[test]
+    fn render_readme_sanitized() {
+        assert_eq!(
+            render_readme(
+                "<script>alert(1)</script>\n\n[x](javascript:alert(1))",
+                BASE
+            ),
+            "\n<p><a rel=\"noopener noreferrer\">x</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn render_highlight_marks() {
+        assert_eq!(
+            render_highlight("The [Russian] Campaign", "[", "]"),
+            "The <mark>Russian</mark> Campaign"
+        );
+    }
+
+    #[test]
+    fn render_highlight_escaped() {
+        assert_eq!(
+            render_highlight("<b>Tom & Jerry's</b> [war]", "[", "]"),
+            "&lt;b&gt;Tom &amp; Jerry&#39;s&lt;/b&gt; <mark>war</mark>"
+        );
+    }
+}
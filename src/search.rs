@@ -0,0 +1,277 @@
+use serde::Deserialize;
+
+#[derive(Debug, thiserror::Error, Eq, PartialEq)]
+pub enum QueryError {
+    #[error("query is empty")]
+    Empty,
+    #[error("phrase is unterminated")]
+    UnterminatedPhrase,
+    #[error("field {0} is unknown")]
+    UnknownField(String),
+    #[error("field {0} has no term")]
+    MissingTerm(String)
+}
+
+// Fields which may scope a term, and the FTS columns they search; titles
+// include alternate titles
+const FIELDS: &[(&str, &str)] = &[
+    ("title", "{game_title game_alternate_titles}"),
+    ("publisher", "game_publisher"),
+    ("year", "game_year"),
+    ("description", "description"),
+    ("readme", "readme"),
+    ("designer", "game_designers"),
+    ("era", "game_era"),
+    ("package", "package_names"),
+    ("filename", "release_filenames"),
+    ("owner", "owner_usernames")
+];
+
+#[derive(Debug, Eq, PartialEq)]
+struct Term<'a> {
+    column: Option<&'static str>,
+    text: &'a str,
+    phrase: bool
+}
+
+fn column(field: &str) -> Result<&'static str, QueryError> {
+    FIELDS.iter()
+        .find(|(f, _)| f.eq_ignore_ascii_case(field))
+        .map(|(_, c)| *c)
+        .ok_or_else(|| QueryError::UnknownField(field.into()))
+}
+
+fn next_term<'a>(
+    s: &'a str
+) -> Result<Option<(Term<'a>, &'a str)>, QueryError>
+{
+    let s = s.trim_start();
+    if s.is_empty() {
+        return Ok(None);
+    }
+
+    // a field is a run of letters followed by a colon
+    let (column, s) = match s.find(|c: char| !c.is_ascii_alphabetic()) {
+        Some(i) if i > 0 && s[i..].starts_with(':') => {
+            let field = &s[..i];
+            let rest = &s[i + 1..];
+            if rest.is_empty() || rest.starts_with(char::is_whitespace) {
+                return Err(QueryError::MissingTerm(field.into()));
+            }
+            (Some(column(field)?), rest)
+        },
+        _ => (None, s)
+    };
+
+    match s.strip_prefix('"') {
+        Some(s) => {
+            let end = s.find('"').ok_or(QueryError::UnterminatedPhrase)?;
+            Ok(Some((
+                Term { column, text: &s[..end], phrase: true },
+                &s[end + 1..]
+            )))
+        },
+        None => {
+            let end = s.find(char::is_whitespace).unwrap_or(s.len());
+            Ok(Some((
+                Term { column, text: &s[..end], phrase: false },
+                &s[end..]
+            )))
+        }
+    }
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+// Converts user input into an FTS5 expression. Terms are ANDed together;
+// double quotes delimit phrases; a field name and a colon before a term
+// restricts it to that field; the last term, unless it is a phrase,
+// matches as a prefix. Every term is quoted, so FTS5 operators and
+// punctuation in the input are only text.
+pub fn parse_query(q: &str) -> Result<String, QueryError> {
+    let mut terms = vec![];
+    let mut rest = q;
+    while let Some((term, r)) = next_term(rest)? {
+        terms.push(term);
+        rest = r;
+    }
+
+    let last = terms.len().checked_sub(1).ok_or(QueryError::Empty)?;
+
+    Ok(
+        terms.iter()
+            .enumerate()
+            .map(|(i, t)| {
+                let mut expr = match t.column {
+                    Some(column) => format!("{column} : {}", quote(t.text)),
+                    None => quote(t.text)
+                };
+                if i == last && !t.phrase {
+                    expr.push('*');
+                }
+                expr
+            })
+            .collect::<Vec<_>>()
+            .join(" AND ")
+    )
+}
+
+// Weights of the FTS columns when ranking search results by relevance
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct SearchWeights {
+    pub game_title: f64,
+    pub game_publisher: f64,
+    pub game_year: f64,
+    pub description: f64,
+    pub readme: f64,
+    pub game_designers: f64,
+    pub game_era: f64,
+    pub package_names: f64,
+    pub release_filenames: f64,
+    pub owner_usernames: f64,
+    pub game_alternate_titles: f64
+}
+
+impl Default for SearchWeights {
+    fn default() -> Self {
+        SearchWeights {
+            game_title: 100.0,
+            game_publisher: 1.0,
+            game_year: 1.0,
+            description: 1.0,
+            readme: 1.0,
+            game_designers: 1.0,
+            game_era: 1.0,
+            package_names: 1.0,
+            release_filenames: 1.0,
+            owner_usernames: 1.0,
+            game_alternate_titles: 50.0
+        }
+    }
+}
+
+impl SearchWeights {
+    // the weights must be in the order of the columns of projects_fts
+    pub fn rank(&self) -> String {
+        format!(
+            "bm25({:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?}, {:?})",
+            self.game_title,
+            self.game_publisher,
+            self.game_year,
+            self.description,
+            self.readme,
+            self.game_designers,
+            self.game_era,
+            self.package_names,
+            self.release_filenames,
+            self.owner_usernames,
+            self.game_alternate_titles
+        )
+    }
+}
+
+// Fields offered as search suggestions
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SuggestionField {
+    ProjectName,
+    GameTitle,
+    GamePublisher
+}
+
+// Converts partial user input into an FTS5 expression for suggestions;
+// the words are ANDed together and the last one matches as a prefix
+pub fn prefix_query(q: &str) -> Option<String> {
+    let words = q.split_whitespace().map(quote).collect::<Vec<_>>();
+    match words.is_empty() {
+        true => None,
+        false => Some(format!("{}*", words.join(" AND ")))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn search_weights_rank() {
+        assert_eq!(
+            SearchWeights::default().rank(),
+            "bm25(100.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 50.0)"
+        );
+    }
+
+    #[test]
+    fn prefix_query_ok() {
+        assert_eq!(prefix_query("empi").unwrap(), "\"empi\"*");
+        assert_eq!(
+            prefix_query(" third  \"rei ").unwrap(),
+            "\"third\" AND \"\"\"rei\"*"
+        );
+        assert_eq!(prefix_query("  "), None);
+    }
+
+    #[test]
+    fn parse_query_terms() {
+        assert_eq!(
+            parse_query("russian campaign").unwrap(),
+            "\"russian\" AND \"campaign\"*"
+        );
+    }
+
+    #[test]
+    fn parse_query_phrase() {
+        assert_eq!(
+            parse_query("\"third reich\" avalon").unwrap(),
+            "\"third reich\" AND \"avalon\"*"
+        );
+        assert_eq!(
+            parse_query("avalon \"third reich\"").unwrap(),
+            "\"avalon\" AND \"third reich\""
+        );
+    }
+
+    #[test]
+    fn parse_query_field() {
+        assert_eq!(
+            parse_query("publisher:gmt Title:\"paths of glory\"").unwrap(),
+            "game_publisher : \"gmt\" AND {game_title game_alternate_titles} : \"paths of glory\""
+        );
+    }
+
+    #[test]
+    fn parse_query_related_fields() {
+        assert_eq!(
+            parse_query("filename:eia_v3.vmod owner:bob").unwrap(),
+            "release_filenames : \"eia_v3.vmod\" AND owner_usernames : \"bob\"*"
+        );
+    }
+
+    #[test]
+    fn parse_query_operators_are_text() {
+        assert_eq!(
+            parse_query("NOT - AND a\"b").unwrap(),
+            "\"NOT\" AND \"-\" AND \"AND\" AND \"a\"\"b\"*"
+        );
+        assert_eq!(parse_query("12:30").unwrap(), "\"12:30\"*");
+    }
+
+    #[test]
+    fn parse_query_errors() {
+        assert_eq!(parse_query("  ").unwrap_err(), QueryError::Empty);
+        assert_eq!(
+            parse_query("\"unterminated").unwrap_err(),
+            QueryError::UnterminatedPhrase
+        );
+        assert_eq!(
+            parse_query("col:x").unwrap_err(),
+            QueryError::UnknownField("col".into())
+        );
+        assert_eq!(
+            parse_query("publisher: gmt").unwrap_err(),
+            QueryError::MissingTerm("publisher".into())
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+// Leading articles in English, French, German, Spanish, Italian, and Dutch;
+// elided articles end with an apostrophe and need no following space.
+// Words which are often part of a name rather than an article, such as
+// "El" in "El Alamein" or "De" in "De Bellis Antiquitatis", are left out.
+const ARTICLES: &[&str] = &[
+    "a", "an", "the",
+    "le", "la", "les", "l'", "un", "une", "des",
+    "der", "das", "ein", "eine",
+    "una",
+    "il", "gli",
+    "het", "een"
+];
+
+// Numbers are padded to this many digits so that they sort numerically
+const NUMBER_WIDTH: usize = 8;
+
+fn strip_article(title: &str) -> &str {
+    for article in ARTICLES {
+        if !title.get(..article.len())
+            .is_some_and(|head| head.eq_ignore_ascii_case(article))
+        {
+            continue;
+        }
+
+        let rest = &title[article.len()..];
+        let stripped = match article.ends_with('\'') {
+            true => rest.trim_start(),
+            false if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            false => continue
+        };
+
+        // a title which is only an article keeps it
+        if !stripped.is_empty() {
+            return stripped;
+        }
+    }
+    title
+}
+
+fn fold_char(c: char, out: &mut String) {
+    // letters which do not decompose into a base letter and a mark
+    match c {
+        'ß' => out.push_str("ss"),
+        'æ' => out.push_str("ae"),
+        'Æ' => out.push_str("AE"),
+        'œ' => out.push_str("oe"),
+        'Œ' => out.push_str("OE"),
+        'ø' => out.push('o'),
+        'Ø' => out.push('O'),
+        'ł' => out.push('l'),
+        'Ł' => out.push('L'),
+        'đ' => out.push('d'),
+        'Đ' => out.push('D'),
+        'þ' => out.push_str("th"),
+        'Þ' => out.push_str("TH"),
+        _ => out.push(c)
+    }
+}
+
+fn fold_diacritics(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .for_each(|c| fold_char(c, &mut out));
+    out
+}
+
+fn pad_numbers(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut digits = String::new();
+
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+        }
+        else {
+            push_number(&mut out, &mut digits);
+            out.push(c);
+        }
+    }
+
+    push_number(&mut out, &mut digits);
+    out
+}
+
+fn push_number(out: &mut String, digits: &mut String) {
+    if !digits.is_empty() {
+        out.push_str(&format!("{:0>width$}", digits, width = NUMBER_WIDTH));
+        digits.clear();
+    }
+}
+
+pub fn title_sort_key(title: &str) -> String {
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    pad_numbers(&fold_diacritics(strip_article(&title)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn title_sort_key_articles() {
+        assert_eq!(title_sort_key("The Russian Campaign"), "Russian Campaign");
+        assert_eq!(title_sort_key("A Game of Tests"), "Game of Tests");
+        assert_eq!(title_sort_key("an Inconvenient Test"), "Inconvenient Test");
+        assert_eq!(title_sort_key("Les Misérables"), "Miserables");
+        assert_eq!(title_sort_key("L'Armée du Nord"), "Armee du Nord");
+        assert_eq!(title_sort_key("Der Weltkrieg"), "Weltkrieg");
+        assert_eq!(title_sort_key("Il Principe"), "Principe");
+        assert_eq!(title_sort_key("Het Spel"), "Spel");
+    }
+
+    #[test]
+    fn title_sort_key_not_articles() {
+        assert_eq!(title_sort_key("Theatre of War"), "Theatre of War");
+        assert_eq!(title_sort_key("Anzio"), "Anzio");
+        assert_eq!(title_sort_key("The"), "The");
+        assert_eq!(title_sort_key("Leningrad"), "Leningrad");
+        assert_eq!(title_sort_key("El Alamein"), "El Alamein");
+        assert_eq!(title_sort_key("De Bellis Antiquitatis"), "De Bellis Antiquitatis");
+        assert_eq!(title_sort_key("Die Hard"), "Die Hard");
+    }
+
+    #[test]
+    fn title_sort_key_diacritics() {
+        assert_eq!(title_sort_key("Kriegsspiel für Anfänger"), "Kriegsspiel fur Anfanger");
+        assert_eq!(title_sort_key("Große Schlacht"), "Grosse Schlacht");
+        assert_eq!(title_sort_key("Søren's Œuvre"), "Soren's OEuvre");
+    }
+
+    #[test]
+    fn title_sort_key_numbers() {
+        assert_eq!(title_sort_key("Squad Leader 2"), "Squad Leader 00000002");
+        assert_eq!(title_sort_key("1914"), "00001914");
+        assert_eq!(title_sort_key("The 30 Years War"), "00000030 Years War");
+    }
+
+    #[test]
+    fn title_sort_key_whitespace() {
+        assert_eq!(title_sort_key("  The   Big  Game "), "Big Game");
+    }
+}
@@ -1,28 +1,69 @@
 use axum::async_trait;
 use sqlx::{
     Database, Executor,
+    error::ErrorKind,
     sqlite::Sqlite
 };
 
+mod gallery;
+mod games;
 mod images;
 mod packages;
 mod players;
 mod project;
 mod projects;
+mod purge;
 mod releases;
+mod revisions;
+mod search_index;
+mod search_suggestions;
 mod users;
 
 use crate::{
     core::CoreError,
-    db::{DatabaseClient, FileRow, PackageRow, ProjectRow, ProjectSummaryRow},
-    model::{Owner, Package, PackageDataPost, Project, ProjectDataPatch, ProjectDataPost, User, Users},
-    pagination::{Direction, SortBy},
+    db::{DatabaseClient, FileRow, GameRow, HighlightRow, ImageFileRow, ImageRow, PackageRow, ProjectRow, ProjectSummaryRow, RevisionSummaryRow, SearchIndexMismatchRow},
+    model::{GalleryItem, Game, GamePatch, Owner, Package, PackageDataPost, Project, ProjectDataPatch, ProjectDataPost, User, Users},
+    pagination::{Direction, Facet, Filters, SortBy},
+    search::{SearchWeights, SuggestionField},
     time::rfc3339_to_nanos,
     version::Version
 };
 
 pub type Pool = sqlx::Pool<Sqlite>;
 
+// Constraint violations are caused by bad requests, so they are reported
+// as such rather than as database errors with the offending SQL
+impl From<sqlx::Error> for CoreError {
+    fn from(err: sqlx::Error) -> Self {
+        match &err {
+            sqlx::Error::Database(e) => match e.kind() {
+                ErrorKind::UniqueViolation => unique_violation(e.message()),
+                ErrorKind::ForeignKeyViolation => CoreError::InvalidReference,
+                _ => CoreError::DatabaseError(err)
+            },
+            _ => CoreError::DatabaseError(err)
+        }
+    }
+}
+
+// SQLite names the violated constraint only in the message, by the table
+// and columns of the index, e.g., "UNIQUE constraint failed:
+// packages.project_id, packages.name"; violations of constraints which
+// requests cannot reach are plain conflicts
+fn unique_violation(msg: &str) -> CoreError {
+    let table = msg.strip_prefix("UNIQUE constraint failed: ")
+        .and_then(|cols| cols.split_once('.'))
+        .map(|(table, _)| table);
+
+    match table {
+        Some("games") => CoreError::GameExists,
+        Some("packages") => CoreError::PackageExists,
+        Some("projects") => CoreError::ProjectNameInUse,
+        Some("releases") => CoreError::VersionExists,
+        _ => CoreError::Conflict
+    }
+}
+
 #[derive(Clone)]
 pub struct SqlxDatabaseClient<DB: Database>(pub sqlx::Pool<DB>);
 
@@ -36,19 +77,84 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         project::get_project_id(&self.0, projname).await
     }
 
+    async fn get_deleted_project_id(
+        &self,
+        projname: &str
+    ) -> Result<Project, CoreError>
+    {
+        project::get_deleted_project_id(&self.0, projname).await
+    }
+
+    async fn get_project_alias(
+        &self,
+        projname: &str
+    ) -> Result<String, CoreError>
+    {
+        project::get_project_alias(&self.0, projname).await
+    }
+
     async fn get_projects_count(
         &self,
+        filters: &Filters
     ) -> Result<i64, CoreError>
     {
-        projects::get_projects_count(&self.0).await
+        projects::get_projects_count(&self.0, filters).await
     }
 
     async fn get_projects_query_count(
         &self,
-        query: &str
+        query: &str,
+        filters: &Filters
     ) -> Result<i64, CoreError>
     {
-        projects::get_projects_query_count(&self.0, query).await
+        projects::get_projects_query_count(&self.0, query, filters).await
+    }
+
+    async fn get_projects_highlights(
+        &self,
+        query: &str,
+        names: &[String]
+    ) -> Result<Vec<HighlightRow>, CoreError>
+    {
+        projects::get_projects_highlights(&self.0, query, names).await
+    }
+
+    async fn get_search_suggestions(
+        &self,
+        field: SuggestionField,
+        query: &str,
+        limit: u32
+    ) -> Result<Vec<String>, CoreError>
+    {
+        search_suggestions::get_search_suggestions(&self.0, field, query, limit)
+            .await
+    }
+
+    async fn set_search_weights(
+        &self,
+        weights: &SearchWeights
+    ) -> Result<(), CoreError>
+    {
+        search_index::set_search_rank(&self.0, &weights.rank()).await
+    }
+
+    async fn reindex_search(
+        &self
+    ) -> Result<(Vec<SearchIndexMismatchRow>, Vec<SearchIndexMismatchRow>), CoreError>
+    {
+        search_index::reindex_search(&self.0).await
+    }
+
+    async fn get_projects_facet(
+        &self,
+        facet: Facet,
+        query: Option<&str>,
+        filters: &Filters,
+        limit: u32
+    ) -> Result<Vec<(String, i64)>, CoreError>
+    {
+        projects::get_projects_facet(&self.0, facet, query, filters, limit)
+            .await
     }
 
     async fn get_user_id(
@@ -76,6 +182,14 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         users::user_is_owner(&self.0, user, proj).await
     }
 
+    async fn user_is_admin(
+        &self,
+        user: User
+    ) -> Result<bool, CoreError>
+    {
+        users::user_is_admin(&self.0, user).await
+    }
+
     async fn add_owner(
         &self,
         user: User,
@@ -124,10 +238,17 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         &self,
         sort_by: SortBy,
         dir: Direction,
+        filters: &Filters,
         limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>
     {
-        projects::get_projects_end_window(&self.0, sort_by, dir, limit).await
+        projects::get_projects_end_window(
+            &self.0,
+            sort_by,
+            dir,
+            filters,
+            limit
+        ).await
     }
 
     async fn get_projects_query_end_window(
@@ -135,10 +256,18 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         query: &str,
         sort_by: SortBy,
         dir: Direction,
+        filters: &Filters,
         limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>
     {
-        projects::get_projects_query_end_window(&self.0, query, sort_by, dir, limit).await
+        projects::get_projects_query_end_window(
+            &self.0,
+            query,
+            sort_by,
+            dir,
+            filters,
+            limit
+        ).await
     }
 
     async fn get_projects_mid_window(
@@ -147,6 +276,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         dir: Direction,
         field: &str,
         id: u32,
+        filters: &Filters,
         limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>
     {
@@ -158,6 +288,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
                 dir,
                 &rfc3339_to_nanos(field)?,
                 id,
+                filters,
                 limit
             ).await,
             _ => projects::get_projects_mid_window(
@@ -166,6 +297,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
                 dir,
                 &field,
                 id,
+                filters,
                 limit
             ).await
         }
@@ -178,6 +310,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         dir: Direction,
         field: &str,
         id: u32,
+        filters: &Filters,
         limit: u32
     ) -> Result<Vec<ProjectSummaryRow>, CoreError>
     {
@@ -190,6 +323,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
                 dir,
                 &rfc3339_to_nanos(field)?,
                 id,
+                filters,
                 limit
             ).await,
             SortBy::Relevance => projects::get_projects_query_mid_window(
@@ -199,6 +333,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
                 dir,
                 &field.parse::<f64>().map_err(|_| CoreError::MalformedQuery)?,
                 id,
+                filters,
                 limit
             ).await,
             _ => projects::get_projects_query_mid_window(
@@ -208,6 +343,7 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
                 dir,
                 &field,
                 id,
+                filters,
                 limit
             ).await
         }
@@ -228,11 +364,62 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         &self,
         owner: Owner,
         proj: Project,
+        expected: Option<i64>,
         proj_data: &ProjectDataPatch,
         now: i64
     ) -> Result<(), CoreError>
     {
-        project::update_project(&self.0, owner, proj, proj_data, now).await
+        project::update_project(&self.0, owner, proj, expected, proj_data, now)
+            .await
+    }
+
+    async fn rename_project(
+        &self,
+        owner: Owner,
+        proj: Project,
+        name: &str,
+        now: i64
+    ) -> Result<(), CoreError>
+    {
+        project::rename_project(&self.0, owner, proj, name, now).await
+    }
+
+    async fn revert_project(
+        &self,
+        owner: Owner,
+        proj: Project,
+        revision: i64,
+        now: i64
+    ) -> Result<(), CoreError>
+    {
+        project::revert_project(&self.0, owner, proj, revision, now).await
+    }
+
+    async fn delete_project(
+        &self,
+        owner: Owner,
+        proj: Project,
+        now: i64
+    ) -> Result<(), CoreError>
+    {
+        project::delete_project(&self.0, owner, proj, now).await
+    }
+
+    async fn restore_project(
+        &self,
+        proj: Project,
+        after: i64
+    ) -> Result<(), CoreError>
+    {
+        project::restore_project(&self.0, proj, after).await
+    }
+
+    async fn purge_projects(
+        &self,
+        before: i64
+    ) -> Result<Vec<String>, CoreError>
+    {
+        purge::purge_projects(&self.0, before).await
     }
 
     async fn get_project_row(
@@ -252,6 +439,15 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         project::get_project_row_revision(&self.0, proj, revision).await
     }
 
+    async fn get_gallery(
+        &self,
+        proj: Project,
+        revision: i64
+    ) -> Result<Vec<GalleryItem>, CoreError>
+    {
+        gallery::get_gallery(&self.0, proj, revision).await
+    }
+
     async fn get_packages(
         &self,
         proj: Project
@@ -273,12 +469,21 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         &self,
         owner: Owner,
         proj: Project,
+        expected: Option<i64>,
         pkg: &str,
         pkg_data: &PackageDataPost,
         now: i64
     ) -> Result<(), CoreError>
     {
-        packages::create_package(&self.0, owner, proj, pkg, pkg_data, now).await
+        packages::create_package(
+            &self.0,
+            owner,
+            proj,
+            expected,
+            pkg,
+            pkg_data,
+            now
+        ).await
     }
 
     async fn get_releases(
@@ -412,16 +617,82 @@ impl DatabaseClient for SqlxDatabaseClient<Sqlite> {
         images::get_image_url_at(&self.0, proj, img_name, date).await
     }
 
+    async fn get_images(
+        &self,
+        proj: Project
+    ) -> Result<Vec<ImageRow>, CoreError>
+    {
+        images::get_images(&self.0, proj).await
+    }
+
     async fn add_image_url(
+        &self,
+        owner: Owner,
+        proj: Project,
+        expected: Option<i64>,
+        img: &ImageFileRow<'_>,
+        now: i64
+    ) -> Result<(), CoreError>
+    {
+        images::add_image_url(&self.0, owner, proj, expected, img, now).await
+    }
+
+    async fn remove_image(
         &self,
         owner: Owner,
         proj: Project,
         img_name: &str,
-        url: &str,
         now: i64
     ) -> Result<(), CoreError>
     {
-        images::add_image_url(&self.0, owner, proj, img_name, url, now).await
+        images::remove_image(&self.0, owner, proj, img_name, now).await
+    }
+
+    async fn get_revisions_count(
+        &self,
+        proj: Project
+    ) -> Result<i64, CoreError>
+    {
+        revisions::get_revisions_count(&self.0, proj).await
+    }
+
+    async fn get_revisions_window(
+        &self,
+        proj: Project,
+        dir: Direction,
+        revision: i64,
+        limit: u32
+    ) -> Result<Vec<RevisionSummaryRow>, CoreError>
+    {
+        revisions::get_revisions_window(&self.0, proj, dir, revision, limit)
+            .await
+    }
+
+    async fn get_game_row(
+        &self,
+        game: Game
+    ) -> Result<GameRow, CoreError>
+    {
+        games::get_game_row(&self.0, game).await
+    }
+
+    async fn get_game_projects(
+        &self,
+        game: Game
+    ) -> Result<Vec<String>, CoreError>
+    {
+        games::get_game_projects(&self.0, game).await
+    }
+
+    async fn update_game(
+        &self,
+        owner: Owner,
+        game: Game,
+        game_data: &GamePatch,
+        now: i64
+    ) -> Result<(), CoreError>
+    {
+        games::update_game(&self.0, owner, game, game_data, now).await
     }
 }
 
@@ -456,6 +727,27 @@ ORDER BY users.username
 mod test {
     use super::*;
 
+    #[test]
+    fn unique_violation_known() {
+        assert_eq!(
+            unique_violation("UNIQUE constraint failed: packages.project_id, packages.name"),
+            CoreError::PackageExists
+        );
+        assert_eq!(
+            unique_violation("UNIQUE constraint failed: projects.normalized_name"),
+            CoreError::ProjectNameInUse
+        );
+    }
+
+    #[test]
+    fn unique_violation_other() {
+        assert_eq!(
+            unique_violation("UNIQUE constraint failed: project_revisions.project_id, project_revisions.revision"),
+            CoreError::Conflict
+        );
+        assert_eq!(unique_violation("bogus"), CoreError::Conflict);
+    }
+
     #[sqlx::test(fixtures("users", "projects", "packages", "authors"))]
     async fn get_authors_ok(pool: Pool) {
         assert_eq!(
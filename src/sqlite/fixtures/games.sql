@@ -0,0 +1,25 @@
+INSERT INTO games (
+  game_id,
+  title,
+  title_sort,
+  publisher,
+  year
+)
+VALUES
+  (
+    1,
+    "A Game of Tests",
+    "Game of Tests, A",
+    "Test Game Company",
+    "1979"
+  ),
+  (
+    2,
+    "Some Other Game",
+    "Some Other Game",
+    "XYZ",
+    "1993"
+  );
+
+UPDATE projects SET game_id = 1 WHERE project_id = 42;
+UPDATE projects SET game_id = 2 WHERE project_id = 6;
@@ -2,6 +2,8 @@ INSERT INTO images (
   project_id,
   filename,
   url,
+  content_type,
+  size,
   published_at,
   published_by
 )
@@ -10,6 +12,8 @@ VALUES
     42,
     "img.png",
     "https://example.com/images/img.png",
+    "image/png",
+    1234,
     1694804206419538067,
     1
   );
@@ -18,6 +22,8 @@ INSERT INTO image_revisions (
   project_id,
   filename,
   url,
+  content_type,
+  size,
   published_at,
   published_by
 )
@@ -26,6 +32,8 @@ VALUES
     42,
     "img.png",
     "https://example.com/images/img.png",
+    "image/png",
+    1234,
     1694804206419538067,
     1
   );
@@ -0,0 +1,58 @@
+UPDATE projects SET
+  game_publisher = "GMT",
+  game_year = "1979",
+  tags = '["WWII", "hex and counter"]'
+WHERE project_id = 1;
+
+UPDATE projects SET
+  game_publisher = "Avalon Hill",
+  game_year = "1985",
+  tags = '["wwii"]'
+WHERE project_id = 2;
+
+UPDATE projects SET
+  game_publisher = "gmt",
+  game_year = "c. 1990"
+WHERE project_id = 3;
+
+UPDATE projects SET
+  game_publisher = "GMT",
+  game_year = "2001",
+  tags = '["card driven"]'
+WHERE project_id = 4;
+
+INSERT INTO owners (user_id, project_id)
+VALUES
+  (1, 1),
+  (2, 2),
+  (1, 3);
+
+INSERT INTO packages (
+  package_id,
+  project_id,
+  name,
+  created_at,
+  created_by
+)
+VALUES
+  (1, 1, "a_package", 0, 1),
+  (2, 2, "b_package", 0, 1);
+
+INSERT INTO releases (
+  release_id,
+  package_id,
+  version,
+  version_major,
+  version_minor,
+  version_patch,
+  version_pre,
+  version_build,
+  url,
+  filename,
+  size,
+  checksum,
+  published_at,
+  published_by
+)
+VALUES
+  (1, 1, "1.0.0", 1, 0, 0, "", "", "https://example.com/a_package-1.0.0", "a_package-1.0.0", 1, "", 0, 1);
@@ -0,0 +1,169 @@
+INSERT INTO projects (
+  project_id,
+  name,
+  normalized_name,
+  created_at,
+  description,
+  game_title,
+  game_title_sort,
+  game_publisher,
+  game_year,
+  readme,
+  image,
+  modified_at,
+  modified_by,
+  revision
+)
+VALUES
+  (
+    42,
+    "test_game",
+    "test_game",
+    1702651000000000000,
+    "Brian's Trademarked Game of Being a Test Case",
+    "A Game of Tests",
+    "Game of Tests, A",
+    "Test Game Company",
+    "1979",
+    "",
+    NULL,
+    1702656000000000000,
+    1,
+    6
+  );
+
+INSERT INTO project_data (
+  project_data_id,
+  project_id,
+  description,
+  game_title,
+  game_title_sort,
+  game_publisher,
+  game_year,
+  readme,
+  image
+)
+VALUES
+  (
+    1,
+    42,
+    "Brian's Trademarked Game of Being a Test Case",
+    "A Game of Tests",
+    "Game of Tests, A",
+    "Test Game Company",
+    "1978",
+    "",
+    NULL
+  ),
+  (
+    2,
+    42,
+    "Brian's Trademarked Game of Being a Test Case",
+    "A Game of Tests",
+    "Game of Tests, A",
+    "Test Game Company",
+    "1979",
+    "",
+    NULL
+  );
+
+INSERT INTO project_revisions (
+  project_id,
+  name,
+  created_at,
+  modified_at,
+  modified_by,
+  revision,
+  project_data_id
+)
+VALUES
+  (42, "old_game", 1702651000000000000, 1702651000000000000, 1, 1, 1),
+  (42, "old_game", 1702651000000000000, 1702652000000000000, 1, 2, 2),
+  (42, "test_game", 1702651000000000000, 1702653000000000000, 1, 3, 2),
+  (42, "test_game", 1702651000000000000, 1702654000000000000, 1, 4, 2),
+  (42, "test_game", 1702651000000000000, 1702655000000000000, 1, 5, 2),
+  (42, "test_game", 1702651000000000000, 1702656000000000000, 1, 6, 2);
+
+INSERT INTO packages (
+  package_id,
+  project_id,
+  name,
+  created_at,
+  created_by
+)
+VALUES
+  (1, 42, "a_package", 1702654000000000000, 1);
+
+INSERT INTO releases (
+  release_id,
+  package_id,
+  version,
+  version_major,
+  version_minor,
+  version_patch,
+  version_pre,
+  version_build,
+  url,
+  filename,
+  size,
+  checksum,
+  published_at,
+  published_by
+)
+VALUES
+  (
+    1,
+    1,
+    "1.2.3",
+    1,
+    2,
+    3,
+    "",
+    "",
+    "https://example.com/a_package-1.2.3",
+    "a_package-1.2.3",
+    1234,
+    "c0e0fa7373a12b45a91e4f4d4e2e186442fc6ee9b346caa2fdc1c09026a2144a",
+    1702655000000000000,
+    1
+  );
+
+INSERT INTO images (
+  project_id,
+  filename,
+  url,
+  content_type,
+  size,
+  published_at,
+  published_by
+)
+VALUES
+  (
+    42,
+    "img.png",
+    "https://example.com/images/img.png",
+    "image/png",
+    1234,
+    1702656000000000000,
+    1
+  );
+
+INSERT INTO image_revisions (
+  project_id,
+  filename,
+  url,
+  content_type,
+  size,
+  published_at,
+  published_by
+)
+VALUES
+  (
+    42,
+    "img.png",
+    "https://example.com/images/img.png",
+    "image/png",
+    1234,
+    1702656000000000000,
+    1
+  );
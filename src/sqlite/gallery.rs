@@ -0,0 +1,255 @@
+use sqlx::{
+    Executor, Transaction,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    model::{GalleryItem, Project},
+    sqlite::images::check_image_reference
+};
+
+pub async fn get_gallery<'e, E>(
+    ex: E,
+    proj: Project,
+    revision: i64
+) -> Result<Vec<GalleryItem>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_as!(
+            GalleryItem,
+            "
+SELECT
+    gallery_items.filename,
+    gallery_items.caption
+FROM gallery_items
+JOIN project_revisions
+ON gallery_items.project_data_id = project_revisions.project_data_id
+WHERE project_revisions.project_id = ?
+    AND project_revisions.revision = ?
+ORDER BY gallery_items.position ASC
+            ",
+            proj.0,
+            revision
+        )
+        .fetch_all(ex)
+        .await?
+    )
+}
+
+async fn create_gallery_item_row<'e, E>(
+    ex: E,
+    proj: Project,
+    project_data_id: i64,
+    position: i64,
+    item: &GalleryItem
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query!(
+        "
+INSERT INTO gallery_items (
+    project_data_id,
+    position,
+    project_id,
+    filename,
+    caption
+)
+VALUES (?, ?, ?, ?, ?)
+        ",
+        project_data_id,
+        position,
+        proj.0,
+        item.filename,
+        item.caption
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn create_gallery_items(
+    tx: &mut Transaction<'_, Sqlite>,
+    proj: Project,
+    project_data_id: i64,
+    items: &[GalleryItem]
+) -> Result<(), CoreError>
+{
+    for (position, item) in items.iter().enumerate() {
+        // gallery images must be ones which have not been deleted
+        check_image_reference(&mut **tx, proj, &item.filename).await?;
+
+        create_gallery_item_row(
+            &mut **tx,
+            proj,
+            project_data_id,
+            position as i64,
+            item
+        ).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn copy_gallery_items<'e, E>(
+    ex: E,
+    from_project_data_id: i64,
+    to_project_data_id: i64
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query!(
+        "
+INSERT INTO gallery_items (
+    project_data_id,
+    position,
+    project_id,
+    filename,
+    caption
+)
+SELECT
+    ?,
+    position,
+    project_id,
+    filename,
+    caption
+FROM gallery_items
+WHERE project_data_id = ?
+        ",
+        to_project_data_id,
+        from_project_data_id
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        model::{Owner, ProjectDataPatch},
+        sqlite::project::{get_project_row, update_project}
+    };
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    fn gallery() -> Vec<GalleryItem> {
+        vec![
+            GalleryItem {
+                filename: "img.png".into(),
+                caption: "The box".into()
+            }
+        ]
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn get_gallery_empty(pool: Pool) {
+        assert_eq!(
+            get_gallery(&pool, Project(42), 1).await.unwrap(),
+            vec![]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn update_project_gallery_ok(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ProjectDataPatch {
+                gallery: Some(gallery()),
+                ..Default::default()
+            },
+            1703980420641538066
+        ).await.unwrap();
+
+        // new revision has the gallery, old revision does not
+        assert_eq!(
+            get_gallery(&pool, proj, orig_row.revision + 1).await.unwrap(),
+            gallery()
+        );
+
+        assert_eq!(
+            get_gallery(&pool, proj, orig_row.revision).await.unwrap(),
+            vec![]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn update_project_gallery_carried_over(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ProjectDataPatch {
+                gallery: Some(gallery()),
+                ..Default::default()
+            },
+            1703980420641538066
+        ).await.unwrap();
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ProjectDataPatch {
+                description: Some("foo".into()),
+                ..Default::default()
+            },
+            1703980420641538067
+        ).await.unwrap();
+
+        assert_eq!(
+            get_gallery(&pool, proj, orig_row.revision + 2).await.unwrap(),
+            gallery()
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn update_project_gallery_not_an_image(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        assert_eq!(
+            update_project(
+                &pool,
+                Owner(1),
+                proj,
+                None,
+                &ProjectDataPatch {
+                    gallery: Some(vec![
+                        GalleryItem {
+                            filename: "bogus.png".into(),
+                            caption: "".into()
+                        }
+                    ]),
+                    ..Default::default()
+                },
+                1703980420641538066
+            ).await.unwrap_err(),
+            CoreError::InvalidReference
+        );
+
+        assert_eq!(
+            get_project_row(&pool, proj).await.unwrap().revision,
+            orig_row.revision
+        );
+    }
+}
@@ -0,0 +1,409 @@
+use sqlx::{
+    Acquire, Executor, QueryBuilder,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    db::GameRow,
+    model::{Game, GameDataPatch, GamePatch, Owner, Project, ProjectDataPatch},
+    sort_key::title_sort_key,
+    sqlite::project::update_project
+};
+
+pub async fn get_game_row<'e, E>(
+    ex: E,
+    game: Game
+) -> Result<GameRow, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query_as!(
+        GameRow,
+        "
+SELECT
+    game_id,
+    title,
+    title_sort,
+    publisher,
+    year
+FROM games
+WHERE game_id = ?
+LIMIT 1
+        ",
+        game.0
+    )
+    .fetch_optional(ex)
+    .await?
+    .ok_or(CoreError::NotAGame)
+}
+
+pub async fn get_game_projects<'e, E>(
+    ex: E,
+    game: Game
+) -> Result<Vec<String>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_scalar!(
+            "
+SELECT name
+FROM projects
+WHERE game_id = ?
+    AND deleted_at IS NULL
+ORDER BY normalized_name
+            ",
+            game.0
+        )
+        .fetch_all(ex)
+        .await?
+    )
+}
+
+// Games are identified by title, publisher, and year; the sort key of a
+// new game is the one given by the first project for it
+pub async fn get_or_create_game<'e, E>(
+    ex: E,
+    title: &str,
+    title_sort: &str,
+    publisher: &str,
+    year: &str
+) -> Result<GameRow, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // SQLite does not finish an INSERT ... RETURNING until all its rows
+    // are read, so fetch them all to ensure the insert is done
+    sqlx::query_as!(
+        GameRow,
+        "
+INSERT INTO games (
+    title,
+    title_sort,
+    publisher,
+    year
+)
+VALUES (?, ?, ?, ?)
+ON CONFLICT(title, publisher, year) DO UPDATE SET title = title
+RETURNING
+    game_id,
+    title,
+    title_sort,
+    publisher,
+    year
+        ",
+        title,
+        title_sort,
+        publisher,
+        year
+    )
+    .fetch_all(ex)
+    .await?
+    .into_iter()
+    .next()
+    .ok_or(CoreError::InternalError)
+}
+
+// A game id links to that game; otherwise the game is the one with the
+// given title, publisher, and year
+pub async fn find_game<'e, E>(
+    ex: E,
+    id: Option<i64>,
+    title: &str,
+    title_sort: &str,
+    publisher: &str,
+    year: &str
+) -> Result<GameRow, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    match id {
+        Some(id) => get_game_row(ex, Game(id)).await,
+        None => get_or_create_game(ex, title, title_sort, publisher, year).await
+    }
+}
+
+// A project sort key which is not an override by the owner follows the
+// sort key of its game
+fn inherited_sort_key(game: &GameRow, key: &str) -> bool {
+    key == game.title_sort ||
+        key == game.title ||
+        key == title_sort_key(&game.title)
+}
+
+async fn update_game_row<'e, E>(
+    ex: E,
+    game: Game,
+    gd: &GamePatch
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    let mut qb: QueryBuilder<E::Database> = QueryBuilder::new(
+        "UPDATE games SET "
+    );
+
+    let mut qbs = qb.separated(", ");
+
+    if let Some(title) = &gd.title {
+        qbs.push("title = ").push_bind_unseparated(title);
+    }
+
+    if let Some(title_sort) = &gd.title_sort_key {
+        qbs.push("title_sort = ").push_bind_unseparated(title_sort);
+    }
+
+    if let Some(publisher) = &gd.publisher {
+        qbs.push("publisher = ").push_bind_unseparated(publisher);
+    }
+
+    if let Some(year) = &gd.year {
+        qbs.push("year = ").push_bind_unseparated(year);
+    }
+
+    qb
+        .push(" WHERE game_id = ")
+        .push_bind(game.0)
+        .build()
+        .execute(ex)
+        .await?;
+
+    Ok(())
+}
+
+pub async fn update_game<'a, A>(
+    conn: A,
+    owner: Owner,
+    game: Game,
+    gd: &GamePatch,
+    now: i64
+) -> Result<(), CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    // the game must exist
+    let old = get_game_row(&mut *tx, game).await?;
+
+    update_game_row(&mut *tx, game, gd).await?;
+
+    let new = get_game_row(&mut *tx, game).await?;
+
+    // the game is part of the project data, so each project for the game
+    // gets a new revision carrying the new game fields
+    let projs = sqlx::query!(
+        "
+SELECT
+    project_id,
+    game_title_sort
+FROM projects
+WHERE game_id = ?
+    AND deleted_at IS NULL
+ORDER BY project_id
+        ",
+        game.0
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for proj in projs {
+        let pd = ProjectDataPatch {
+            game: GameDataPatch {
+                id: Some(game.0),
+                title_sort_key: inherited_sort_key(&old, &proj.game_title_sort)
+                    .then(|| new.title_sort.clone()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        update_project(
+            &mut *tx,
+            owner,
+            Project(proj.project_id),
+            None,
+            &pd,
+            now
+        ).await?;
+    }
+
+    // deleted projects get no revisions, but their copies of the game
+    // fields are indexed for search again if they are restored
+    sqlx::query!(
+        "
+UPDATE projects
+SET
+    game_title = games.title,
+    game_title_sort = CASE
+        WHEN projects.game_title_sort = ? THEN games.title_sort
+        ELSE projects.game_title_sort
+    END,
+    game_publisher = games.publisher,
+    game_year = games.year
+FROM games
+WHERE projects.game_id = games.game_id
+    AND games.game_id = ?
+    AND projects.deleted_at IS NOT NULL
+        ",
+        old.title_sort,
+        game.0
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_row_ok(pool: Pool) {
+        assert_eq!(
+            get_game_row(&pool, Game(1)).await.unwrap(),
+            GameRow {
+                game_id: 1,
+                title: "A Game of Tests".into(),
+                title_sort: "Game of Tests, A".into(),
+                publisher: "Test Game Company".into(),
+                year: "1979".into()
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_row_not_a_game(pool: Pool) {
+        assert_eq!(
+            get_game_row(&pool, Game(0)).await.unwrap_err(),
+            CoreError::NotAGame
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_projects_ok(pool: Pool) {
+        assert_eq!(
+            get_game_projects(&pool, Game(1)).await.unwrap(),
+            vec!["test_game".to_string()]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_game_projects_none(pool: Pool) {
+        assert_eq!(
+            get_game_projects(&pool, Game(0)).await.unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_or_create_game_existing(pool: Pool) {
+        assert_eq!(
+            get_or_create_game(
+                &pool,
+                "A Game of Tests",
+                "whatever",
+                "Test Game Company",
+                "1979"
+            ).await.unwrap().game_id,
+            1
+        );
+
+        // the sort key of an existing game is unchanged
+        assert_eq!(
+            get_game_row(&pool, Game(1)).await.unwrap().title_sort,
+            "Game of Tests, A"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_or_create_game_new(pool: Pool) {
+        let game = get_or_create_game(
+            &pool,
+            "A Game of Tests",
+            "Game of Tests, A",
+            "Test Game Company",
+            "1980"
+        ).await.unwrap();
+
+        // the new game is visible to other connections
+        assert_ne!(game.game_id, 1);
+        assert_eq!(
+            get_game_row(&pool, Game(game.game_id)).await.unwrap(),
+            game
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_game_ok(pool: Pool) {
+        let gd = GamePatch {
+            publisher: Some("TGC".into()),
+            ..Default::default()
+        };
+
+        update_game(&pool, Owner(1), Game(1), &gd, 1702569006419538068)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_game_row(&pool, Game(1)).await.unwrap().publisher,
+            "TGC"
+        );
+
+        // the project for the game has a new revision with the new fields,
+        // and its copies of them are updated for search
+        let row = crate::sqlite::project::get_project_row(&pool, Project(42))
+            .await
+            .unwrap();
+        assert_eq!(row.revision, 4);
+        assert_eq!(row.game_publisher, "TGC");
+
+        assert_eq!(
+            sqlx::query_scalar::<_, String>(
+                "SELECT game_publisher FROM projects WHERE project_id = 42"
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap(),
+            "TGC"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_game_not_a_game(pool: Pool) {
+        let gd = GamePatch {
+            publisher: Some("TGC".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update_game(&pool, Owner(1), Game(0), &gd, 1702569006419538068)
+                .await
+                .unwrap_err(),
+            CoreError::NotAGame
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_game_existing(pool: Pool) {
+        let gd = GamePatch {
+            title: Some("Some Other Game".into()),
+            publisher: Some("XYZ".into()),
+            year: Some("1993".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update_game(&pool, Owner(1), Game(1), &gd, 1702569006419538068)
+                .await
+                .unwrap_err(),
+            CoreError::GameExists
+        );
+    }
+}
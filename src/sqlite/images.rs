@@ -5,8 +5,12 @@ use sqlx::{
 
 use crate::{
     core::CoreError,
+    db::{ImageFileRow, ImageRow},
     model::{Owner, Project},
-    sqlite::project::update_project_non_project_data
+    sqlite::{
+        gallery::get_gallery,
+        project::{check_revision, get_project_row, update_project_non_project_data}
+    }
 };
 
 pub async fn get_image_url<'e, E>(
@@ -23,6 +27,7 @@ SELECT url
 FROM images
 WHERE project_id = ?
     AND filename = ?
+    AND deleted = 0
 LIMIT 1
         ",
         proj.0,
@@ -33,6 +38,22 @@ LIMIT 1
     .ok_or(CoreError::NotFound)
 }
 
+// Checks an image referred to by project data
+pub async fn check_image_reference<'e, E>(
+    ex: E,
+    proj: Project,
+    img_name: &str
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    match get_image_url(ex, proj, img_name).await {
+        Ok(_) => Ok(()),
+        Err(CoreError::NotFound) => Err(CoreError::InvalidReference),
+        Err(e) => Err(e)
+    }
+}
+
 pub async fn get_image_url_at<'e, E>(
     ex: E,
     proj: Project,
@@ -42,9 +63,9 @@ pub async fn get_image_url_at<'e, E>(
 where
     E: Executor<'e, Database = Sqlite>
 {
-    sqlx::query_scalar!(
+    sqlx::query!(
         "
-SELECT url
+SELECT url, deleted
 FROM image_revisions
 WHERE project_id = ?
     AND filename = ?
@@ -58,15 +79,49 @@ LIMIT 1
     )
     .fetch_optional(ex)
     .await?
+    // a deleted image has no url after its deletion
+    .filter(|r| r.deleted == 0)
+    .map(|r| r.url)
     .ok_or(CoreError::NotFound)
 }
 
+pub async fn get_images<'e, E>(
+    ex: E,
+    proj: Project
+) -> Result<Vec<ImageRow>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_as!(
+            ImageRow,
+            "
+SELECT
+    images.filename,
+    images.url,
+    images.content_type,
+    images.size,
+    images.published_at,
+    users.username AS published_by
+FROM images
+JOIN users
+ON images.published_by = users.user_id
+WHERE images.project_id = ?
+    AND images.deleted = 0
+ORDER BY images.filename COLLATE NOCASE ASC
+            ",
+            proj.0
+        )
+        .fetch_all(ex)
+        .await?
+    )
+}
+
 async fn update_image_row<'e, E>(
     ex: E,
     owner: Owner,
     proj: Project,
-    img_name: &str,
-    url: &str,
+    img: &ImageFileRow<'_>,
     now: i64
 ) -> Result<(), CoreError>
 where
@@ -78,19 +133,26 @@ INSERT INTO images (
     project_id,
     filename,
     url,
+    content_type,
+    size,
     published_at,
     published_by
 )
-VALUES (?, ?, ?, ?, ?)
+VALUES (?, ?, ?, ?, ?, ?, ?)
 ON CONFLICT(project_id, filename)
 DO UPDATE
 SET url = excluded.url,
+    content_type = excluded.content_type,
+    size = excluded.size,
     published_at = excluded.published_at,
-    published_by = excluded.published_by
+    published_by = excluded.published_by,
+    deleted = 0
         ",
         proj.0,
-        img_name,
-        url,
+        img.filename,
+        img.url,
+        img.content_type,
+        img.size,
         now,
         owner.0
     )
@@ -104,8 +166,8 @@ async fn create_image_revision_row<'e, E>(
     ex: E,
     owner: Owner,
     proj: Project,
-    img_name: &str,
-    url: &str,
+    img: &ImageFileRow<'_>,
+    deleted: bool,
     now: i64
 ) -> Result<(), CoreError>
 where
@@ -117,14 +179,20 @@ INSERT INTO image_revisions (
     project_id,
     filename,
     url,
+    content_type,
+    size,
+    deleted,
     published_at,
     published_by
 )
-VALUES (?, ?, ?, ?, ?)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?)
         ",
         proj.0,
-        img_name,
-        url,
+        img.filename,
+        img.url,
+        img.content_type,
+        img.size,
+        deleted,
         now,
         owner.0
     )
@@ -138,34 +206,132 @@ pub async fn add_image_url<'a, A>(
     conn: A,
     owner: Owner,
     proj: Project,
-    img_name: &str,
-    url: &str,
-    now: i64,
+    expected: Option<i64>,
+    img: &ImageFileRow<'_>,
+    now: i64
 ) -> Result<(), CoreError>
 where
     A: Acquire<'a, Database = Sqlite>
 {
     let mut tx = conn.begin().await?;
 
+    check_revision(&mut *tx, proj, expected).await?;
+
     // update row in images
-    update_image_row(
-        &mut *tx,
-        owner,
-        proj,
-        img_name,
-        url,
-        now
-    ).await?;
+    update_image_row(&mut *tx, owner, proj, img, now).await?;
 
     // insert row in images_revisions
-    create_image_revision_row(
-        &mut *tx,
-        owner,
-        proj,
-        img_name,
-        url,
-        now
-    ).await?;
+    create_image_revision_row(&mut *tx, owner, proj, img, false, now).await?;
+
+    // update project to reflect the change
+    update_project_non_project_data(&mut tx, owner, proj, now).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+async fn get_image_row<'e, E>(
+    ex: E,
+    proj: Project,
+    img_name: &str
+) -> Result<ImageRow, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query_as!(
+        ImageRow,
+        "
+SELECT
+    images.filename,
+    images.url,
+    images.content_type,
+    images.size,
+    images.published_at,
+    users.username AS published_by
+FROM images
+JOIN users
+ON images.published_by = users.user_id
+WHERE images.project_id = ?
+    AND images.filename = ?
+    AND images.deleted = 0
+LIMIT 1
+        ",
+        proj.0,
+        img_name
+    )
+    .fetch_optional(ex)
+    .await?
+    .ok_or(CoreError::NotFound)
+}
+
+async fn delete_image_row<'e, E>(
+    ex: E,
+    proj: Project,
+    img_name: &str
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // Image rows are retained because old project data may refer to them
+    sqlx::query!(
+        "
+UPDATE images
+SET deleted = 1
+WHERE project_id = ?
+    AND filename = ?
+        ",
+        proj.0,
+        img_name
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn remove_image<'a, A>(
+    conn: A,
+    owner: Owner,
+    proj: Project,
+    img_name: &str,
+    now: i64
+) -> Result<(), CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    // check that the image exists
+    let row = get_image_row(&mut *tx, proj, img_name).await?;
+
+    // prevent removal of the current project image
+    let proj_row = get_project_row(&mut *tx, proj).await?;
+    if proj_row.image.as_deref() == Some(img_name) {
+        return Err(CoreError::ImageInUse);
+    }
+
+    // prevent removal of images in the current gallery
+    if get_gallery(&mut *tx, proj, proj_row.revision)
+        .await?
+        .iter()
+        .any(|item| item.filename == img_name)
+    {
+        return Err(CoreError::ImageInUse);
+    }
+
+    // mark row in images as deleted
+    delete_image_row(&mut *tx, proj, img_name).await?;
+
+    // insert row in images_revisions
+    let img = ImageFileRow {
+        filename: img_name,
+        url: &row.url,
+        content_type: &row.content_type,
+        size: row.size
+    };
+
+    create_image_revision_row(&mut *tx, owner, proj, &img, true, now).await?;
 
     // update project to reflect the change
     update_project_non_project_data(&mut tx, owner, proj, now).await?;
@@ -179,6 +345,11 @@ where
 mod test {
     use super::*;
 
+    use crate::{
+        model::{GalleryItem, ProjectDataPatch},
+        sqlite::project::update_project
+    };
+
     type Pool = sqlx::Pool<Sqlite>;
 
     #[sqlx::test(fixtures("users", "projects", "images"))]
@@ -245,8 +416,13 @@ mod test {
             &pool,
             Owner(1),
             Project(42),
-            "image.png",
-            "https://example.com/image.png",
+            None,
+            &ImageFileRow {
+                filename: "image.png",
+                url: "https://example.com/image.png",
+                content_type: "image/png",
+                size: 1234
+            },
             1703980420641538067
         ).await.unwrap();
 
@@ -256,6 +432,31 @@ mod test {
         );
     }
 
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn add_image_url_stale_revision(pool: Pool) {
+        assert_eq!(
+            add_image_url(
+                &pool,
+                Owner(1),
+                Project(42),
+                Some(0),
+                &ImageFileRow {
+                    filename: "image.png",
+                    url: "https://example.com/image.png",
+                    content_type: "image/png",
+                    size: 1234
+                },
+                1703980420641538067
+            ).await.unwrap_err(),
+            CoreError::PreconditionFailed
+        );
+
+        assert_eq!(
+            get_image_url(&pool, Project(42), "image.png").await.unwrap_err(),
+            CoreError::NotFound
+        );
+    }
+
     #[sqlx::test(fixtures("users", "projects", "images"))]
     async fn add_image_url_not_a_user(pool: Pool) {
         // This should not happen; the Owner passed in should be good.
@@ -265,11 +466,16 @@ mod test {
                     &pool,
                     Owner(0),
                     Project(42),
-                    "image.png",
-                    "https://example.com/image.png",
+                    None,
+                    &ImageFileRow {
+                        filename: "image.png",
+                        url: "https://example.com/image.png",
+                        content_type: "image/png",
+                        size: 1234
+                    },
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -283,12 +489,209 @@ mod test {
                     &pool,
                     Owner(1),
                     Project(0),
-                    "image.png",
-                    "https://example.com/image.png",
+                    None,
+                    &ImageFileRow {
+                        filename: "image.png",
+                        url: "https://example.com/image.png",
+                        content_type: "image/png",
+                        size: 1234
+                    },
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn get_images_ok(pool: Pool) {
+        assert_eq!(
+            get_images(&pool, Project(42)).await.unwrap(),
+            [
+                ImageRow {
+                    filename: "img.png".into(),
+                    url: "https://example.com/images/img.png".into(),
+                    content_type: "image/png".into(),
+                    size: 1234,
+                    published_at: 1694804206419538067,
+                    published_by: "bob".into()
+                }
+            ]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn get_images_not_a_project(pool: Pool) {
+        // This should not happen; the Project passed in should be good.
+        // However, it's not an error if it does.
+        assert_eq!(
+            get_images(&pool, Project(0)).await.unwrap(),
+            []
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_ok(pool: Pool) {
+        let proj = Project(42);
+        let revision = get_project_row(&pool, proj).await.unwrap().revision;
+
+        remove_image(
+            &pool,
+            Owner(1),
+            proj,
+            "img.png",
+            1703980420641538067
+        ).await.unwrap();
+
+        // the image is gone
+        assert_eq!(
+            get_images(&pool, proj).await.unwrap(),
+            []
+        );
+
+        assert_eq!(
+            get_image_url(&pool, proj, "img.png").await.unwrap_err(),
+            CoreError::NotFound
+        );
+
+        // the image is gone as of the deletion
+        assert_eq!(
+            get_image_url_at(
+                &pool,
+                proj,
+                "img.png",
+                1703980420641538067
+            ).await.unwrap_err(),
+            CoreError::NotFound
+        );
+
+        // the image is still present before the deletion
+        assert_eq!(
+            get_image_url_at(
+                &pool,
+                proj,
+                "img.png",
+                1703980420641538066
+            ).await.unwrap(),
+            "https://example.com/images/img.png"
+        );
+
+        // the deletion is a new project revision
+        assert_eq!(
+            get_project_row(&pool, proj).await.unwrap().revision,
+            revision + 1
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_readd_ok(pool: Pool) {
+        let proj = Project(42);
+
+        remove_image(
+            &pool,
+            Owner(1),
+            proj,
+            "img.png",
+            1703980420641538067
+        ).await.unwrap();
+
+        add_image_url(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ImageFileRow {
+                filename: "img.png",
+                url: "https://example.com/img.png",
+                content_type: "image/png",
+                size: 5678
+            },
+            1703980420641538068
+        ).await.unwrap();
+
+        assert_eq!(
+            get_image_url(&pool, proj, "img.png").await.unwrap(),
+            "https://example.com/img.png"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_not_an_image(pool: Pool) {
+        assert_eq!(
+            remove_image(
+                &pool,
+                Owner(1),
+                Project(42),
+                "bogus",
+                1703980420641538067
+            ).await.unwrap_err(),
+            CoreError::NotFound
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_in_use(pool: Pool) {
+        let proj = Project(42);
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ProjectDataPatch {
+                image: Some(Some("img.png".into())),
+                ..Default::default()
+            },
+            1703980420641538066
+        ).await.unwrap();
+
+        assert_eq!(
+            remove_image(
+                &pool,
+                Owner(1),
+                proj,
+                "img.png",
+                1703980420641538067
+            ).await.unwrap_err(),
+            CoreError::ImageInUse
+        );
+
+        assert_eq!(
+            get_image_url(&pool, proj, "img.png").await.unwrap(),
+            "https://example.com/images/img.png"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn remove_image_in_gallery(pool: Pool) {
+        let proj = Project(42);
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &ProjectDataPatch {
+                gallery: Some(vec![
+                    GalleryItem {
+                        filename: "img.png".into(),
+                        caption: "A picture".into()
+                    }
+                ]),
+                ..Default::default()
+            },
+            1703980420641538066
+        ).await.unwrap();
+
+        assert_eq!(
+            remove_image(
+                &pool,
+                Owner(1),
+                proj,
+                "img.png",
+                1703980420641538067
+            ).await.unwrap_err(),
+            CoreError::ImageInUse
+        );
+    }
 }
@@ -7,7 +7,7 @@ use crate::{
     core::CoreError,
     db::PackageRow,
     model::{Owner, PackageDataPost, Project},
-    sqlite::project::update_project_non_project_data
+    sqlite::project::{check_revision, update_project_non_project_data}
 };
 
 pub async fn get_packages<'e, E>(
@@ -69,6 +69,7 @@ pub async fn create_package<'a, A>(
     conn: A,
     owner: Owner,
     proj: Project,
+    expected: Option<i64>,
     pkg: &str,
     pkg_data: &PackageDataPost,
     now: i64
@@ -78,6 +79,8 @@ where
 {
     let mut tx = conn.begin().await?;
 
+    check_revision(&mut *tx, proj, expected).await?;
+
     sqlx::query!(
         "
 INSERT INTO packages (
@@ -199,6 +202,7 @@ mod test {
             &pool,
             Owner(1),
             proj,
+            None,
             "newpkg",
             &PackageDataPost {
                 description: "".into()
@@ -231,13 +235,14 @@ mod test {
                     &pool,
                     Owner(1),
                     Project(0),
+                    None,
                     "newpkg",
                     &PackageDataPost {
                         description: "".into()
                     },
                     1699804206419538067
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -250,14 +255,43 @@ mod test {
                     &pool,
                     Owner(1),
                     Project(42),
+                    None,
                     "a_package",
                     &PackageDataPost {
                         description: "".into()
                     },
                     1699804206419538067
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::PackageExists
             )
         );
     }
+
+    #[sqlx::test(fixtures("users", "projects", "packages"))]
+    async fn create_package_stale_revision(pool: Pool) {
+        assert_eq!(
+            create_package(
+                &pool,
+                Owner(1),
+                Project(42),
+                Some(0),
+                "newpkg",
+                &PackageDataPost {
+                    description: "".into()
+                },
+                1699804206419538067
+            ).await.unwrap_err(),
+            CoreError::PreconditionFailed
+        );
+
+        assert_eq!(
+            get_packages(&pool, Project(42))
+                .await
+                .unwrap()
+                .into_iter()
+                .filter(|p| p.name == "newpkg")
+                .count(),
+            0
+        );
+    }
 }
@@ -165,7 +165,7 @@ mod test {
         assert!(
             matches!(
                 add_player(&pool, User(2), Project(0)).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -175,7 +175,7 @@ mod test {
         assert!(
             matches!(
                 add_player(&pool, User(0), Project(42)).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
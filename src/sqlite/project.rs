@@ -1,13 +1,19 @@
 use sqlx::{
     Acquire, Executor, QueryBuilder, Transaction,
-    sqlite::Sqlite
+    sqlite::Sqlite,
+    types::Json
 };
 
 use crate::{
     core::CoreError,
-    db::ProjectRow,
-    model::{Owner, Project, ProjectDataPatch, ProjectDataPost, User},
-    sqlite::users::add_owner
+    db::{GameRow, ProjectRow},
+    model::{GameDataPatch, Owner, Project, ProjectDataPatch, ProjectDataPost, User},
+    sqlite::{
+        gallery::{copy_gallery_items, create_gallery_items, get_gallery},
+        games::find_game,
+        images::check_image_reference,
+        users::add_owner
+    }
 };
 
 pub async fn get_project_id<'e, E>(
@@ -22,6 +28,29 @@ where
 SELECT project_id
 FROM projects
 WHERE name = ?
+    AND deleted_at IS NULL
+        ",
+        projname
+    )
+    .fetch_optional(ex)
+    .await?
+    .map(Project)
+    .ok_or(CoreError::NotAProject)
+}
+
+pub async fn get_deleted_project_id<'e, E>(
+    ex: E,
+    projname: &str
+) -> Result<Project, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query_scalar!(
+        "
+SELECT project_id
+FROM projects
+WHERE name = ?
+    AND deleted_at IS NOT NULL
         ",
         projname
     )
@@ -31,21 +60,239 @@ WHERE name = ?
     .ok_or(CoreError::NotAProject)
 }
 
+pub async fn delete_project<'e, E>(
+    ex: E,
+    owner: Owner,
+    proj: Project,
+    now: i64
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // deleting an already deleted project keeps the original deletion time
+    sqlx::query!(
+        "
+UPDATE projects
+SET deleted_at = ?,
+    deleted_by = ?
+WHERE project_id = ?
+    AND deleted_at IS NULL
+        ",
+        now,
+        owner.0,
+        proj.0
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn restore_project<'e, E>(
+    ex: E,
+    proj: Project,
+    after: i64
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // projects deleted before the cutoff are past their grace period and
+    // awaiting purging, so cannot be restored
+    let restored = sqlx::query!(
+        "
+UPDATE projects
+SET deleted_at = NULL,
+    deleted_by = NULL
+WHERE project_id = ?
+    AND deleted_at > ?
+        ",
+        proj.0,
+        after
+    )
+    .execute(ex)
+    .await?
+    .rows_affected();
+
+    match restored {
+        0 => Err(CoreError::NotAProject),
+        _ => Ok(())
+    }
+}
+
+pub async fn get_project_alias<'e, E>(
+    ex: E,
+    projname: &str
+) -> Result<String, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query_scalar!(
+        "
+SELECT projects.name
+FROM project_aliases
+JOIN projects
+ON project_aliases.project_id = projects.project_id
+WHERE project_aliases.name = ?
+    AND projects.deleted_at IS NULL
+LIMIT 1
+        ",
+        projname
+    )
+    .fetch_optional(ex)
+    .await?
+    .ok_or(CoreError::NotAProject)
+}
+
+async fn create_project_alias(
+    tx: &mut Transaction<'_, Sqlite>,
+    proj: Project,
+    old_name: &str,
+    new_name: &str,
+    now: i64
+) -> Result<(), CoreError>
+{
+    // a project's current name is never an alias
+    sqlx::query!(
+        "
+DELETE FROM project_aliases
+WHERE name = ?
+        ",
+        new_name
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    // an old name taken by a rename points to the project which last had it
+    sqlx::query!(
+        "
+INSERT INTO project_aliases (
+    name,
+    project_id,
+    created_at
+)
+VALUES (?, ?, ?)
+ON CONFLICT(name)
+DO UPDATE
+SET project_id = excluded.project_id,
+    created_at = excluded.created_at
+        ",
+        old_name,
+        proj.0,
+        now
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
 fn normalize_project_name(proj: &str) -> String {
     proj.to_lowercase().replace('-', "_")
 }
 
+async fn normalized_name_in_use<'e, E>(
+    ex: E,
+    proj: Project,
+    proj_norm: &str
+) -> Result<bool, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query!(
+            "
+SELECT 1 AS present
+FROM projects
+WHERE normalized_name = ?
+    AND project_id != ?
+LIMIT 1
+            ",
+            proj_norm,
+            proj.0
+        )
+        .fetch_optional(ex)
+        .await?
+        .is_some()
+    )
+}
+
+async fn update_project_name<'e, E>(
+    ex: E,
+    proj: Project,
+    name: &str,
+    proj_norm: &str
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query!(
+        "
+UPDATE projects
+SET name = ?,
+    normalized_name = ?
+WHERE project_id = ?
+        ",
+        name,
+        proj_norm,
+        proj.0
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn rename_project<'a, A>(
+    conn: A,
+    owner: Owner,
+    proj: Project,
+    name: &str,
+    now: i64
+) -> Result<(), CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    let row = get_project_row(&mut *tx, proj).await?;
+    if row.name == name {
+        return Ok(());
+    }
+
+    // the new name must not collide with any other project
+    let proj_norm = normalize_project_name(name);
+    if normalized_name_in_use(&mut *tx, proj, &proj_norm).await? {
+        return Err(CoreError::ProjectNameInUse);
+    }
+
+    update_project_name(&mut *tx, proj, name, &proj_norm).await?;
+
+    // keep the old name so that it can be redirected
+    create_project_alias(&mut tx, proj, &row.name, name, now).await?;
+
+    // the new revision records the new name
+    update_project_non_project_data(&mut tx, owner, proj, now).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
 async fn create_project_row<'e, E>(
     ex: E,
     user: User,
     proj: &str,
     proj_data: &ProjectDataPost,
+    game: &GameRow,
     now: i64
 ) -> Result<Project, CoreError>
 where
     E: Executor<'e, Database = Sqlite>
 {
     let proj_norm = normalize_project_name(proj);
+    let alternate_titles = Json(&proj_data.game.alternate_titles);
+    let designers = Json(&proj_data.game.designers);
+    let tags = Json(&proj_data.tags);
 
     Ok(
         Project(
@@ -60,23 +307,41 @@ INSERT INTO projects (
     game_title_sort,
     game_publisher,
     game_year,
+    game_id,
+    game_alternate_titles,
+    game_designers,
+    game_min_players,
+    game_max_players,
+    game_playing_time,
+    game_era,
+    game_external_id,
+    tags,
     readme,
     image,
     modified_at,
     modified_by,
     revision
 )
-VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 RETURNING project_id
                 ",
                 proj,
                 proj_norm,
                 now,
                 proj_data.description,
-                proj_data.game.title,
+                game.title,
                 proj_data.game.title_sort_key,
-                proj_data.game.publisher,
-                proj_data.game.year,
+                game.publisher,
+                game.year,
+                game.game_id,
+                alternate_titles,
+                designers,
+                proj_data.game.min_players,
+                proj_data.game.max_players,
+                proj_data.game.playing_time,
+                proj_data.game.era,
+                proj_data.game.external_id,
+                tags,
                 "",
                 None::<&str>,
                 now,
@@ -97,6 +362,15 @@ struct ProjectDataRow<'a> {
     game_title_sort: &'a str,
     game_publisher: &'a str,
     game_year: &'a str,
+    game_id: i64,
+    game_alternate_titles: &'a [String],
+    game_designers: &'a [String],
+    game_min_players: Option<i64>,
+    game_max_players: Option<i64>,
+    game_playing_time: Option<i64>,
+    game_era: Option<&'a str>,
+    game_external_id: Option<&'a str>,
+    tags: &'a [String],
     readme: &'a str,
     image: Option<&'a str>
 }
@@ -108,6 +382,10 @@ async fn create_project_data_row<'e, E>(
 where
     E: Executor<'e, Database = Sqlite>
 {
+    let alternate_titles = Json(row.game_alternate_titles);
+    let designers = Json(row.game_designers);
+    let tags = Json(row.tags);
+
     Ok(
         sqlx::query_scalar!(
             "
@@ -118,10 +396,19 @@ INSERT INTO project_data (
     game_title_sort,
     game_publisher,
     game_year,
+    game_id,
+    game_alternate_titles,
+    game_designers,
+    game_min_players,
+    game_max_players,
+    game_playing_time,
+    game_era,
+    game_external_id,
+    tags,
     readme,
     image
 )
-VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
 RETURNING project_data_id
             ",
             row.project_id,
@@ -130,6 +417,15 @@ RETURNING project_data_id
             row.game_title_sort,
             row.game_publisher,
             row.game_year,
+            row.game_id,
+            alternate_titles,
+            designers,
+            row.game_min_players,
+            row.game_max_players,
+            row.game_playing_time,
+            row.game_era,
+            row.game_external_id,
+            tags,
             row.readme,
             row.image
         )
@@ -195,8 +491,19 @@ where
 {
     let mut tx = conn.begin().await?;
 
+    // find the game or create it if this is the first project for it
+    let game = find_game(
+        &mut *tx,
+        pd.game.id,
+        &pd.game.title,
+        &pd.game.title_sort_key,
+        &pd.game.publisher,
+        &pd.game.year
+    ).await?;
+
     // create project row
-    let proj = create_project_row(&mut *tx, owner, name, pd, now).await?;
+    let proj = create_project_row(&mut *tx, owner, name, pd, &game, now)
+        .await?;
 
     // associate new owner with the project
     add_owner(&mut *tx, owner, proj).await?;
@@ -205,16 +512,27 @@ where
     let dr = ProjectDataRow {
         project_id: proj.0,
         description: &pd.description,
-        game_title: &pd.game.title,
+        game_title: &game.title,
         game_title_sort: &pd.game.title_sort_key,
-        game_publisher:  &pd.game.publisher,
-        game_year: &pd.game.year,
+        game_publisher: &game.publisher,
+        game_year: &game.year,
+        game_id: game.game_id,
+        game_alternate_titles: &pd.game.alternate_titles,
+        game_designers: &pd.game.designers,
+        game_min_players: pd.game.min_players,
+        game_max_players: pd.game.max_players,
+        game_playing_time: pd.game.playing_time,
+        game_era: pd.game.era.as_deref(),
+        game_external_id: pd.game.external_id.as_deref(),
+        tags: &pd.tags,
         readme: &pd.readme,
         image: pd.image.as_deref()
     };
 
     let project_data_id = create_project_data_row(&mut *tx, &dr).await?;
 
+    create_gallery_items(&mut tx, proj, project_data_id, &pd.gallery).await?;
+
     let rr = ProjectRevisionRow {
         project_id: proj.0,
         name,
@@ -238,6 +556,7 @@ async fn update_project_row<'e, E>(
     proj: Project,
     revision: i64,
     pd: &ProjectDataPatch,
+    game: Option<&GameRow>,
     now: i64
 ) -> Result<(), CoreError>
 where
@@ -257,24 +576,64 @@ where
         .push("modified_by = ")
         .push_bind_unseparated(owner.0);
 
-    if let Some(description) = &pd.description {
-        qbs.push("description = ").push_bind_unseparated(description);
+    // the copies of the game fields are kept for full-text search
+    if let Some(game) = game {
+        qbs
+            .push("game_id = ")
+            .push_bind_unseparated(game.game_id)
+            .push("game_title = ")
+            .push_bind_unseparated(&game.title)
+            .push("game_publisher = ")
+            .push_bind_unseparated(&game.publisher)
+            .push("game_year = ")
+            .push_bind_unseparated(&game.year);
     }
 
-    if let Some(game_title) = &pd.game.title {
-        qbs.push("game_title = ").push_bind_unseparated(game_title);
+    if let Some(description) = &pd.description {
+        qbs.push("description = ").push_bind_unseparated(description);
     }
 
+    // the sort key belongs to the project, so owners may override it
     if let Some(game_title_sort) = &pd.game.title_sort_key {
         qbs.push("game_title_sort = ").push_bind_unseparated(game_title_sort);
     }
 
-    if let Some(game_publisher) = &pd.game.publisher {
-        qbs.push("game_publisher = ").push_bind_unseparated(game_publisher);
+    if let Some(game_alternate_titles) = &pd.game.alternate_titles {
+        qbs.push("game_alternate_titles = ")
+            .push_bind_unseparated(Json(game_alternate_titles));
+    }
+
+    if let Some(game_designers) = &pd.game.designers {
+        qbs.push("game_designers = ")
+            .push_bind_unseparated(Json(game_designers));
+    }
+
+    if let Some(game_min_players) = &pd.game.min_players {
+        qbs.push("game_min_players = ")
+            .push_bind_unseparated(game_min_players);
+    }
+
+    if let Some(game_max_players) = &pd.game.max_players {
+        qbs.push("game_max_players = ")
+            .push_bind_unseparated(game_max_players);
+    }
+
+    if let Some(game_playing_time) = &pd.game.playing_time {
+        qbs.push("game_playing_time = ")
+            .push_bind_unseparated(game_playing_time);
     }
 
-    if let Some(game_year) = &pd.game.year {
-        qbs.push("game_year = ").push_bind_unseparated(game_year);
+    if let Some(game_era) = &pd.game.era {
+        qbs.push("game_era = ").push_bind_unseparated(game_era);
+    }
+
+    if let Some(game_external_id) = &pd.game.external_id {
+        qbs.push("game_external_id = ")
+            .push_bind_unseparated(game_external_id);
+    }
+
+    if let Some(tags) = &pd.tags {
+        qbs.push("tags = ").push_bind_unseparated(Json(tags));
     }
 
     if let Some(readme) = &pd.readme {
@@ -299,6 +658,7 @@ pub async fn update_project<'a, A>(
     conn: A,
     owner: Owner,
     proj: Project,
+    expected: Option<i64>,
     pd: &ProjectDataPatch,
     now: i64
 ) -> Result<(), CoreError>
@@ -307,27 +667,94 @@ where
 {
     let mut tx = conn.begin().await?;
 
+    check_revision(&mut *tx, proj, expected).await?;
+
     // get project
     let row = get_project_row(&mut *tx, proj).await?;
     let revision = row.revision + 1;
 
+    // a new project image must be one which has not been deleted
+    if let Some(Some(image)) = &pd.image {
+        check_image_reference(&mut *tx, proj, image).await?;
+    }
+
+    // changing the game or its fields may move the project to another game
+    let game = find_game(
+        &mut *tx,
+        pd.game.id,
+        pd.game.title.as_ref().unwrap_or(&row.game_title),
+        pd.game.title_sort_key.as_ref().unwrap_or(&row.game_title_sort),
+        pd.game.publisher.as_ref().unwrap_or(&row.game_publisher),
+        pd.game.year.as_ref().unwrap_or(&row.game_year)
+    ).await?;
+
     // update project
-    update_project_row(&mut *tx, owner, proj, revision, pd, now).await?;
+    update_project_row(
+        &mut *tx,
+        owner,
+        proj,
+        revision,
+        pd,
+        Some(&game),
+        now
+    ).await?;
 
     // create project revision
     let dr = ProjectDataRow {
         project_id: proj.0,
         description: pd.description.as_ref().unwrap_or(&row.description),
-        game_title: pd.game.title.as_ref().unwrap_or(&row.game_title),
-        game_title_sort: pd.game.title_sort_key.as_ref().unwrap_or(&row.game_title_sort),
-        game_publisher: pd.game.publisher.as_ref().unwrap_or(&row.game_publisher),
-        game_year: pd.game.year.as_ref().unwrap_or(&row.game_year),
+        game_title: &game.title,
+        game_title_sort: pd.game.title_sort_key.as_ref()
+            .unwrap_or(&row.game_title_sort),
+        game_publisher: &game.publisher,
+        game_year: &game.year,
+        game_id: game.game_id,
+        game_alternate_titles: pd.game.alternate_titles.as_ref()
+            .unwrap_or(&row.game_alternate_titles.0),
+        game_designers: pd.game.designers.as_ref()
+            .unwrap_or(&row.game_designers.0),
+        game_min_players: pd.game.min_players
+            .unwrap_or(row.game_min_players),
+        game_max_players: pd.game.max_players
+            .unwrap_or(row.game_max_players),
+        game_playing_time: pd.game.playing_time
+            .unwrap_or(row.game_playing_time),
+        game_era: pd.game.era.as_ref()
+            .unwrap_or(&row.game_era)
+            .as_deref(),
+        game_external_id: pd.game.external_id.as_ref()
+            .unwrap_or(&row.game_external_id)
+            .as_deref(),
+        tags: pd.tags.as_ref().unwrap_or(&row.tags.0),
         readme: pd.readme.as_ref().unwrap_or(&row.readme),
         image: pd.image.as_ref().unwrap_or(&row.image).as_deref()
     };
 
     let project_data_id = create_project_data_row(&mut *tx, &dr).await?;
 
+    // set the new gallery or carry over the old one
+    match &pd.gallery {
+        Some(gallery) => create_gallery_items(
+            &mut tx,
+            proj,
+            project_data_id,
+            gallery
+        ).await?,
+        None => {
+            let old_project_data_id = get_project_data_id(
+                &mut *tx,
+                proj,
+                row.revision
+            ).await?;
+
+            copy_gallery_items(
+                &mut *tx,
+                old_project_data_id,
+                project_data_id
+            ).await?
+        }
+    };
+
     let rr = ProjectRevisionRow {
         project_id: proj.0,
         name: &row.name,
@@ -345,6 +772,88 @@ where
     Ok(())
 }
 
+pub async fn revert_project<'a, A>(
+    conn: A,
+    owner: Owner,
+    proj: Project,
+    revision: i64,
+    now: i64
+) -> Result<(), CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    // get the project data at the target revision
+    let row = get_project_row_revision(&mut *tx, proj, revision).await?;
+    let gallery = get_gallery(&mut *tx, proj, revision).await?;
+
+    // set every field to its value at the target revision; packages,
+    // releases, and images are not part of the project data, so are
+    // left untouched
+    let pd = ProjectDataPatch {
+        description: Some(row.description),
+        tags: Some(row.tags.0),
+        game: GameDataPatch {
+            // the project returns to the same game even if it has changed
+            id: row.game_id,
+            title: Some(row.game_title),
+            title_sort_key: Some(row.game_title_sort),
+            publisher: Some(row.game_publisher),
+            year: Some(row.game_year),
+            alternate_titles: Some(row.game_alternate_titles.0),
+            designers: Some(row.game_designers.0),
+            min_players: Some(row.game_min_players),
+            max_players: Some(row.game_max_players),
+            playing_time: Some(row.game_playing_time),
+            era: Some(row.game_era),
+            external_id: Some(row.game_external_id)
+        },
+        readme: Some(row.readme),
+        image: Some(row.image),
+        gallery: Some(gallery)
+    };
+
+    update_project(&mut *tx, owner, proj, None, &pd, now).await?;
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+pub async fn check_revision<'e, E>(
+    ex: E,
+    proj: Project,
+    expected: Option<i64>
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    let expected = match expected {
+        Some(expected) => expected,
+        None => return Ok(())
+    };
+
+    let revision = sqlx::query_scalar!(
+        "
+SELECT revision
+FROM projects
+WHERE project_id = ?
+LIMIT 1
+        ",
+        proj.0
+    )
+    .fetch_optional(ex)
+    .await?
+    .ok_or(CoreError::NotAProject)?;
+
+    // the project must not have changed since the client last saw it
+    match revision == expected {
+        true => Ok(()),
+        false => Err(CoreError::PreconditionFailed)
+    }
+}
+
 pub async fn get_project_row<'e, E>(
     ex: E,
     proj: Project
@@ -356,21 +865,32 @@ where
         ProjectRow,
         "
 SELECT
-    project_id,
-    name,
-    description,
-    revision,
-    created_at,
-    modified_at,
-    modified_by,
-    game_title,
-    game_title_sort,
-    game_publisher,
-    game_year,
-    readme,
-    image
+    projects.project_id,
+    projects.name,
+    projects.description,
+    projects.revision,
+    projects.created_at,
+    projects.modified_at,
+    projects.modified_by,
+    COALESCE(games.title, projects.game_title) AS \"game_title!: String\",
+    projects.game_title_sort,
+    COALESCE(games.publisher, projects.game_publisher) AS \"game_publisher!: String\",
+    COALESCE(games.year, projects.game_year) AS \"game_year!: String\",
+    projects.game_id,
+    projects.game_alternate_titles AS \"game_alternate_titles: Json<Vec<String>>\",
+    projects.game_designers AS \"game_designers: Json<Vec<String>>\",
+    projects.game_min_players,
+    projects.game_max_players,
+    projects.game_playing_time,
+    projects.game_era,
+    projects.game_external_id,
+    projects.tags AS \"tags: Json<Vec<String>>\",
+    projects.readme,
+    projects.image
 FROM projects
-WHERE project_id = ?
+LEFT JOIN games
+ON projects.game_id = games.game_id
+WHERE projects.project_id = ?
 LIMIT 1
         ",
         proj.0
@@ -403,6 +923,15 @@ SELECT
     project_data.game_title_sort,
     project_data.game_publisher,
     project_data.game_year,
+    project_data.game_id,
+    project_data.game_alternate_titles AS \"game_alternate_titles: Json<Vec<String>>\",
+    project_data.game_designers AS \"game_designers: Json<Vec<String>>\",
+    project_data.game_min_players,
+    project_data.game_max_players,
+    project_data.game_playing_time,
+    project_data.game_era,
+    project_data.game_external_id,
+    project_data.tags AS \"tags: Json<Vec<String>>\",
     project_data.image,
     project_data.readme
 FROM project_revisions
@@ -480,6 +1009,7 @@ pub async fn update_project_non_project_data(
         proj,
         revision,
         &Default::default(),
+        None,
         now
     ).await?;
 
@@ -512,6 +1042,223 @@ mod test {
         );
     }
 
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_project_id_deleted(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_id(&pool, "test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_deleted_project_id_ok(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_deleted_project_id(&pool, "test_game").await.unwrap(),
+            Project(42)
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_deleted_project_id_not_deleted(pool: Pool) {
+        assert_eq!(
+            get_deleted_project_id(&pool, "test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn restore_project_ok(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        restore_project(&pool, Project(42), 1703980420641538065)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_id(&pool, "test_game").await.unwrap(),
+            Project(42)
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn restore_project_after_grace_period(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            restore_project(&pool, Project(42), 1703980420641538066)
+                .await
+                .unwrap_err(),
+            CoreError::NotAProject
+        );
+
+        assert_eq!(
+            get_deleted_project_id(&pool, "test_game").await.unwrap(),
+            Project(42)
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_ok(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        rename_project(&pool, Owner(1), proj, "new_name", 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(get_project_id(&pool, "new_name").await.unwrap(), proj);
+
+        assert_eq!(
+            get_project_id(&pool, "test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+
+        assert_eq!(
+            get_project_alias(&pool, "test_game").await.unwrap(),
+            "new_name"
+        );
+
+        let new_row = get_project_row(&pool, proj).await.unwrap();
+        assert_eq!(new_row.revision, orig_row.revision + 1);
+
+        assert_eq!(
+            get_project_row_revision(&pool, proj, new_row.revision)
+                .await
+                .unwrap()
+                .name,
+            "new_name"
+        );
+
+        assert_eq!(
+            get_project_row_revision(&pool, proj, orig_row.revision)
+                .await
+                .unwrap()
+                .name,
+            "test_game"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_twice(pool: Pool) {
+        let proj = Project(42);
+
+        rename_project(&pool, Owner(1), proj, "second", 1703980420641538066)
+            .await
+            .unwrap();
+
+        rename_project(&pool, Owner(1), proj, "third", 1703980420641538067)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_alias(&pool, "test_game").await.unwrap(),
+            "third"
+        );
+
+        assert_eq!(
+            get_project_alias(&pool, "second").await.unwrap(),
+            "third"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_back(pool: Pool) {
+        let proj = Project(42);
+
+        rename_project(&pool, Owner(1), proj, "other", 1703980420641538066)
+            .await
+            .unwrap();
+
+        rename_project(&pool, Owner(1), proj, "test_game", 1703980420641538067)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_alias(&pool, "test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+
+        assert_eq!(
+            get_project_alias(&pool, "other").await.unwrap(),
+            "test_game"
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_change_case(pool: Pool) {
+        let proj = Project(42);
+
+        rename_project(&pool, Owner(1), proj, "Test-Game", 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(get_project_id(&pool, "Test-Game").await.unwrap(), proj);
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_same_name(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        rename_project(&pool, Owner(1), proj, "test_game", 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(get_project_row(&pool, proj).await.unwrap(), orig_row);
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn rename_project_name_in_use(pool: Pool) {
+        assert_eq!(
+            rename_project(
+                &pool,
+                Owner(1),
+                Project(42),
+                "A-Game",
+                1703980420641538066
+            ).await.unwrap_err(),
+            CoreError::ProjectNameInUse
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_project_alias_deleted(pool: Pool) {
+        let proj = Project(42);
+
+        rename_project(&pool, Owner(1), proj, "new_name", 1703980420641538066)
+            .await
+            .unwrap();
+
+        delete_project(&pool, Owner(1), proj, 1703980420641538067)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_alias(&pool, "test_game").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_project_alias_not_an_alias(pool: Pool) {
+        assert_eq!(
+            get_project_alias(&pool, "bogus").await.unwrap_err(),
+            CoreError::NotAProject
+        );
+    }
+
     #[test]
     fn normalize_project_names() {
         assert_eq!(normalize_project_name("foo"), "foo");
@@ -533,6 +1280,15 @@ mod test {
             game_title_sort: "Game of Tests, A".into(),
             game_publisher: "Test Game Company".into(),
             game_year: "1979".into(),
+            game_id: Some(1),
+            game_alternate_titles: Json(vec![]),
+            game_designers: Json(vec![]),
+            game_min_players: None,
+            game_max_players: None,
+            game_playing_time: None,
+            game_era: None,
+            game_external_id: None,
+            tags: Json(vec![]),
             readme: "".into(),
             image: None
         }
@@ -543,13 +1299,16 @@ mod test {
             description: CREATE_ROW.description.clone(),
             tags: vec![],
             game: GameData {
+                id: None,
                 title: CREATE_ROW.game_title.clone(),
                 title_sort_key: CREATE_ROW.game_title_sort.clone(),
                 publisher: CREATE_ROW.game_publisher.clone(),
-                year: CREATE_ROW.game_year.clone()
+                year: CREATE_ROW.game_year.clone(),
+                ..Default::default()
             },
             readme: "".into(),
-            image: None
+            image: None,
+            gallery: vec![]
         }
     );
 
@@ -592,7 +1351,7 @@ mod test {
                     &CREATE_DATA,
                     CREATE_ROW.created_at
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
 
@@ -623,7 +1382,7 @@ mod test {
                     &CREATE_DATA,
                     row.created_at
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::ProjectNameInUse
             )
         );
     }
@@ -650,7 +1409,7 @@ mod test {
                     &CREATE_DATA,
                     row.created_at
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::ProjectNameInUse
             )
         );
     }
@@ -677,7 +1436,7 @@ mod test {
                     &CREATE_DATA,
                     row.created_at
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::ProjectNameInUse
             )
         );
     }
@@ -696,6 +1455,7 @@ mod test {
             &pool,
             Owner(1),
             proj,
+            None,
             &pd,
             1702569006419538068
         ).await.unwrap();
@@ -707,6 +1467,108 @@ mod test {
         assert_eq!(new_row.revision, orig_row.revision + 1);
     }
 
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_project_game_id(pool: Pool) {
+        let proj = Project(42);
+
+        let pd = ProjectDataPatch {
+            game: GameDataPatch {
+                id: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            None,
+            &pd,
+            1702569006419538068
+        ).await.unwrap();
+
+        // the project takes the fields of the game it moves to
+        let row = get_project_row(&pool, proj).await.unwrap();
+        assert_eq!(row.game_id, Some(2));
+        assert_eq!(row.game_title, "Some Other Game");
+        assert_eq!(row.game_publisher, "XYZ");
+        assert_eq!(row.game_year, "1993");
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn update_project_not_a_game(pool: Pool) {
+        let pd = ProjectDataPatch {
+            game: GameDataPatch {
+                id: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update_project(
+                &pool,
+                Owner(1),
+                Project(42),
+                None,
+                &pd,
+                1702569006419538068
+            ).await.unwrap_err(),
+            CoreError::NotAGame
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn update_project_expected_revision(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        let pd = ProjectDataPatch {
+            description: Some("foo".into()),
+            ..Default::default()
+        };
+
+        update_project(
+            &pool,
+            Owner(1),
+            proj,
+            Some(orig_row.revision),
+            &pd,
+            1702569006419538068
+        ).await.unwrap();
+
+        assert_eq!(
+            get_project_row(&pool, proj).await.unwrap().revision,
+            orig_row.revision + 1
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn update_project_stale_revision(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+
+        let pd = ProjectDataPatch {
+            description: Some("foo".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update_project(
+                &pool,
+                Owner(1),
+                proj,
+                Some(orig_row.revision - 1),
+                &pd,
+                1702569006419538068
+            ).await.unwrap_err(),
+            CoreError::PreconditionFailed
+        );
+
+        assert_eq!(get_project_row(&pool, proj).await.unwrap(), orig_row);
+    }
+
     #[sqlx::test(fixtures("users", "projects"))]
     async fn update_project_not_a_project(pool: Pool) {
         let pd = ProjectDataPatch {
@@ -719,6 +1581,7 @@ mod test {
                 &pool,
                 Owner(1),
                 Project(0),
+                None,
                 &pd,
                 0
             ).await.unwrap_err(),
@@ -740,14 +1603,116 @@ mod test {
                     &pool,
                     Owner(0),
                     Project(42),
+                    None,
                     &pd,
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
 
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn update_project_not_an_image(pool: Pool) {
+        let pd = ProjectDataPatch {
+            image: Some(Some("not_an_image.png".into())),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update_project(
+                &pool,
+                Owner(1),
+                Project(42),
+                None,
+                &pd,
+                0
+            ).await.unwrap_err(),
+            CoreError::InvalidReference
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn revert_project_ok(pool: Pool) {
+        let proj = Project(42);
+        let orig_row = get_project_row(&pool, proj).await.unwrap();
+        let old_row = get_project_row_revision(&pool, proj, 1).await.unwrap();
+
+        revert_project(
+            &pool,
+            Owner(1),
+            proj,
+            1,
+            1702569006419538068
+        ).await.unwrap();
+
+        let new_row = get_project_row(&pool, proj).await.unwrap();
+
+        assert_ne!(orig_row.game_year, old_row.game_year);
+        assert_eq!(new_row.game_year, old_row.game_year);
+        assert_eq!(new_row.revision, orig_row.revision + 1);
+        assert!(new_row.game_id.is_some());
+
+        assert_eq!(
+            get_project_row_revision(&pool, proj, new_row.revision)
+                .await
+                .unwrap(),
+            ProjectRow {
+                revision: new_row.revision,
+                modified_at: 1702569006419538068,
+                modified_by: 1,
+                game_id: new_row.game_id,
+                ..old_row
+            }
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn revert_project_not_a_revision(pool: Pool) {
+        assert_eq!(
+            revert_project(
+                &pool,
+                Owner(1),
+                Project(42),
+                4,
+                1702569006419538068
+            ).await.unwrap_err(),
+            CoreError::NotARevision
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "images"))]
+    async fn revert_project_image_deleted(pool: Pool) {
+        let proj = Project(42);
+        let now = 1702569006419538068;
+
+        // set the project image, then unset it and delete the image
+        let pd = ProjectDataPatch {
+            image: Some(Some("img.png".into())),
+            ..Default::default()
+        };
+        update_project(&pool, Owner(1), proj, None, &pd, now).await.unwrap();
+
+        let pd = ProjectDataPatch {
+            image: Some(None),
+            ..Default::default()
+        };
+        update_project(&pool, Owner(1), proj, None, &pd, now + 1).await.unwrap();
+
+        sqlx::query!("UPDATE images SET deleted = 1 WHERE project_id = 42")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // the revision with the image cannot be restored
+        assert_eq!(
+            revert_project(&pool, Owner(1), proj, 4, now + 2)
+                .await
+                .unwrap_err(),
+            CoreError::InvalidReference
+        );
+    }
+
     #[sqlx::test(fixtures("users", "projects"))]
     async fn update_project_non_project_data_ok(pool: Pool) {
 
@@ -799,7 +1764,7 @@ mod test {
                     Project(42),
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -817,6 +1782,15 @@ mod test {
             game_title_sort: "Game of Tests, A".into(),
             game_publisher: "Test Game Company".into(),
             game_year: "1979".into(),
+            game_id: None,
+            game_alternate_titles: Json(vec![]),
+            game_designers: Json(vec![]),
+            game_min_players: None,
+            game_max_players: None,
+            game_playing_time: None,
+            game_era: None,
+            game_external_id: None,
+            tags: Json(vec![]),
             readme: "".into(),
             image: None
         }
@@ -835,6 +1809,15 @@ mod test {
             game_title_sort: "Game of Tests, A".into(),
             game_publisher: "Test Game Company".into(),
             game_year: "1978".into(),
+            game_id: None,
+            game_alternate_titles: Json(vec![]),
+            game_designers: Json(vec![]),
+            game_min_players: None,
+            game_max_players: None,
+            game_playing_time: None,
+            game_era: None,
+            game_external_id: None,
+            tags: Json(vec![]),
             readme: "".into(),
             image: None
         }
@@ -848,6 +1831,19 @@ mod test {
         );
     }
 
+    #[sqlx::test(fixtures("users", "projects", "games"))]
+    async fn get_project_row_game_fields_from_game(pool: Pool) {
+        sqlx::query("UPDATE games SET publisher = 'TGC' WHERE game_id = 1")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_project_row(&pool, Project(42)).await.unwrap().game_publisher,
+            "TGC"
+        );
+    }
+
     #[sqlx::test(fixtures("users", "projects"))]
     async fn get_project_row_not_a_project(pool: Pool) {
         assert_eq!(
@@ -5,47 +5,267 @@ use sqlx::{
 
 use crate::{
     core::CoreError,
-    db::ProjectSummaryRow,
-    pagination::{Direction, SortBy}
+    db::{HIGHLIGHT_END, HIGHLIGHT_START, HighlightRow, ProjectSummaryRow},
+    pagination::{Direction, Facet, Filters, SortBy}
 };
 
+impl Filters {
+    fn push_where<'a>(&'a self, qb: &mut QueryBuilder<'a, Sqlite>) {
+        if let Some(designer) = &self.designer {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM json_each(projects.game_designers) WHERE json_each.value = "
+            )
+            .push_bind(designer)
+            .push(" COLLATE NOCASE)");
+        }
+
+        // projects with only one bound on players are taken to have
+        // exactly that many players
+        if let Some(players) = self.players {
+            qb.push(" AND COALESCE(projects.game_min_players, projects.game_max_players) <= ")
+                .push_bind(players)
+                .push(" AND COALESCE(projects.game_max_players, projects.game_min_players) >= ")
+                .push_bind(players);
+        }
+
+        if let Some(max_playing_time) = self.max_playing_time {
+            qb.push(" AND projects.game_playing_time <= ")
+                .push_bind(max_playing_time);
+        }
+
+        if let Some(era) = &self.era {
+            qb.push(" AND projects.game_era = ")
+                .push_bind(era)
+                .push(" COLLATE NOCASE");
+        }
+
+        if let Some(external_id) = &self.external_id {
+            qb.push(" AND projects.game_external_id = ")
+                .push_bind(external_id);
+        }
+
+        if let Some(publisher) = &self.publisher {
+            qb.push(" AND projects.game_publisher = ")
+                .push_bind(publisher)
+                .push(" COLLATE NOCASE");
+        }
+
+        // years are free text, so only those which are numbers can be
+        // compared with a range
+        if self.year_min.is_some() || self.year_max.is_some() {
+            qb.push(" AND projects.game_year != '' AND projects.game_year NOT GLOB '*[^0-9]*'");
+        }
+
+        if let Some(year_min) = self.year_min {
+            qb.push(" AND CAST(projects.game_year AS INTEGER) >= ")
+                .push_bind(year_min);
+        }
+
+        if let Some(year_max) = self.year_max {
+            qb.push(" AND CAST(projects.game_year AS INTEGER) <= ")
+                .push_bind(year_max);
+        }
+
+        if let Some(tag) = &self.tag {
+            qb.push(
+                " AND EXISTS (SELECT 1 FROM json_each(projects.tags) WHERE json_each.value = "
+            )
+            .push_bind(tag)
+            .push(" COLLATE NOCASE)");
+        }
+
+        if let Some(has_release) = self.has_release {
+            qb.push(match has_release {
+                true => " AND EXISTS",
+                false => " AND NOT EXISTS"
+            })
+            .push(" (SELECT 1 FROM releases JOIN packages ON releases.package_id = packages.package_id WHERE packages.project_id = projects.project_id)");
+        }
+
+        if let Some(owner) = &self.owner {
+            qb.push(" AND EXISTS (SELECT 1 FROM owners JOIN users ON owners.user_id = users.user_id WHERE owners.project_id = projects.project_id AND users.username = ")
+                .push_bind(owner)
+                .push(")");
+        }
+    }
+}
+
 pub async fn get_projects_count<'e, E>(
-    ex: E
+    ex: E,
+    filters: &Filters
 ) -> Result<i64, CoreError>
 where
     E: Executor<'e, Database = Sqlite>
 {
-    Ok(
-        sqlx::query_scalar!(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT COUNT(1)
 FROM projects
-            "
-        )
-        .fetch_one(ex)
-        .await?
-        .into()
+WHERE deleted_at IS NULL"
+    );
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.build_query_scalar::<i64>()
+            .fetch_one(ex)
+            .await?
     )
 }
 
 pub async fn get_projects_query_count<'e, E>(
     ex: E,
-    query: &str
+    query: &str,
+    filters: &Filters
 ) -> Result<i64, CoreError>
 where
     E: Executor<'e, Database = Sqlite>
 {
-    Ok(
-        sqlx::query_scalar!(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT COUNT(1)
+FROM projects
+JOIN (
+    SELECT projects_fts.rowid
+    FROM projects_fts
+    WHERE projects_fts MATCH "
+    );
+
+    qb.push_bind(query)
+        .push(") AS fts ON fts.rowid = projects.project_id WHERE 1");
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.build_query_scalar::<i64>()
+            .fetch_one(ex)
+            .await?
+    )
+}
+
+pub async fn get_projects_highlights<'e, 'f, E>(
+    ex: E,
+    query: &'f str,
+    names: &'f [String]
+) -> Result<Vec<HighlightRow>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // the columns are those of projects_fts: 0 is game_title, 3 is
+    // description, 4 is readme
+    let mut qb = QueryBuilder::new(
+        "
+SELECT
+    projects.name,
+    highlight(projects_fts, 0, "
+    );
+
+    qb.push_bind(HIGHLIGHT_START)
+        .push(", ")
+        .push_bind(HIGHLIGHT_END)
+        .push(") AS title, snippet(projects_fts, 3, ")
+        .push_bind(HIGHLIGHT_START)
+        .push(", ")
+        .push_bind(HIGHLIGHT_END)
+        .push(", '…', 16) AS description, snippet(projects_fts, 4, ")
+        .push_bind(HIGHLIGHT_START)
+        .push(", ")
+        .push_bind(HIGHLIGHT_END)
+        .push(
+            ", '…', 32) AS readme
 FROM projects_fts
-WHERE projects_fts MATCH ?
-            ",
-            query
+JOIN projects
+ON projects.project_id = projects_fts.rowid
+WHERE projects_fts MATCH "
         )
-        .fetch_one(ex)
-        .await?
+        .push_bind(query)
+        .push(" AND projects.name IN (");
+
+    let mut qbs = qb.separated(", ");
+    for name in names {
+        qbs.push_bind(name);
+    }
+
+    Ok(
+        qb.push(")")
+            .build_query_as::<HighlightRow>()
+            .fetch_all(ex)
+            .await?
+    )
+}
+
+impl Facet {
+    fn value(&self) -> &'static str {
+        match self {
+            Facet::Publisher => "projects.game_publisher",
+            Facet::Decade => "(CAST(projects.game_year AS INTEGER) / 10 * 10) || 's'",
+            Facet::Tag => "json_each.value"
+        }
+    }
+
+    fn source(&self) -> &'static str {
+        match self {
+            Facet::Tag => ", json_each(projects.tags)",
+            _ => ""
+        }
+    }
+
+    fn present(&self) -> &'static str {
+        match self {
+            Facet::Publisher => " AND projects.game_publisher != ''",
+            // only years which are numbers belong to a decade
+            Facet::Decade => " AND projects.game_year != '' AND projects.game_year NOT GLOB '*[^0-9]*'",
+            Facet::Tag => ""
+        }
+    }
+}
+
+pub async fn get_projects_facet<'e, 'f, E>(
+    ex: E,
+    facet: Facet,
+    query: Option<&'f str>,
+    filters: &'f Filters,
+    limit: u32
+) -> Result<Vec<(String, i64)>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // values which differ only by case are counted together
+    let mut qb = QueryBuilder::new("SELECT MIN(");
+
+    qb.push(facet.value())
+        .push(") AS value, COUNT(1) AS count FROM projects")
+        .push(facet.source());
+
+    match query {
+        Some(query) => {
+            qb.push(
+                "
+JOIN (
+    SELECT projects_fts.rowid
+    FROM projects_fts
+    WHERE projects_fts MATCH "
+            )
+            .push_bind(query)
+            .push(") AS fts ON fts.rowid = projects.project_id WHERE 1");
+        },
+        None => {
+            qb.push(" WHERE projects.deleted_at IS NULL");
+        }
+    }
+
+    qb.push(facet.present());
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.push(" GROUP BY ")
+            .push(facet.value())
+            .push(" COLLATE NOCASE ORDER BY count DESC, value COLLATE NOCASE LIMIT ")
+            .push_bind(limit)
+            .build_query_as::<(String, i64)>()
+            .fetch_all(ex)
+            .await?
     )
 }
 
@@ -78,18 +298,18 @@ impl Direction {
     }
 }
 
-pub async fn get_projects_end_window<'e, E>(
+pub async fn get_projects_end_window<'e, 'f, E>(
     ex: E,
     sort_by: SortBy,
     dir: Direction,
+    filters: &'f Filters,
     limit: u32
 ) -> Result<Vec<ProjectSummaryRow>, CoreError>
 where
     E: Executor<'e, Database = Sqlite>
 {
-    Ok(
-        QueryBuilder::new(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT
     0.0 AS rank,
     project_id,
@@ -98,40 +318,56 @@ SELECT
     revision,
     created_at,
     modified_at,
-    game_title,
-    game_title_sort,
-    game_publisher,
-    game_year,
+    COALESCE(games.title, projects.game_title) AS game_title,
+    projects.game_title_sort,
+    COALESCE(games.publisher, projects.game_publisher) AS game_publisher,
+    COALESCE(games.year, projects.game_year) AS game_year,
+    projects.game_id,
+    game_alternate_titles,
+    game_designers,
+    game_min_players,
+    game_max_players,
+    game_playing_time,
+    game_era,
+    game_external_id,
+    tags,
     image
 FROM projects
-ORDER BY "
-        )
-        .push(sort_by.field())
-        .push(" ")
-        .push(dir.dir())
-        .push(", project_id ")
-        .push(dir.dir())
-        .push(" LIMIT ")
-        .push_bind(limit)
-        .build_query_as::<ProjectSummaryRow>()
-        .fetch_all(ex)
-        .await?
+LEFT JOIN games
+ON projects.game_id = games.game_id
+WHERE deleted_at IS NULL"
+    );
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.push(" ORDER BY ")
+            .push(sort_by.field())
+            .push(" ")
+            .push(dir.dir())
+            .push(", project_id ")
+            .push(dir.dir())
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .build_query_as::<ProjectSummaryRow>()
+            .fetch_all(ex)
+            .await?
     )
 }
 
-pub async fn get_projects_query_end_window<'e, E>(
+pub async fn get_projects_query_end_window<'e, 'f, E>(
     ex: E,
-    query: &str,
+    query: &'f str,
     sort_by: SortBy,
     dir: Direction,
+    filters: &'f Filters,
     limit: u32
 ) -> Result<Vec<ProjectSummaryRow>, CoreError>
 where
     E: Executor<'e, Database = Sqlite>
 {
-    Ok(
-        QueryBuilder::new(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT
     fts.rank,
     projects.project_id,
@@ -140,28 +376,44 @@ SELECT
     projects.revision,
     projects.created_at,
     projects.modified_at,
-    projects.game_title,
+    COALESCE(games.title, projects.game_title) AS game_title,
     projects.game_title_sort,
-    projects.game_publisher,
-    projects.game_year,
+    COALESCE(games.publisher, projects.game_publisher) AS game_publisher,
+    COALESCE(games.year, projects.game_year) AS game_year,
+    projects.game_id,
+    projects.game_alternate_titles,
+    projects.game_designers,
+    projects.game_min_players,
+    projects.game_max_players,
+    projects.game_playing_time,
+    projects.game_era,
+    projects.game_external_id,
+    projects.tags,
     projects.image
 FROM projects
+LEFT JOIN games
+ON projects.game_id = games.game_id
 JOIN projects_fts AS fts
 ON projects.project_id = fts.rowid
 WHERE projects_fts MATCH "
-        )
-        .push_bind(query)
-        .push(" ORDER BY ")
-        .push(sort_by.field())
-        .push(" ")
-        .push(dir.dir())
-        .push(", projects.project_id ")
-        .push(dir.dir())
-        .push(" LIMIT ")
-        .push_bind(limit)
-        .build_query_as::<ProjectSummaryRow>()
-        .fetch_all(ex)
-        .await?
+    );
+
+    qb.push_bind(query);
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.push(" ORDER BY ")
+            .push(sort_by.field())
+            .push(" ")
+            .push(dir.dir())
+            .push(", projects.project_id ")
+            .push(dir.dir())
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .build_query_as::<ProjectSummaryRow>()
+            .fetch_all(ex)
+            .await?
     )
 }
 
@@ -171,15 +423,15 @@ pub async fn get_projects_mid_window<'e, 'f, E, F>(
     dir: Direction,
     field: &'f F,
     id: u32,
+    filters: &'f Filters,
     limit: u32
 ) -> Result<Vec<ProjectSummaryRow>, CoreError>
 where
     E: Executor<'e, Database = Sqlite>,
     F: Send + Sync + Encode<'f, Sqlite> + Type<Sqlite>
 {
-    Ok(
-        QueryBuilder::new(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT
     0.0 AS rank,
     project_id,
@@ -188,15 +440,27 @@ SELECT
     revision,
     created_at,
     modified_at,
-    game_title,
-    game_title_sort,
-    game_publisher,
-    game_year,
+    COALESCE(games.title, projects.game_title) AS game_title,
+    projects.game_title_sort,
+    COALESCE(games.publisher, projects.game_publisher) AS game_publisher,
+    COALESCE(games.year, projects.game_year) AS game_year,
+    projects.game_id,
+    game_alternate_titles,
+    game_designers,
+    game_min_players,
+    game_max_players,
+    game_playing_time,
+    game_era,
+    game_external_id,
+    tags,
     image
 FROM projects
-WHERE "
-        )
-        .push(sort_by.field())
+LEFT JOIN games
+ON projects.game_id = games.game_id
+WHERE deleted_at IS NULL AND ("
+    );
+
+    qb.push(sort_by.field())
         .push(" ")
         .push(dir.op())
         .push(" ")
@@ -209,20 +473,26 @@ WHERE "
         .push(dir.op())
         .push(" ")
         .push_bind(id)
-        .push(") ORDER BY ")
-        .push(sort_by.field())
-        .push(" ")
-        .push(dir.dir())
-        .push(", project_id ")
-        .push(dir.dir())
-        .push(" LIMIT ")
-        .push_bind(limit)
-        .build_query_as::<ProjectSummaryRow>()
-        .fetch_all(ex)
-        .await?
+        .push("))");
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.push(" ORDER BY ")
+            .push(sort_by.field())
+            .push(" ")
+            .push(dir.dir())
+            .push(", project_id ")
+            .push(dir.dir())
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .build_query_as::<ProjectSummaryRow>()
+            .fetch_all(ex)
+            .await?
     )
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn get_projects_query_mid_window<'e, 'f, E, F>(
     ex: E,
     query: &'f str,
@@ -230,6 +500,7 @@ pub async fn get_projects_query_mid_window<'e, 'f, E, F>(
     dir: Direction,
     field: &'f F,
     id: u32,
+    filters: &'f Filters,
     limit: u32
 ) -> Result<Vec<ProjectSummaryRow>, CoreError>
 where
@@ -239,9 +510,8 @@ where
     // We get rows from the FTS table in a subquery because the sqlite
     // query planner is confused by MATCH when it's used with boolean
     // connectives.
-    Ok(
-        QueryBuilder::new(
-            "
+    let mut qb = QueryBuilder::new(
+        "
 SELECT
     fts.rank,
     projects.project_id,
@@ -250,21 +520,33 @@ SELECT
     projects.revision,
     projects.created_at,
     projects.modified_at,
-    projects.game_title,
+    COALESCE(games.title, projects.game_title) AS game_title,
     projects.game_title_sort,
-    projects.game_publisher,
-    projects.game_year,
+    COALESCE(games.publisher, projects.game_publisher) AS game_publisher,
+    COALESCE(games.year, projects.game_year) AS game_year,
+    projects.game_id,
+    projects.game_alternate_titles,
+    projects.game_designers,
+    projects.game_min_players,
+    projects.game_max_players,
+    projects.game_playing_time,
+    projects.game_era,
+    projects.game_external_id,
+    projects.tags,
     projects.image
 FROM projects
+LEFT JOIN games
+ON projects.game_id = games.game_id
 JOIN (
     SELECT
         projects_fts.rowid,
         projects_fts.rank
     FROM projects_fts
     WHERE projects_fts MATCH "
-        )
-        .push_bind(query)
-        .push(") AS fts ON fts.rowid = projects.project_id WHERE ")
+    );
+
+    qb.push_bind(query)
+        .push(") AS fts ON fts.rowid = projects.project_id WHERE (")
         .push(sort_by.field())
         .push(dir.op())
         .push(" ")
@@ -277,17 +559,22 @@ JOIN (
         .push(dir.op())
         .push(" ")
         .push_bind(id)
-        .push(") ORDER BY ")
-        .push(sort_by.field())
-        .push(" ")
-        .push(dir.dir())
-        .push(", project_id ")
-        .push(dir.dir())
-        .push(" LIMIT ")
-        .push_bind(limit)
-        .build_query_as::<ProjectSummaryRow>()
-        .fetch_all(ex)
-        .await?
+        .push("))");
+
+    filters.push_where(&mut qb);
+
+    Ok(
+        qb.push(" ORDER BY ")
+            .push(sort_by.field())
+            .push(" ")
+            .push(dir.dir())
+            .push(", project_id ")
+            .push(dir.dir())
+            .push(" LIMIT ")
+            .push_bind(limit)
+            .build_query_as::<ProjectSummaryRow>()
+            .fetch_all(ex)
+            .await?
     )
 }
 
@@ -299,7 +586,7 @@ mod test {
 
     #[sqlx::test(fixtures("users", "projects"))]
     async fn get_projects_count_ok(pool: Pool) {
-        assert_eq!(get_projects_count(&pool).await.unwrap(), 2);
+        assert_eq!(get_projects_count(&pool, &Filters::default()).await.unwrap(), 2);
     }
 
     #[track_caller]
@@ -318,7 +605,7 @@ mod test {
     async fn get_projects_end_window_asc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, 3
+                &pool, SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -328,7 +615,7 @@ mod test {
     async fn get_projects_end_window_asc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, 3
+                &pool, SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
             ).await,
             &["a", "b", "c"]
         );
@@ -338,7 +625,7 @@ mod test {
     async fn get_projects_end_window_asc_past_end(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, 5
+                &pool, SortBy::ProjectName, Direction::Ascending, &Filters::default(), 5
             ).await,
             &["a", "b", "c", "d"]
         );
@@ -348,7 +635,7 @@ mod test {
     async fn get_projects_end_window_desc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Descending, 3
+                &pool, SortBy::ProjectName, Direction::Descending, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -358,7 +645,7 @@ mod test {
     async fn get_projects_end_window_desc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Descending, 3
+                &pool, SortBy::ProjectName, Direction::Descending, &Filters::default(), 3
             ).await,
             &["d", "c", "b"]
         );
@@ -368,7 +655,7 @@ mod test {
     async fn get_projects_end_window_desc_past_start(pool: Pool) {
         assert_projects_window(
             get_projects_end_window(
-                &pool, SortBy::ProjectName, Direction::Descending, 5
+                &pool, SortBy::ProjectName, Direction::Descending, &Filters::default(), 5
             ).await,
             &["d", "c", "b", "a"]
         );
@@ -378,7 +665,7 @@ mod test {
     async fn get_projects_mid_window_asc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, &"a", 1, 3
+                &pool, SortBy::ProjectName, Direction::Ascending, &"a", 1, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -388,7 +675,7 @@ mod test {
     async fn get_projects_mid_window_asc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, &"b", 2, 3
+                &pool, SortBy::ProjectName, Direction::Ascending, &"b", 2, &Filters::default(), 3
             ).await,
             &["c", "d"]
         );
@@ -398,7 +685,7 @@ mod test {
     async fn get_projects_mid_window_asc_past_end(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Ascending, &"d", 4, 3
+                &pool, SortBy::ProjectName, Direction::Ascending, &"d", 4, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -408,7 +695,7 @@ mod test {
     async fn get_projects_mid_window_desc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Descending, &"a", 1, 3
+                &pool, SortBy::ProjectName, Direction::Descending, &"a", 1, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -418,7 +705,7 @@ mod test {
     async fn get_projects_mid_window_desc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Descending, &"b", 2, 3
+                &pool, SortBy::ProjectName, Direction::Descending, &"b", 2, &Filters::default(), 3
             ).await,
             &["a"]
         );
@@ -428,7 +715,7 @@ mod test {
     async fn get_projects_mid_window_desc_past_start(pool: Pool) {
         assert_projects_window(
             get_projects_mid_window(
-                &pool, SortBy::ProjectName, Direction::Descending, &"d", 4, 3
+                &pool, SortBy::ProjectName, Direction::Descending, &"d", 4, &Filters::default(), 3
             ).await,
             &["c", "b", "a"]
         );
@@ -438,7 +725,7 @@ mod test {
     async fn get_projects_query_end_window_asc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -448,7 +735,7 @@ mod test {
     async fn get_projects_query_end_window_asc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, 1
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &Filters::default(), 1
             ).await,
             &["a"]
         );
@@ -458,7 +745,7 @@ mod test {
     async fn get_projects_query_end_window_asc_past_end(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, 5
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &Filters::default(), 5
             ).await,
             &["a", "c", "d"]
         );
@@ -468,7 +755,7 @@ mod test {
     async fn get_projects_query_end_window_desc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -478,7 +765,7 @@ mod test {
     async fn get_projects_query_end_window_desc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, 1
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &Filters::default(), 1
             ).await,
             &["d"]
         );
@@ -488,7 +775,7 @@ mod test {
     async fn get_projects_query_end_window_desc_past_start(pool: Pool) {
         assert_projects_window(
             get_projects_query_end_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, 5
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &Filters::default(), 5
             ).await,
             &["d", "c", "a"]
         );
@@ -498,7 +785,7 @@ mod test {
     async fn get_projects_query_mid_window_asc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"a", 1, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"a", 1, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -508,7 +795,7 @@ mod test {
     async fn get_projects_query_mid_window_asc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"b", 2, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"b", 2, &Filters::default(), 3
             ).await,
             &["c", "d"]
         );
@@ -518,7 +805,7 @@ mod test {
     async fn get_projects_query_mid_window_asc_past_end(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"d", 4, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &"d", 4, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -528,7 +815,7 @@ mod test {
     async fn get_projects_query_mid_window_desc_empty(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"a", 1, 3
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"a", 1, &Filters::default(), 3
             ).await,
             &[]
         );
@@ -538,7 +825,7 @@ mod test {
     async fn get_projects_query_mid_window_desc_not_all(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"d", 4, 1
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"d", 4, &Filters::default(), 1
             ).await,
             &["c"]
         );
@@ -548,9 +835,419 @@ mod test {
     async fn get_projects_query_mid_window_desc_past_start(pool: Pool) {
         assert_projects_window(
             get_projects_query_mid_window(
-                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"d", 4, 5
+                &pool, "abc", SortBy::ProjectName, Direction::Descending, &"d", 4, &Filters::default(), 5
             ).await,
             &["c", "a"]
         );
     }
+
+    async fn delete(pool: &Pool, id: i64) {
+        crate::sqlite::project::delete_project(
+            pool,
+            crate::model::Owner(1),
+            crate::model::Project(id),
+            1
+        ).await.unwrap();
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window"))]
+    async fn get_projects_count_deleted(pool: Pool) {
+        delete(&pool, 2).await;
+        assert_eq!(get_projects_count(&pool, &Filters::default()).await.unwrap(), 3);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window"))]
+    async fn get_projects_end_window_deleted(pool: Pool) {
+        delete(&pool, 2).await;
+        assert_projects_window(
+            get_projects_end_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
+            ).await,
+            &["a", "c", "d"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window"))]
+    async fn get_projects_mid_window_deleted(pool: Pool) {
+        delete(&pool, 3).await;
+        assert_projects_window(
+            get_projects_mid_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &"b", 2, &Filters::default(), 3
+            ).await,
+            &["d"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_query_count_deleted(pool: Pool) {
+        delete(&pool, 3).await;
+        assert_eq!(get_projects_query_count(&pool, "abc", &Filters::default()).await.unwrap(), 2);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_query_count_package_release(pool: Pool) {
+        let q = "package_names : \"eia\" AND release_filenames : \"eia_v3.vmod\"";
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 0);
+
+        sqlx::query(
+            "
+INSERT INTO packages (package_id, project_id, name, created_at, created_by)
+VALUES (1, 2, 'eia', 0, 1);
+
+INSERT INTO releases (
+    package_id,
+    version,
+    version_major,
+    version_minor,
+    version_patch,
+    version_pre,
+    version_build,
+    url,
+    filename,
+    size,
+    checksum,
+    published_at,
+    published_by
+)
+VALUES (1, '3.0.0', 3, 0, 0, '', '', '', 'eia_v3.vmod', 0, '', 0, 1);
+            "
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 1);
+
+        sqlx::query("DELETE FROM releases; DELETE FROM packages;")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 0);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_query_count_owner(pool: Pool) {
+        let q = "owner_usernames : \"chuck\"";
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 0);
+
+        crate::sqlite::users::add_owner(
+            &pool,
+            crate::model::User(3),
+            crate::model::Project(2)
+        ).await.unwrap();
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 1);
+
+        sqlx::query("UPDATE users SET username = 'charles' WHERE user_id = 3")
+            .execute(&pool)
+            .await
+            .unwrap();
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 0);
+
+        let q = "owner_usernames : \"charles\"";
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 1);
+
+        crate::sqlite::users::remove_owner(
+            &pool,
+            crate::model::User(3),
+            crate::model::Project(2)
+        ).await.unwrap();
+        assert_eq!(get_projects_query_count(&pool, q, &Filters::default()).await.unwrap(), 0);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_query_end_window_deleted(pool: Pool) {
+        delete(&pool, 1).await;
+        assert_projects_window(
+            get_projects_query_end_window(
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
+            ).await,
+            &["c", "d"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_query_end_window_restored(pool: Pool) {
+        delete(&pool, 1).await;
+        crate::sqlite::project::restore_project(
+            &pool,
+            crate::model::Project(1),
+            0
+        ).await.unwrap();
+
+        assert_projects_window(
+            get_projects_query_end_window(
+                &pool, "abc", SortBy::ProjectName, Direction::Ascending, &Filters::default(), 3
+            ).await,
+            &["a", "c", "d"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_metadata"))]
+    async fn get_projects_count_filters(pool: Pool) {
+        let filters = Filters {
+            designer: Some("jim dunnigan".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 2);
+
+        let filters = Filters {
+            era: Some("WWII".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 2);
+
+        let filters = Filters {
+            external_id: Some("bgg:3".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 1);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_metadata"))]
+    async fn get_projects_end_window_players(pool: Pool) {
+        let filters = Filters {
+            players: Some(3),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_end_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &filters, 3
+            ).await,
+            &["a"]
+        );
+
+        let filters = Filters {
+            players: Some(1),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_end_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &filters, 3
+            ).await,
+            &["b"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_metadata"))]
+    async fn get_projects_mid_window_filters(pool: Pool) {
+        let filters = Filters {
+            designer: Some("Richard Berg".into()),
+            max_playing_time: Some(600),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_mid_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &"a", 1, &filters, 3
+            ).await,
+            &["b"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_metadata"))]
+    async fn get_projects_query_count_designer(pool: Pool) {
+        assert_eq!(
+            get_projects_query_count(&pool, "berg", &Filters::default())
+                .await
+                .unwrap(),
+            2
+        );
+
+        let filters = Filters {
+            era: Some("napoleonic".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_projects_query_count(&pool, "berg", &filters).await.unwrap(),
+            1
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_metadata"))]
+    async fn get_projects_query_count_alternate_title(pool: Pool) {
+        let filters = Filters::default();
+        assert_eq!(
+            get_projects_query_count(&pool, "\"eia\"", &filters).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            get_projects_query_count(
+                &pool,
+                "{game_title game_alternate_titles} : \"eia\"",
+                &filters
+            ).await.unwrap(),
+            1
+        );
+        assert_eq!(
+            get_projects_query_count(&pool, "game_title : \"eia\"", &filters)
+                .await
+                .unwrap(),
+            0
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_count_facet_filters(pool: Pool) {
+        let filters = Filters {
+            publisher: Some("gmt".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 3);
+
+        let filters = Filters {
+            tag: Some("WWII".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 2);
+
+        let filters = Filters {
+            owner: Some("bob".into()),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 2);
+
+        let filters = Filters {
+            has_release: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(get_projects_count(&pool, &filters).await.unwrap(), 3);
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_end_window_years(pool: Pool) {
+        let filters = Filters {
+            year_min: Some(1980),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_end_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &filters, 3
+            ).await,
+            &["b", "d"]
+        );
+
+        let filters = Filters {
+            year_min: Some(1979),
+            year_max: Some(1990),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_end_window(
+                &pool, SortBy::ProjectName, Direction::Ascending, &filters, 3
+            ).await,
+            &["a", "b"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_mid_window_has_release(pool: Pool) {
+        let filters = Filters {
+            publisher: Some("GMT".into()),
+            has_release: Some(true),
+            ..Default::default()
+        };
+        assert_projects_window(
+            get_projects_mid_window(
+                &pool, SortBy::ProjectName, Direction::Descending, &"b", 2, &filters, 3
+            ).await,
+            &["a"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_facet_ok(pool: Pool) {
+        let filters = Filters::default();
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Publisher, None, &filters, 10)
+                .await
+                .unwrap(),
+            [("GMT".into(), 3), ("Avalon Hill".into(), 1)]
+        );
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Decade, None, &filters, 10)
+                .await
+                .unwrap(),
+            [("1970s".into(), 1), ("1980s".into(), 1), ("2000s".into(), 1)]
+        );
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Tag, None, &filters, 2)
+                .await
+                .unwrap(),
+            [("WWII".into(), 2), ("card driven".into(), 1)]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_facet_query(pool: Pool) {
+        let filters = Filters {
+            has_release: Some(false),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Publisher, Some("gmt"), &filters, 10)
+                .await
+                .unwrap(),
+            [("GMT".into(), 2)]
+        );
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Tag, Some("gmt"), &filters, 10)
+                .await
+                .unwrap(),
+            [("card driven".into(), 1)]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_facet_deleted(pool: Pool) {
+        delete(&pool, 1).await;
+
+        assert_eq!(
+            get_projects_facet(&pool, Facet::Decade, None, &Filters::default(), 10)
+                .await
+                .unwrap(),
+            [("1980s".into(), 1), ("2000s".into(), 1)]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn get_projects_highlights_ok(pool: Pool) {
+        let rows = get_projects_highlights(
+            &pool, "abc", &["a".into(), "b".into(), "c".into()]
+        ).await.unwrap();
+
+        // b does not match, so has no highlights
+        assert_eq!(
+            rows.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(),
+            ["a", "c"]
+        );
+        assert_eq!(
+            rows[0].description,
+            format!("{HIGHLIGHT_START}abc{HIGHLIGHT_END} xyz")
+        );
+        assert_eq!(rows[0].title, "");
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_projects_query_count_parsed(pool: Pool) {
+        let query = crate::search::parse_query("publisher:gm").unwrap();
+        assert_eq!(
+            get_projects_query_count(&pool, &query, &Filters::default())
+                .await
+                .unwrap(),
+            3
+        );
+
+        let query = crate::search::parse_query("AND -").unwrap();
+        assert_eq!(
+            get_projects_query_count(&pool, &query, &Filters::default())
+                .await
+                .unwrap(),
+            0
+        );
+    }
 }
@@ -0,0 +1,308 @@
+use sqlx::{
+    Acquire, Executor, Transaction,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    model::Project
+};
+
+async fn get_deleted_projects<'e, E>(
+    ex: E,
+    before: i64
+) -> Result<Vec<Project>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_scalar!(
+            "
+SELECT project_id
+FROM projects
+WHERE deleted_at <= ?
+ORDER BY project_id
+            ",
+            before
+        )
+        .fetch_all(ex)
+        .await?
+        .into_iter()
+        .map(Project)
+        .collect()
+    )
+}
+
+async fn get_blob_urls<'e, E>(
+    ex: E,
+    proj: Project
+) -> Result<Vec<String>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_scalar!(
+            "
+SELECT url
+FROM image_revisions
+WHERE project_id = ?
+UNION
+SELECT releases.url
+FROM releases
+JOIN packages
+ON releases.package_id = packages.package_id
+WHERE packages.project_id = ?
+UNION
+SELECT files.url
+FROM files
+JOIN packages
+ON files.package_id = packages.package_id
+WHERE packages.project_id = ?
+ORDER BY url
+            ",
+            proj.0,
+            proj.0,
+            proj.0
+        )
+        .fetch_all(ex)
+        .await?
+    )
+}
+
+async fn blob_url_in_use<'e, E>(
+    ex: E,
+    url: &str
+) -> Result<bool, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query!(
+            "
+SELECT 1 AS present
+FROM image_revisions
+WHERE url = ?
+UNION ALL
+SELECT 1 AS present
+FROM releases
+WHERE url = ?
+UNION ALL
+SELECT 1 AS present
+FROM files
+WHERE url = ?
+LIMIT 1
+            ",
+            url,
+            url,
+            url
+        )
+        .fetch_optional(ex)
+        .await?
+        .is_some()
+    )
+}
+
+async fn purge_project(
+    tx: &mut Transaction<'_, Sqlite>,
+    proj: Project
+) -> Result<Vec<String>, CoreError>
+{
+    let urls = get_blob_urls(&mut **tx, proj).await?;
+
+    // Rows are removed so that no foreign key is left dangling; the
+    // project image is cleared first because projects and images refer
+    // to each other.
+    sqlx::query!(
+        "
+DELETE FROM authors
+WHERE release_id IN (
+    SELECT releases.release_id
+    FROM releases
+    JOIN packages
+    ON releases.package_id = packages.package_id
+    WHERE packages.project_id = ?
+);
+
+DELETE FROM releases
+WHERE package_id IN (
+    SELECT package_id
+    FROM packages
+    WHERE project_id = ?
+);
+
+DELETE FROM files
+WHERE package_id IN (
+    SELECT package_id
+    FROM packages
+    WHERE project_id = ?
+);
+
+DELETE FROM packages WHERE project_id = ?;
+DELETE FROM owners WHERE project_id = ?;
+DELETE FROM players WHERE project_id = ?;
+DELETE FROM gallery_items WHERE project_id = ?;
+DELETE FROM project_aliases WHERE project_id = ?;
+DELETE FROM project_revisions WHERE project_id = ?;
+UPDATE projects SET image = NULL WHERE project_id = ?;
+DELETE FROM project_data WHERE project_id = ?;
+DELETE FROM image_revisions WHERE project_id = ?;
+DELETE FROM images WHERE project_id = ?;
+DELETE FROM projects WHERE project_id = ?;
+        ",
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0,
+        proj.0
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(urls)
+}
+
+pub async fn purge_projects<'a, A>(
+    conn: A,
+    before: i64
+) -> Result<Vec<String>, CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    let mut urls = vec![];
+    for proj in get_deleted_projects(&mut *tx, before).await? {
+        urls.append(&mut purge_project(&mut tx, proj).await?);
+    }
+
+    // blobs may be shared with projects which remain, e.g., images
+    // uploaded under the same filename, so only those no longer referred
+    // to by any row are returned for removal
+    urls.sort_unstable();
+    urls.dedup();
+
+    let mut unused = vec![];
+    for url in urls {
+        if !blob_url_in_use(&mut *tx, &url).await? {
+            unused.push(url);
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(unused)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        model::Owner,
+        sqlite::project::{delete_project, get_project_row}
+    };
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "packages", "images"))]
+    async fn purge_projects_ok(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            purge_projects(&pool, 1703980420641538066).await.unwrap(),
+            [
+                "https://example.com/a_package-1.2.3",
+                "https://example.com/a_package-1.2.4",
+                "https://example.com/c_package-0.1.0",
+                "https://example.com/images/img.png"
+            ]
+        );
+
+        assert_eq!(
+            get_project_row(&pool, Project(42)).await.unwrap_err(),
+            CoreError::NotAProject
+        );
+
+        // other projects are untouched
+        get_project_row(&pool, Project(6)).await.unwrap();
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "packages", "images"))]
+    async fn purge_projects_shared_filename(pool: Pool) {
+        // another project has an image with the same filename, so stored
+        // at the same URL
+        sqlx::query(
+            "
+INSERT INTO image_revisions (
+    project_id,
+    filename,
+    url,
+    content_type,
+    size,
+    published_at,
+    published_by
+)
+VALUES (
+    6,
+    'img.png',
+    'https://example.com/images/img.png',
+    'image/png',
+    1234,
+    1694804206419538067,
+    1
+)
+            "
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            purge_projects(&pool, 1703980420641538066).await.unwrap(),
+            [
+                "https://example.com/a_package-1.2.3",
+                "https://example.com/a_package-1.2.4",
+                "https://example.com/c_package-0.1.0"
+            ]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "one_owner", "packages", "images"))]
+    async fn purge_projects_in_grace_period(pool: Pool) {
+        delete_project(&pool, Owner(1), Project(42), 1703980420641538066)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            purge_projects(&pool, 1703980420641538065).await.unwrap(),
+            Vec::<String>::new()
+        );
+
+        get_project_row(&pool, Project(42)).await.unwrap();
+    }
+
+    #[sqlx::test(fixtures("users", "projects", "packages"))]
+    async fn purge_projects_none_deleted(pool: Pool) {
+        assert_eq!(
+            purge_projects(&pool, i64::MAX).await.unwrap(),
+            Vec::<String>::new()
+        );
+
+        get_project_row(&pool, Project(42)).await.unwrap();
+    }
+}
@@ -624,7 +624,7 @@ mod test {
                     "https://example.com/new_thing.vmod",
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -680,7 +680,7 @@ mod test {
                     "https://example.com/new_thing.vmod",
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -708,7 +708,7 @@ mod test {
                     "https://example.com/new_thing.vmod",
                     0
                 ).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::VersionExists
             )
         );
     }
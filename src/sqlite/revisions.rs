@@ -0,0 +1,284 @@
+use sqlx::{
+    Executor,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    db::RevisionSummaryRow,
+    model::Project,
+    pagination::Direction
+};
+
+pub async fn get_revisions_count<'e, E>(
+    ex: E,
+    proj: Project
+) -> Result<i64, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query_scalar!(
+            r#"
+SELECT COUNT(1) AS "count!: i64"
+FROM project_revisions
+WHERE project_id = ?
+            "#,
+            proj.0
+        )
+        .fetch_one(ex)
+        .await?
+    )
+}
+
+// Changes are inferred by comparing each revision with its predecessor
+// and by finding packages, releases, and images published at the time
+// the revision was made.
+
+pub async fn get_revisions_window<'e, E>(
+    ex: E,
+    proj: Project,
+    dir: Direction,
+    revision: i64,
+    limit: u32
+) -> Result<Vec<RevisionSummaryRow>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        match dir {
+            Direction::Descending => sqlx::query_as!(
+                RevisionSummaryRow,
+                r#"
+SELECT
+    r.revision,
+    r.modified_at,
+    users.username AS modified_by,
+    p.revision IS NULL AS "created!: bool",
+    p.project_data_id IS NOT NULL
+        AND p.project_data_id != r.project_data_id AS "metadata!: bool",
+    p.name IS NOT NULL AND p.name != r.name AS "renamed!: bool",
+    EXISTS(
+        SELECT 1
+        FROM image_revisions
+        WHERE image_revisions.project_id = r.project_id
+            AND image_revisions.published_at = r.modified_at
+    ) AS "image!: bool",
+    EXISTS(
+        SELECT 1
+        FROM packages
+        WHERE packages.project_id = r.project_id
+            AND packages.created_at = r.modified_at
+    ) AS "package!: bool",
+    EXISTS(
+        SELECT 1
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = r.project_id
+            AND releases.published_at = r.modified_at
+    ) AS "release!: bool"
+FROM project_revisions AS r
+JOIN users
+ON r.modified_by = users.user_id
+LEFT JOIN project_revisions AS p
+ON p.project_id = r.project_id
+    AND p.revision = r.revision - 1
+WHERE r.project_id = ?
+    AND r.revision < ?
+ORDER BY r.revision DESC
+LIMIT ?
+                "#,
+                proj.0,
+                revision,
+                limit
+            )
+            .fetch_all(ex)
+            .await?,
+            Direction::Ascending => sqlx::query_as!(
+                RevisionSummaryRow,
+                r#"
+SELECT
+    r.revision,
+    r.modified_at,
+    users.username AS modified_by,
+    p.revision IS NULL AS "created!: bool",
+    p.project_data_id IS NOT NULL
+        AND p.project_data_id != r.project_data_id AS "metadata!: bool",
+    p.name IS NOT NULL AND p.name != r.name AS "renamed!: bool",
+    EXISTS(
+        SELECT 1
+        FROM image_revisions
+        WHERE image_revisions.project_id = r.project_id
+            AND image_revisions.published_at = r.modified_at
+    ) AS "image!: bool",
+    EXISTS(
+        SELECT 1
+        FROM packages
+        WHERE packages.project_id = r.project_id
+            AND packages.created_at = r.modified_at
+    ) AS "package!: bool",
+    EXISTS(
+        SELECT 1
+        FROM releases
+        JOIN packages
+        ON releases.package_id = packages.package_id
+        WHERE packages.project_id = r.project_id
+            AND releases.published_at = r.modified_at
+    ) AS "release!: bool"
+FROM project_revisions AS r
+JOIN users
+ON r.modified_by = users.user_id
+LEFT JOIN project_revisions AS p
+ON p.project_id = r.project_id
+    AND p.revision = r.revision - 1
+WHERE r.project_id = ?
+    AND r.revision > ?
+ORDER BY r.revision ASC
+LIMIT ?
+                "#,
+                proj.0,
+                revision,
+                limit
+            )
+            .fetch_all(ex)
+            .await?
+        }
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    fn rev(revision: i64, modified_at: i64) -> RevisionSummaryRow {
+        RevisionSummaryRow {
+            revision,
+            modified_at,
+            modified_by: "bob".into(),
+            created: false,
+            metadata: false,
+            renamed: false,
+            image: false,
+            package: false,
+            release: false
+        }
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_count_ok(pool: Pool) {
+        assert_eq!(
+            get_revisions_count(&pool, Project(42)).await.unwrap(),
+            6
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_count_not_a_project(pool: Pool) {
+        assert_eq!(
+            get_revisions_count(&pool, Project(0)).await.unwrap(),
+            0
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_window_descending_start(pool: Pool) {
+        assert_eq!(
+            get_revisions_window(
+                &pool,
+                Project(42),
+                Direction::Descending,
+                i64::MAX,
+                10
+            ).await.unwrap(),
+            vec![
+                RevisionSummaryRow {
+                    image: true,
+                    ..rev(6, 1702656000000000000)
+                },
+                RevisionSummaryRow {
+                    release: true,
+                    ..rev(5, 1702655000000000000)
+                },
+                RevisionSummaryRow {
+                    package: true,
+                    ..rev(4, 1702654000000000000)
+                },
+                RevisionSummaryRow {
+                    renamed: true,
+                    ..rev(3, 1702653000000000000)
+                },
+                RevisionSummaryRow {
+                    metadata: true,
+                    ..rev(2, 1702652000000000000)
+                },
+                RevisionSummaryRow {
+                    created: true,
+                    ..rev(1, 1702651000000000000)
+                }
+            ]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_window_descending_mid(pool: Pool) {
+        assert_eq!(
+            get_revisions_window(
+                &pool,
+                Project(42),
+                Direction::Descending,
+                4,
+                2
+            ).await.unwrap(),
+            vec![
+                RevisionSummaryRow {
+                    renamed: true,
+                    ..rev(3, 1702653000000000000)
+                },
+                RevisionSummaryRow {
+                    metadata: true,
+                    ..rev(2, 1702652000000000000)
+                }
+            ]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_window_ascending_mid(pool: Pool) {
+        assert_eq!(
+            get_revisions_window(
+                &pool,
+                Project(42),
+                Direction::Ascending,
+                4,
+                2
+            ).await.unwrap(),
+            vec![
+                RevisionSummaryRow {
+                    release: true,
+                    ..rev(5, 1702655000000000000)
+                },
+                RevisionSummaryRow {
+                    image: true,
+                    ..rev(6, 1702656000000000000)
+                }
+            ]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "revisions"))]
+    async fn get_revisions_window_not_a_project(pool: Pool) {
+        assert_eq!(
+            get_revisions_window(
+                &pool,
+                Project(0),
+                Direction::Descending,
+                i64::MAX,
+                10
+            ).await.unwrap(),
+            vec![]
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use sqlx::{
+    Acquire, Executor,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    db::SearchIndexMismatchRow
+};
+
+// The full-text indices, with the columns of projects which they index
+const FTS_TABLES: &[(&str, &[&str])] = &[
+    (
+        "projects_fts",
+        &[
+            "game_title",
+            "game_publisher",
+            "game_year",
+            "description",
+            "readme",
+            "game_designers",
+            "game_era",
+            "package_names",
+            "release_filenames",
+            "owner_usernames",
+            "game_alternate_titles"
+        ]
+    ),
+    (
+        "search_suggestions_fts",
+        &["name", "game_title", "game_publisher"]
+    )
+];
+
+pub async fn set_search_rank<'e, E>(
+    ex: E,
+    rank: &str
+) -> Result<(), CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    sqlx::query!(
+        "
+INSERT INTO projects_fts (
+    projects_fts,
+    rank
+)
+VALUES ('rank', ?)
+        ",
+        rank
+    )
+    .execute(ex)
+    .await?;
+
+    Ok(())
+}
+
+async fn get_fts_mismatches<'e, E>(
+    ex: &mut E,
+    table: &str,
+    columns: &[&str]
+) -> Result<Vec<i64>, CoreError>
+where
+    for<'a> &'a mut E: Executor<'a, Database = Sqlite>
+{
+    let columns = columns.join(", ");
+
+    // index the current projects afresh in a scratch table; a row is
+    // mismatched if its terms in the index differ from those in the
+    // scratch table
+    sqlx::query(&format!(
+        "CREATE VIRTUAL TABLE temp.fts_check USING fts5({columns}, content='')"
+    ))
+    .execute(&mut *ex)
+    .await?;
+
+    sqlx::query(&format!(
+        "
+INSERT INTO temp.fts_check (rowid, {columns})
+SELECT project_id, {columns}
+FROM projects
+WHERE deleted_at IS NULL
+        "
+    ))
+    .execute(&mut *ex)
+    .await?;
+
+    sqlx::query(&format!(
+        "CREATE VIRTUAL TABLE temp.fts_index_vocab USING fts5vocab(main, {table}, instance)"
+    ))
+    .execute(&mut *ex)
+    .await?;
+
+    sqlx::query(
+        "CREATE VIRTUAL TABLE temp.fts_check_vocab USING fts5vocab(temp, fts_check, instance)"
+    )
+    .execute(&mut *ex)
+    .await?;
+
+    let docs = sqlx::query_scalar::<_, i64>(
+        "
+SELECT doc FROM (
+    SELECT term, doc, col, offset FROM temp.fts_index_vocab
+    EXCEPT
+    SELECT term, doc, col, offset FROM temp.fts_check_vocab
+)
+UNION
+SELECT doc FROM (
+    SELECT term, doc, col, offset FROM temp.fts_check_vocab
+    EXCEPT
+    SELECT term, doc, col, offset FROM temp.fts_index_vocab
+)
+ORDER BY doc
+        "
+    )
+    .fetch_all(&mut *ex)
+    .await?;
+
+    for scratch in ["fts_check_vocab", "fts_index_vocab", "fts_check"] {
+        sqlx::query(&format!("DROP TABLE temp.{scratch}"))
+            .execute(&mut *ex)
+            .await?;
+    }
+
+    Ok(docs)
+}
+
+async fn rebuild_fts<'e, E>(
+    ex: &mut E,
+    table: &str,
+    columns: &[&str]
+) -> Result<(), CoreError>
+where
+    for<'a> &'a mut E: Executor<'a, Database = Sqlite>
+{
+    let columns = columns.join(", ");
+
+    // the FTS rebuild command would index deleted projects, so the index
+    // is emptied and refilled instead
+    sqlx::query(&format!(
+        "INSERT INTO {table} ({table}) VALUES ('delete-all')"
+    ))
+    .execute(&mut *ex)
+    .await?;
+
+    sqlx::query(&format!(
+        "
+INSERT INTO {table} (rowid, {columns})
+SELECT project_id, {columns}
+FROM projects
+WHERE deleted_at IS NULL
+        "
+    ))
+    .execute(&mut *ex)
+    .await?;
+
+    Ok(())
+}
+
+async fn get_mismatches<'e, E>(
+    ex: &mut E
+) -> Result<Vec<SearchIndexMismatchRow>, CoreError>
+where
+    for<'a> &'a mut E: Executor<'a, Database = Sqlite>
+{
+    let mut ids = vec![];
+    for (table, columns) in FTS_TABLES {
+        ids.extend(get_fts_mismatches(ex, table, columns).await?);
+    }
+    ids.sort_unstable();
+    ids.dedup();
+
+    // purged projects have no names
+    let mut mismatches = Vec::with_capacity(ids.len());
+    for id in ids {
+        let name = sqlx::query_scalar!(
+            "
+SELECT name
+FROM projects
+WHERE project_id = ?
+            ",
+            id
+        )
+        .fetch_optional(&mut *ex)
+        .await?;
+
+        mismatches.push(SearchIndexMismatchRow { project_id: id, name });
+    }
+
+    Ok(mismatches)
+}
+
+// Returns the rows which mismatched before the rebuild and those which
+// still mismatch after it
+pub async fn reindex_search<'a, A>(
+    conn: A
+) -> Result<(Vec<SearchIndexMismatchRow>, Vec<SearchIndexMismatchRow>), CoreError>
+where
+    A: Acquire<'a, Database = Sqlite>
+{
+    let mut tx = conn.begin().await?;
+
+    let before = get_mismatches(&mut *tx).await?;
+
+    for (table, columns) in FTS_TABLES {
+        rebuild_fts(&mut *tx, table, columns).await?;
+    }
+
+    let after = get_mismatches(&mut *tx).await?;
+
+    tx.commit().await?;
+
+    Ok((before, after))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn reindex_search_ok(pool: Pool) {
+        assert_eq!(
+            reindex_search(&pool).await.unwrap(),
+            (vec![], vec![])
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn reindex_search_drift(pool: Pool) {
+        // make the index drift by changing a project behind its back
+        sqlx::query("DROP TRIGGER projects_au")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        sqlx::query("UPDATE projects SET description = 'xyz' WHERE project_id = 3")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            reindex_search(&pool).await.unwrap(),
+            (
+                vec![
+                    SearchIndexMismatchRow {
+                        project_id: 3,
+                        name: Some("c".into())
+                    }
+                ],
+                vec![]
+            )
+        );
+
+        // the rebuilt index finds the new description
+        assert_eq!(
+            sqlx::query_scalar::<_, i64>(
+                "SELECT rowid FROM projects_fts WHERE projects_fts MATCH 'xyz' ORDER BY rowid"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap(),
+            [1, 3]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn reindex_search_excludes_deleted(pool: Pool) {
+        crate::sqlite::project::delete_project(
+            &pool,
+            crate::model::Owner(1),
+            crate::model::Project(1),
+            1
+        ).await.unwrap();
+
+        assert_eq!(
+            reindex_search(&pool).await.unwrap(),
+            (vec![], vec![])
+        );
+
+        assert_eq!(
+            sqlx::query_scalar::<_, i64>(
+                "SELECT rowid FROM projects_fts WHERE projects_fts MATCH 'abc' ORDER BY rowid"
+            )
+            .fetch_all(&pool)
+            .await
+            .unwrap(),
+            [3, 4]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_query_window"))]
+    async fn set_search_rank_ok(pool: Pool) {
+        set_search_rank(&pool, "bm25(1.0, 1.0, 1.0, 10.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0)")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            sqlx::query_scalar::<_, String>(
+                "SELECT v FROM projects_fts_config WHERE k = 'rank'"
+            )
+            .fetch_one(&pool)
+            .await
+            .unwrap(),
+            "bm25(1.0, 1.0, 1.0, 10.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0)"
+        );
+    }
+}
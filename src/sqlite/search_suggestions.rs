@@ -0,0 +1,109 @@
+use sqlx::{
+    Executor, QueryBuilder,
+    sqlite::Sqlite
+};
+
+use crate::{
+    core::CoreError,
+    search::SuggestionField
+};
+
+impl SuggestionField {
+    fn column(&self) -> &'static str {
+        match self {
+            SuggestionField::ProjectName => "name",
+            SuggestionField::GameTitle => "game_title",
+            SuggestionField::GamePublisher => "game_publisher"
+        }
+    }
+}
+
+pub async fn get_search_suggestions<'e, 'f, E>(
+    ex: E,
+    field: SuggestionField,
+    query: &'f str,
+    limit: u32
+) -> Result<Vec<String>, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    // values which differ only by case are suggested once
+    let mut qb = QueryBuilder::new("SELECT MIN(");
+
+    Ok(
+        qb.push(field.column())
+            .push(") FROM search_suggestions_fts WHERE search_suggestions_fts MATCH ")
+            .push_bind(format!("{} : ({query})", field.column()))
+            .push(" GROUP BY ")
+            .push(field.column())
+            .push(" COLLATE NOCASE ORDER BY MIN(rank) LIMIT ")
+            .push_bind(limit)
+            .build_query_scalar::<String>()
+            .fetch_all(ex)
+            .await?
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Pool = sqlx::Pool<Sqlite>;
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_search_suggestions_publishers(pool: Pool) {
+        assert_eq!(
+            get_search_suggestions(
+                &pool, SuggestionField::GamePublisher, "\"g\"*", 10
+            ).await.unwrap(),
+            ["GMT"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_search_suggestions_titles(pool: Pool) {
+        assert_eq!(
+            get_search_suggestions(
+                &pool, SuggestionField::GameTitle, "\"tes\"*", 10
+            ).await.unwrap(),
+            ["A Game of Tests"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_search_suggestions_projects(pool: Pool) {
+        assert_eq!(
+            get_search_suggestions(
+                &pool, SuggestionField::ProjectName, "\"test\" AND \"g\"*", 10
+            ).await.unwrap(),
+            ["test_game"]
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "projects"))]
+    async fn get_search_suggestions_none(pool: Pool) {
+        assert_eq!(
+            get_search_suggestions(
+                &pool, SuggestionField::ProjectName, "\"xyz\"*", 10
+            ).await.unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[sqlx::test(fixtures("users", "proj_window", "proj_facets"))]
+    async fn get_search_suggestions_deleted(pool: Pool) {
+        crate::sqlite::project::delete_project(
+            &pool,
+            crate::model::Owner(1),
+            crate::model::Project(2),
+            1
+        ).await.unwrap();
+
+        assert_eq!(
+            get_search_suggestions(
+                &pool, SuggestionField::GamePublisher, "\"ava\"*", 10
+            ).await.unwrap(),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -83,6 +83,29 @@ LIMIT 1
     )
 }
 
+pub async fn user_is_admin<'e, E>(
+    ex: E,
+    user: User
+) -> Result<bool, CoreError>
+where
+    E: Executor<'e, Database = Sqlite>
+{
+    Ok(
+        sqlx::query!(
+            "
+SELECT 1 AS present
+FROM admins
+WHERE user_id = ?
+LIMIT 1
+            ",
+            user.0
+        )
+        .fetch_optional(ex)
+        .await?
+        .is_some()
+    )
+}
+
 pub async fn add_owner<'e, E>(
     ex: E,
     user: User,
@@ -120,9 +143,9 @@ where
 
     for username in &owners.users {
         // get user id of new owner
-        let owner = get_user_id(&mut *tx, username).await?;
+        let user = get_user_id(&mut *tx, username).await?;
         // associate new owner with the project
-        add_owner(&mut *tx, owner, proj).await?;
+        add_owner(&mut *tx, user, proj).await?;
     }
 
     tx.commit().await?;
@@ -165,9 +188,9 @@ where
 
     for username in &owners.users {
         // get user id of owner
-        let owner = get_user_id(&mut *tx, username).await?;
+        let user = get_user_id(&mut *tx, username).await?;
         // remove old owner from the project
-        remove_owner(&mut *tx, owner, proj).await?;
+        remove_owner(&mut *tx, user, proj).await?;
     }
 
     // prevent removal of last owner
@@ -244,6 +267,16 @@ mod test {
         assert!(!user_is_owner(&pool, User(2), Project(0)).await.unwrap());
     }
 
+    #[sqlx::test(fixtures("users", "admins"))]
+    async fn user_is_admin_true(pool: Pool) {
+        assert!(user_is_admin(&pool, User(3)).await.unwrap());
+    }
+
+    #[sqlx::test(fixtures("users", "admins"))]
+    async fn user_is_admin_false(pool: Pool) {
+        assert!(!user_is_admin(&pool, User(1)).await.unwrap());
+    }
+
     #[sqlx::test(fixtures("users", "projects", "one_owner"))]
     async fn add_owner_new(pool: Pool) {
         assert_eq!(
@@ -281,7 +314,7 @@ mod test {
         assert!(
             matches!(
                 add_owner(&pool, User(1), Project(0)).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         );
     }
@@ -292,7 +325,7 @@ mod test {
         assert!(
             matches!(
                 add_owner(&pool, User(0), Project(42)).await.unwrap_err(),
-                CoreError::DatabaseError(_)
+                CoreError::InvalidReference
             )
         )
     }
@@ -9,7 +9,7 @@ use std::{
 };
 use thiserror::Error;
 use tokio::{
-    fs::File,
+    fs::{self, File},
     io::{
         AsyncWrite,
         BufWriter
@@ -40,7 +40,7 @@ pub async fn stream_to_file<S>(
     uploads_directory: &str,
     path: &str,
     stream: S
-) -> Result<(), UploadError>
+) -> Result<u64, UploadError>
 where
     S: Stream<Item = Result<Bytes, io::Error>>,
 {
@@ -55,7 +55,7 @@ where
 pub async fn stream_to_writer<S, W>(
     stream: S,
     writer: W
-) -> Result<(), UploadError>
+) -> Result<u64, UploadError>
 where
     S: Stream<Item = Result<Bytes, io::Error>>,
     W: AsyncWrite
@@ -65,9 +65,7 @@ where
     futures::pin_mut!(reader);
     futures::pin_mut!(writer);
 
-    tokio::io::copy(&mut reader, &mut writer).await?;
-
-    Ok(())
+    Ok(tokio::io::copy(&mut reader, &mut writer).await?)
 }
 
 #[async_trait]
@@ -76,11 +74,18 @@ pub trait Uploader {
         &self,
         _filename: &str,
         _stream: S
-    ) -> Result<String, UploadError>
+    ) -> Result<(String, u64), UploadError>
     where
         S: Stream<Item = Result<Bytes, io::Error>> + Send;
+
+    async fn delete(
+        &self,
+        _url: &str
+    ) -> Result<(), UploadError>;
 }
 
+const LOCAL_UPLOADS_URL: &str = "http://localhost:3000/uploads/";
+
 pub struct LocalUploader {
     pub uploads_directory: String
 }
@@ -91,12 +96,31 @@ impl Uploader for LocalUploader {
         &self,
         filename: &str,
         stream: S
-    ) -> Result<String, UploadError>
+    ) -> Result<(String, u64), UploadError>
     where
         S: Stream<Item = Result<Bytes, io::Error>> + Send
     {
-        stream_to_file("uploads", filename, stream).await?;
+        let size = stream_to_file("uploads", filename, stream).await?;
+
+        Ok((format!("{LOCAL_UPLOADS_URL}{filename}"), size))
+    }
+
+    async fn delete(
+        &self,
+        url: &str
+    ) -> Result<(), UploadError>
+    {
+        // files not uploaded here are not ours to remove
+        let filename = match url.strip_prefix(LOCAL_UPLOADS_URL) {
+            Some(path) => require_filename(path)?,
+            None => return Ok(())
+        };
+
+        let path = Path::new(&self.uploads_directory).join(filename);
 
-        Ok(format!("http://localhost:3000/uploads/{filename}"))
+        match fs::remove_file(path).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(())
+        }
     }
 }
@@ -0,0 +1,509 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{GalleryItem, GameData, GameDataPatch, GamePatch, PackageDataPost, ProjectDataPatch, ProjectDataPost};
+
+// Limits are maximum lengths in characters, except for alternate_titles,
+// tags, designers, and gallery, which are maximum numbers of items
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
+#[serde(default)]
+pub struct Limits {
+    pub description: usize,
+    pub title: usize,
+    pub title_sort_key: usize,
+    pub alternate_titles: usize,
+    pub publisher: usize,
+    pub year: usize,
+    pub designer: usize,
+    pub designers: usize,
+    pub era: usize,
+    pub external_id: usize,
+    pub readme: usize,
+    pub tag: usize,
+    pub tags: usize,
+    pub caption: usize,
+    pub gallery: usize,
+    pub filename: usize,
+    pub package_description: usize
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            description: 1024,
+            title: 256,
+            title_sort_key: 256,
+            alternate_titles: 16,
+            publisher: 256,
+            year: 32,
+            designer: 256,
+            designers: 32,
+            era: 256,
+            external_id: 256,
+            readme: 65536,
+            tag: 64,
+            tags: 32,
+            caption: 1024,
+            gallery: 100,
+            filename: 255,
+            package_description: 1024
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FieldError {
+    pub field: String,
+    pub reason: String
+}
+
+impl FieldError {
+    fn new(field: &str, reason: impl Into<String>) -> Self {
+        FieldError { field: field.into(), reason: reason.into() }
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    errors: Vec<FieldError>
+}
+
+impl Validator {
+    fn max_len(&mut self, field: &str, value: &str, max: usize) {
+        if value.chars().count() > max {
+            self.errors.push(
+                FieldError::new(field, format!("longer than {max} characters"))
+            );
+        }
+    }
+
+    fn non_empty(&mut self, field: &str, value: &str) {
+        if value.trim().is_empty() {
+            self.errors.push(FieldError::new(field, "empty"));
+        }
+    }
+
+    fn max_items<T>(&mut self, field: &str, value: &[T], max: usize) {
+        if value.len() > max {
+            self.errors.push(
+                FieldError::new(field, format!("more than {max} items"))
+            );
+        }
+    }
+
+    fn filename(&mut self, field: &str, value: &str, limits: &Limits) {
+        if value.is_empty() {
+            self.errors.push(FieldError::new(field, "empty"));
+        }
+        else if value == "." || value == ".." ||
+            value.contains(['/', '\\']) ||
+            value.contains(char::is_control)
+        {
+            self.errors.push(FieldError::new(field, "not a valid filename"));
+        }
+        self.max_len(field, value, limits.filename);
+    }
+
+    fn tags(&mut self, tags: &[String], limits: &Limits) {
+        self.max_items("tags", tags, limits.tags);
+        for (i, tag) in tags.iter().enumerate() {
+            let field = format!("tags[{i}]");
+            self.non_empty(&field, tag);
+            self.max_len(&field, tag, limits.tag);
+        }
+    }
+
+    fn gallery(&mut self, gallery: &[GalleryItem], limits: &Limits) {
+        self.max_items("gallery", gallery, limits.gallery);
+        for (i, item) in gallery.iter().enumerate() {
+            self.filename(
+                &format!("gallery[{i}].filename"),
+                &item.filename,
+                limits
+            );
+            self.max_len(
+                &format!("gallery[{i}].caption"),
+                &item.caption,
+                limits.caption
+            );
+        }
+    }
+
+    fn positive(&mut self, field: &str, value: Option<i64>) {
+        if value.is_some_and(|v| v < 1) {
+            self.errors.push(FieldError::new(field, "less than 1"));
+        }
+    }
+
+    fn alternate_titles(&mut self, titles: &[String], limits: &Limits) {
+        self.max_items("game.alternate_titles", titles, limits.alternate_titles);
+        for (i, title) in titles.iter().enumerate() {
+            let field = format!("game.alternate_titles[{i}]");
+            self.non_empty(&field, title);
+            self.max_len(&field, title, limits.title);
+        }
+    }
+
+    fn designers(&mut self, designers: &[String], limits: &Limits) {
+        self.max_items("game.designers", designers, limits.designers);
+        for (i, designer) in designers.iter().enumerate() {
+            let field = format!("game.designers[{i}]");
+            self.non_empty(&field, designer);
+            self.max_len(&field, designer, limits.designer);
+        }
+    }
+
+    fn players(&mut self, min: Option<i64>, max: Option<i64>) {
+        self.positive("game.min_players", min);
+        self.positive("game.max_players", max);
+        if let (Some(min), Some(max)) = (min, max) {
+            if max < min {
+                self.errors.push(
+                    FieldError::new("game.max_players", "less than min_players")
+                );
+            }
+        }
+    }
+
+    fn game(&mut self, game: &GameData, limits: &Limits) {
+        self.non_empty("game.title", &game.title);
+        self.max_len("game.title", &game.title, limits.title);
+        self.max_len(
+            "game.title_sort_key",
+            &game.title_sort_key,
+            limits.title_sort_key
+        );
+        self.max_len("game.publisher", &game.publisher, limits.publisher);
+        self.max_len("game.year", &game.year, limits.year);
+        self.alternate_titles(&game.alternate_titles, limits);
+        self.designers(&game.designers, limits);
+        self.players(game.min_players, game.max_players);
+        self.positive("game.playing_time", game.playing_time);
+        if let Some(era) = &game.era {
+            self.max_len("game.era", era, limits.era);
+        }
+        if let Some(external_id) = &game.external_id {
+            self.max_len("game.external_id", external_id, limits.external_id);
+        }
+    }
+
+    fn game_patch(&mut self, game: &GameDataPatch, limits: &Limits) {
+        if let Some(title) = &game.title {
+            self.non_empty("game.title", title);
+            self.max_len("game.title", title, limits.title);
+        }
+        if let Some(title_sort_key) = &game.title_sort_key {
+            self.max_len(
+                "game.title_sort_key",
+                title_sort_key,
+                limits.title_sort_key
+            );
+        }
+        if let Some(publisher) = &game.publisher {
+            self.max_len("game.publisher", publisher, limits.publisher);
+        }
+        if let Some(year) = &game.year {
+            self.max_len("game.year", year, limits.year);
+        }
+        if let Some(alternate_titles) = &game.alternate_titles {
+            self.alternate_titles(alternate_titles, limits);
+        }
+        if let Some(designers) = &game.designers {
+            self.designers(designers, limits);
+        }
+        self.players(game.min_players.flatten(), game.max_players.flatten());
+        self.positive("game.playing_time", game.playing_time.flatten());
+        if let Some(Some(era)) = &game.era {
+            self.max_len("game.era", era, limits.era);
+        }
+        if let Some(Some(external_id)) = &game.external_id {
+            self.max_len("game.external_id", external_id, limits.external_id);
+        }
+    }
+
+    fn finish(self) -> Result<(), Vec<FieldError>> {
+        match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(self.errors)
+        }
+    }
+}
+
+pub trait Validate {
+    fn validate(&self, limits: &Limits) -> Result<(), Vec<FieldError>>;
+}
+
+impl Validate for ProjectDataPost {
+    fn validate(&self, limits: &Limits) -> Result<(), Vec<FieldError>> {
+        let mut v = Validator::default();
+        v.max_len("description", &self.description, limits.description);
+        v.tags(&self.tags, limits);
+        v.game(&self.game, limits);
+        v.max_len("readme", &self.readme, limits.readme);
+        if let Some(image) = &self.image {
+            v.filename("image", image, limits);
+        }
+        v.gallery(&self.gallery, limits);
+        v.finish()
+    }
+}
+
+impl Validate for ProjectDataPatch {
+    fn validate(&self, limits: &Limits) -> Result<(), Vec<FieldError>> {
+        let mut v = Validator::default();
+        if let Some(description) = &self.description {
+            v.max_len("description", description, limits.description);
+        }
+        if let Some(tags) = &self.tags {
+            v.tags(tags, limits);
+        }
+        v.game_patch(&self.game, limits);
+        if let Some(readme) = &self.readme {
+            v.max_len("readme", readme, limits.readme);
+        }
+        if let Some(Some(image)) = &self.image {
+            v.filename("image", image, limits);
+        }
+        if let Some(gallery) = &self.gallery {
+            v.gallery(gallery, limits);
+        }
+        v.finish()
+    }
+}
+
+impl Validate for PackageDataPost {
+    fn validate(&self, limits: &Limits) -> Result<(), Vec<FieldError>> {
+        let mut v = Validator::default();
+        v.max_len(
+            "description",
+            &self.description,
+            limits.package_description
+        );
+        v.finish()
+    }
+}
+
+impl Validate for GamePatch {
+    fn validate(&self, limits: &Limits) -> Result<(), Vec<FieldError>> {
+        let mut v = Validator::default();
+        if let Some(title) = &self.title {
+            v.non_empty("title", title);
+            v.max_len("title", title, limits.title);
+        }
+        if let Some(title_sort_key) = &self.title_sort_key {
+            v.max_len("title_sort_key", title_sort_key, limits.title_sort_key);
+        }
+        if let Some(publisher) = &self.publisher {
+            v.max_len("publisher", publisher, limits.publisher);
+        }
+        if let Some(year) = &self.year {
+            v.max_len("year", year, limits.year);
+        }
+        v.finish()
+    }
+}
+
+pub fn validate_filename(
+    field: &str,
+    filename: &str,
+    limits: &Limits
+) -> Result<(), Vec<FieldError>>
+{
+    let mut v = Validator::default();
+    v.filename(field, filename, limits);
+    v.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn project_data_post() -> ProjectDataPost {
+        ProjectDataPost {
+            description: "A test game".into(),
+            tags: vec!["a".into(), "b".into()],
+            game: GameData {
+                id: None,
+                title: "A Game of Tests".into(),
+                title_sort_key: "Game of Tests, A".into(),
+                publisher: "Test Game Company".into(),
+                year: "1978".into(),
+                ..Default::default()
+            },
+            readme: "".into(),
+            image: Some("img.png".into()),
+            gallery: vec![
+                GalleryItem {
+                    filename: "img.png".into(),
+                    caption: "".into()
+                }
+            ]
+        }
+    }
+
+    #[test]
+    fn validate_project_data_post_ok() {
+        assert_eq!(project_data_post().validate(&Limits::default()), Ok(()));
+    }
+
+    #[test]
+    fn validate_project_data_post_errors() {
+        let mut pd = project_data_post();
+        pd.description = "x".repeat(1025);
+        pd.game.title = " ".into();
+        pd.tags.push("".into());
+        pd.gallery[0].filename = "../img.png".into();
+
+        assert_eq!(
+            pd.validate(&Limits::default()),
+            Err(vec![
+                FieldError::new("description", "longer than 1024 characters"),
+                FieldError::new("tags[2]", "empty"),
+                FieldError::new("game.title", "empty"),
+                FieldError::new("gallery[0].filename", "not a valid filename")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_project_data_post_limits() {
+        let limits = Limits {
+            tags: 1,
+            readme: 2,
+            ..Default::default()
+        };
+
+        let mut pd = project_data_post();
+        pd.readme = "abc".into();
+
+        assert_eq!(
+            pd.validate(&limits),
+            Err(vec![
+                FieldError::new("tags", "more than 1 items"),
+                FieldError::new("readme", "longer than 2 characters")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_project_data_patch_ok() {
+        assert_eq!(
+            ProjectDataPatch {
+                description: Some("new description".into()),
+                ..Default::default()
+            }.validate(&Limits::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_project_data_patch_errors() {
+        assert_eq!(
+            ProjectDataPatch {
+                game: GameDataPatch {
+                    title: Some("".into()),
+                    year: Some("1".repeat(33)),
+                    ..Default::default()
+                },
+                image: Some(Some("a/b.png".into())),
+                ..Default::default()
+            }.validate(&Limits::default()),
+            Err(vec![
+                FieldError::new("game.title", "empty"),
+                FieldError::new("game.year", "longer than 32 characters"),
+                FieldError::new("image", "not a valid filename")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_project_data_post_game_metadata() {
+        let mut pd = project_data_post();
+        pd.game.alternate_titles = vec!["EiA".into(), "x".repeat(257)];
+        pd.game.designers = vec!["Jim Dunnigan".into(), "".into()];
+        pd.game.min_players = Some(3);
+        pd.game.max_players = Some(2);
+        pd.game.playing_time = Some(0);
+
+        assert_eq!(
+            pd.validate(&Limits::default()),
+            Err(vec![
+                FieldError::new(
+                    "game.alternate_titles[1]",
+                    "longer than 256 characters"
+                ),
+                FieldError::new("game.designers[1]", "empty"),
+                FieldError::new("game.max_players", "less than min_players"),
+                FieldError::new("game.playing_time", "less than 1")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_project_data_patch_game_metadata() {
+        assert_eq!(
+            ProjectDataPatch {
+                game: GameDataPatch {
+                    alternate_titles: Some(vec!["EiA".into(); 17]),
+                    min_players: Some(Some(0)),
+                    era: Some(Some("x".repeat(257))),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }.validate(&Limits::default()),
+            Err(vec![
+                FieldError::new("game.alternate_titles", "more than 16 items"),
+                FieldError::new("game.min_players", "less than 1"),
+                FieldError::new("game.era", "longer than 256 characters")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_package_data_post_errors() {
+        assert_eq!(
+            PackageDataPost { description: "x".repeat(1025) }
+                .validate(&Limits::default()),
+            Err(vec![
+                FieldError::new(
+                    "description",
+                    "longer than 1024 characters"
+                )
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_game_patch_errors() {
+        assert_eq!(
+            GamePatch {
+                title: Some(" ".into()),
+                year: Some("x".repeat(33)),
+                ..Default::default()
+            }.validate(&Limits::default()),
+            Err(vec![
+                FieldError::new("title", "empty"),
+                FieldError::new("year", "longer than 32 characters")
+            ])
+        );
+    }
+
+    #[test]
+    fn validate_filename_ok() {
+        assert_eq!(
+            validate_filename("img_name", "map.png", &Limits::default()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn validate_filename_errors() {
+        let limits = Limits::default();
+        assert!(validate_filename("img_name", "", &limits).is_err());
+        assert!(validate_filename("img_name", "..", &limits).is_err());
+        assert!(validate_filename("img_name", "a\\b", &limits).is_err());
+        assert!(validate_filename("img_name", "a\nb", &limits).is_err());
+        assert!(
+            validate_filename("img_name", &"x".repeat(256), &limits).is_err()
+        );
+    }
+}